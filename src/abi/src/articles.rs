@@ -1,10 +1,118 @@
-use wikiblocks_primitives::{Title, Script};
+use wikiblocks_primitives::{
+  Article, ArticleVersion, EditSummary, RateLimits, Title, Script, WikiblocksAddress,
+};
 
 #[derive(Clone, PartialEq, Eq, Debug, scale::Encode, scale::Decode, scale_info::TypeInfo)]
 #[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(all(feature = "std", feature = "serde"), derive(serde::Deserialize))]
 pub enum Call {
-  add_article { title: Title, script: Script },
-  add_version { title: Title, script: Script },
+  add_article {
+    title: Title,
+    summary: Option<EditSummary>,
+    script: Script,
+  },
+  add_version {
+    title: Title,
+    summary: Option<EditSummary>,
+    expected_last_version: ArticleVersion,
+    rebase: bool,
+    script: Script,
+  },
+  add_redirect {
+    from: Title,
+    to: Title,
+  },
+  move_article {
+    from: Title,
+    to: Title,
+    versions: u32,
+  },
+  tag {
+    title: Title,
+    category: Title,
+  },
+  untag {
+    title: Title,
+    category: Title,
+  },
+  revert {
+    title: Title,
+    to_version: ArticleVersion,
+  },
+  // the calls only governance may make are skipped
+  #[codec(index = 9)]
+  accept_version {
+    title: Title,
+    id: u32,
+  },
+  #[codec(index = 10)]
+  reject_version {
+    title: Title,
+    id: u32,
+  },
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, scale::Encode, scale::Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(all(feature = "std", feature = "serde"), derive(serde::Deserialize))]
+pub enum Event {
+  ArticleCreated {
+    article: Article,
+    author: WikiblocksAddress,
+  },
+  VersionAdded {
+    article: Article,
+    author: WikiblocksAddress,
+  },
+  RedirectAdded {
+    from: Title,
+    to: Title,
+    author: WikiblocksAddress,
+  },
+  ArticleMoved {
+    from: Title,
+    to: Title,
+    author: Option<WikiblocksAddress>,
+  },
+  ArticleTagged {
+    title: Title,
+    category: Title,
+    author: WikiblocksAddress,
+  },
+  ArticleUntagged {
+    title: Title,
+    category: Title,
+    author: WikiblocksAddress,
+  },
+  Reverted {
+    article: Article,
+    to: ArticleVersion,
+    author: WikiblocksAddress,
+  },
+  RateLimitsSet {
+    limits: RateLimits,
+  },
+  ProtectionSet {
+    title: Title,
+    protected: bool,
+  },
+  VersionPending {
+    title: Title,
+    id: u32,
+    author: WikiblocksAddress,
+  },
+  VersionAccepted {
+    article: Article,
+    id: u32,
+    author: WikiblocksAddress,
+    reviewer: WikiblocksAddress,
+  },
+  VersionRejected {
+    title: Title,
+    id: u32,
+    author: WikiblocksAddress,
+    reviewer: WikiblocksAddress,
+  },
 }
@@ -19,4 +19,6 @@ pub enum Event {
   Mint { to: WikiblocksAddress, amount: SubstrateAmount },
   Burn { from: WikiblocksAddress, amount: SubstrateAmount },
   Transfer { from: WikiblocksAddress, to: WikiblocksAddress, amount: SubstrateAmount },
+  Held { account: WikiblocksAddress, reason: [u8; 8], amount: SubstrateAmount },
+  Released { account: WikiblocksAddress, reason: [u8; 8], amount: SubstrateAmount },
 }
@@ -0,0 +1,22 @@
+use wikiblocks_primitives::{ArticleVersion, CommentBody, CommentId, Title, WikiblocksAddress};
+
+#[derive(Clone, PartialEq, Eq, Debug, scale::Encode, scale::Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(all(feature = "std", feature = "serde"), derive(serde::Deserialize))]
+pub enum Call {
+  post_comment {
+    title: Title,
+    version: Option<ArticleVersion>,
+    parent: Option<CommentId>,
+    body: CommentBody,
+  },
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, scale::Encode, scale::Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(all(feature = "std", feature = "serde"), derive(serde::Deserialize))]
+pub enum Event {
+  CommentPosted { title: Title, id: CommentId, author: WikiblocksAddress },
+}
@@ -13,15 +13,18 @@ pub mod timestamp;
 
 pub mod coins;
 pub mod validator_sets;
+pub mod emissions;
 
 pub mod articles;
 pub mod votes;
+pub mod discussions;
 
 pub mod babe;
 pub mod grandpa;
 
 pub mod tx;
 
+// Variants are only ever appended, as their indices are part of every signed transaction.
 #[derive(Clone, PartialEq, Eq, Debug, scale::Encode, scale::Decode, scale_info::TypeInfo)]
 pub enum Call {
   Timestamp(timestamp::Call),
@@ -31,6 +34,8 @@ pub enum Call {
   Votes(votes::Call),
   Babe(babe::Call),
   Grandpa(grandpa::Call),
+  Discussions(discussions::Call),
+  Emissions(emissions::Call),
 }
 
 // TODO: Remove this
@@ -46,10 +51,12 @@ pub enum Event {
   TransactionPayment(TransactionPaymentEvent),
   Coins(coins::Event),
   ValidatorSets(validator_sets::Event),
-  Articles,
-  Votes,
+  Emissions(emissions::Event),
+  Articles(articles::Event),
+  Votes(votes::Event),
   Babe,
   Grandpa(grandpa::Event),
+  Discussions(discussions::Event),
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, scale::Encode, scale::Decode, scale_info::TypeInfo)]
@@ -1,4 +1,6 @@
-use wikiblocks_primitives::Article;
+use wikiblocks_primitives::{
+  Article, ArticleVersion, Conviction, SubstrateAmount, Title, Vote, WikiblocksAddress,
+};
 
 #[derive(Clone, PartialEq, Eq, Debug, scale::Encode, scale::Decode, scale_info::TypeInfo)]
 #[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
@@ -6,4 +8,20 @@ use wikiblocks_primitives::Article;
 #[cfg_attr(all(feature = "std", feature = "serde"), derive(serde::Deserialize))]
 pub enum Call {
   upvote { article: Article },
+  retract_vote { article: Article },
+  downvote { article: Article },
+  vote_with_stake { article: Article, vote: Vote, amount: SubstrateAmount, conviction: Conviction },
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, scale::Encode, scale::Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(all(feature = "std", feature = "serde"), derive(serde::Deserialize))]
+pub enum Event {
+  Upvoted { article: Article, voter: WikiblocksAddress },
+  Downvoted { article: Article, voter: WikiblocksAddress },
+  VoteRetracted { article: Article, voter: WikiblocksAddress },
+  StakeLocked { voter: WikiblocksAddress, amount: SubstrateAmount, weight: u64, until: u64 },
+  StakeUnlocked { voter: WikiblocksAddress, amount: SubstrateAmount },
+  CanonicalVersionChanged { title: Title, version: Option<ArticleVersion> },
 }
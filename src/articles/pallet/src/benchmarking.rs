@@ -0,0 +1,308 @@
+use super::*;
+
+use scale::Encode;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+use sp_core::sr25519::Public;
+use sp_std::vec;
+
+use wikiblocks_primitives::{
+  Article, ArticleVersion, Body, Direction, OpCode, RateLimits, Script, Title, MAX_BODY_LEN,
+  MAX_DATA_LEN, MAX_ARTICLE_CATEGORIES, MAX_REBASE_DEPTH, MAX_REDIRECT_DEPTH, MAX_RENDER_DEPTH,
+  MAX_SCRIPT_LEN, MAX_TITLE_LEN,
+};
+
+// Canonicalizing a title and computing its skeleton takes time linear to its length, so every
+// benchmark uses the longest title possible.
+fn title() -> Title {
+  Title::new(vec![b'a'; usize::try_from(MAX_TITLE_LEN).unwrap()]).unwrap()
+}
+
+// A title of the same length as `title()`, distinct for every `i`.
+fn other_title(i: u32) -> Title {
+  let mut data = vec![b'b'; usize::try_from(MAX_TITLE_LEN - 8).unwrap()];
+  for byte in i.to_be_bytes() {
+    data.extend([b'a' + (byte >> 4), b'a' + (byte & 0xf)]);
+  }
+  Title::new(data).unwrap()
+}
+
+// An article with a version pending review, and a reviewer who isn't its author.
+fn pending_version<T: Config>() -> Public {
+  let author: Public = whitelisted_caller();
+  Pallet::<T>::add_article(RawOrigin::Signed(author).into(), title(), None, add_script(1)).unwrap();
+  Pallet::<T>::set_protected(RawOrigin::Root.into(), title(), true).unwrap();
+  Pallet::<T>::add_version(
+    RawOrigin::Signed(author).into(),
+    title(),
+    None,
+    ArticleVersion(0),
+    false,
+    add_script(1),
+  )
+  .unwrap();
+
+  T::BenchmarkHelper::reviewer()
+}
+
+fn add_script(len: u32) -> Script {
+  Script::new(vec![OpCode::Add(Body::new(vec![b'a'; usize::try_from(len).unwrap()]).unwrap())])
+    .unwrap()
+}
+
+#[benchmarks]
+mod benchmarks {
+  use super::*;
+
+  #[benchmark]
+  fn add_article(l: Linear<1, { MAX_BODY_LEN }>) {
+    let caller: Public = whitelisted_caller();
+    // the extrinsic the script is indexed along with is hashed, and is at least as long as it
+    frame_system::Pallet::<T>::note_extrinsic(add_script(l).encode());
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), title(), None, add_script(l));
+
+    assert_eq!(Pallet::<T>::last_version(title()), Some(ArticleVersion(0)));
+  }
+
+  // Titles are looked up in the `LastVersion` map, so the amount of titles doesn't matter. The
+  // cost of a version is the data it adds and the versions it references, each of which has its
+  // length read. Versions may only reference `MAX_RENDER_DEPTH` distinct versions, as each of them
+  // is replayed to render it.
+  #[benchmark]
+  fn add_version(
+    l: Linear<1, { MAX_DATA_LEN - (8 * MAX_SCRIPT_LEN) }>,
+    o: Linear<1, { MAX_SCRIPT_LEN }>,
+  ) {
+    let caller: Public = whitelisted_caller();
+    Pallet::<T>::add_article(RawOrigin::Signed(caller).into(), title(), None, add_script(1))
+      .unwrap();
+
+    // make versions for every reference to read the length of
+    for version in 1 ..= o {
+      BodyLengths::<T>::set(Article::new(title(), ArticleVersion(version)), Some(1));
+    }
+    LastVersion::<T>::set(title(), Some(ArticleVersion(o)));
+
+    let mut opcodes = add_script(l).consume();
+    opcodes.extend((1 .. o).map(|i| OpCode::Reference(ArticleVersion(1 + (i % MAX_RENDER_DEPTH)))));
+    let script = Script::new(opcodes).unwrap();
+    frame_system::Pallet::<T>::note_extrinsic(script.encode());
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), title(), None, ArticleVersion(o), false, script);
+
+    assert_eq!(Pallet::<T>::last_version(title()), Some(ArticleVersion(o + 1)));
+  }
+
+  // The worst case is a target `MAX_REDIRECT_DEPTH - 1` redirects away from its article.
+  #[benchmark]
+  fn add_redirect() {
+    let caller: Public = whitelisted_caller();
+    Pallet::<T>::add_article(RawOrigin::Signed(caller).into(), title(), None, add_script(1))
+      .unwrap();
+
+    let mut target = title();
+    for i in 1 .. MAX_REDIRECT_DEPTH {
+      Redirects::<T>::set(other_title(i), Some(target));
+      target = other_title(i);
+    }
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), other_title(0), target);
+
+    assert_eq!(Pallet::<T>::redirect(other_title(0)), Some(title()));
+  }
+
+  // Every version is moved, along with its checkpoint.
+  #[benchmark]
+  fn move_article(v: Linear<1, 1_000>) {
+    let caller: Public = whitelisted_caller();
+    Pallet::<T>::add_article(RawOrigin::Signed(caller).into(), title(), None, add_script(1))
+      .unwrap();
+
+    for i in 0 .. MAX_ARTICLE_CATEGORIES {
+      ArticleCategories::<T>::insert(title(), other_title(i), ());
+      Categories::<T>::insert(other_title(i), title(), ());
+    }
+    for version in 1 .. v {
+      let article = Article::new(title(), ArticleVersion(version));
+      Articles::<T>::set(&article, Some(add_script(1)));
+      ScriptHashes::<T>::set(&article, Some(([0; 32], 1)));
+      BodyLengths::<T>::set(&article, Some(1));
+      Checkpoints::<T>::set(&article, Some(add_script(1)));
+      Authors::<T>::set(&article, Some(caller));
+    }
+    LastVersion::<T>::set(title(), Some(ArticleVersion(v - 1)));
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), title(), other_title(0), v);
+
+    assert_eq!(Pallet::<T>::last_version(other_title(0)), Some(ArticleVersion(v - 1)));
+  }
+
+  // The article is reached through the longest chain of redirects, and already has every other
+  // category it may have.
+  #[benchmark]
+  fn tag() {
+    let caller: Public = whitelisted_caller();
+    Pallet::<T>::add_article(RawOrigin::Signed(caller).into(), title(), None, add_script(1))
+      .unwrap();
+
+    let mut target = title();
+    for i in 1 ..= MAX_REDIRECT_DEPTH {
+      Redirects::<T>::set(other_title(i), Some(target));
+      target = other_title(i);
+    }
+    for i in 1 .. MAX_ARTICLE_CATEGORIES {
+      ArticleCategories::<T>::insert(title(), other_title(i), ());
+    }
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), target, other_title(0));
+
+    assert_eq!(Pallet::<T>::category_size(other_title(0)), 1);
+  }
+
+  #[benchmark]
+  fn untag() {
+    let caller: Public = whitelisted_caller();
+    Pallet::<T>::add_article(RawOrigin::Signed(caller).into(), title(), None, add_script(1))
+      .unwrap();
+
+    let mut target = title();
+    for i in 1 ..= MAX_REDIRECT_DEPTH {
+      Redirects::<T>::set(other_title(i), Some(target));
+      target = other_title(i);
+    }
+    Pallet::<T>::tag(RawOrigin::Signed(caller).into(), title(), other_title(0)).unwrap();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), target, other_title(0));
+
+    assert_eq!(Pallet::<T>::category_size(other_title(0)), 0);
+  }
+
+  // Reverting to a version `MAX_RENDER_DEPTH` deep copies its script, so the benchmark reverts to
+  // one with the longest script. Reverting to a version without a stored length is costlier, yet
+  // limited to versions from before lengths were stored, so the version has one.
+  #[benchmark]
+  fn revert() {
+    let caller: Public = whitelisted_caller();
+    Pallet::<T>::add_article(RawOrigin::Signed(caller).into(), title(), None, add_script(1))
+      .unwrap();
+
+    let article = Article::new(title(), ArticleVersion(1));
+    let len = MAX_DATA_LEN - (8 * MAX_SCRIPT_LEN);
+    Articles::<T>::set(&article, Some(add_script(len)));
+    BodyLengths::<T>::set(&article, Some(len));
+    RenderDepths::<T>::set(&article, MAX_RENDER_DEPTH);
+    LastVersion::<T>::set(title(), Some(ArticleVersion(1)));
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), title(), ArticleVersion(1));
+
+    assert_eq!(
+      Pallet::<T>::articles(Article::new(title(), ArticleVersion(2))),
+      Some(add_script(len))
+    );
+  }
+
+  // The script is rebased over `MAX_REBASE_DEPTH` versions of the longest body, every one of them
+  // replacing its first character while the script replaces its last.
+  #[benchmark]
+  fn rebase() {
+    let caller: Public = whitelisted_caller();
+    Pallet::<T>::add_article(
+      RawOrigin::Signed(caller).into(),
+      title(),
+      None,
+      add_script(MAX_BODY_LEN),
+    )
+    .unwrap();
+
+    let edit = |version, at| {
+      let mut opcodes = vec![OpCode::Reference(ArticleVersion(version))];
+      if at != 0 {
+        opcodes.extend([OpCode::Cp(at), OpCode::MvCr(Direction::Right, at)]);
+      }
+      opcodes.extend(add_script(1).consume());
+      opcodes.push(OpCode::Del(1));
+      if at != (MAX_BODY_LEN - 1) {
+        opcodes.push(OpCode::Cp(MAX_BODY_LEN - 1 - at));
+      }
+      Script::new(opcodes).unwrap()
+    };
+    for version in 0 .. MAX_REBASE_DEPTH {
+      Pallet::<T>::add_version(
+        RawOrigin::Signed(caller).into(),
+        title(),
+        None,
+        ArticleVersion(version),
+        false,
+        edit(version, 0),
+      )
+      .unwrap();
+    }
+    let script = edit(0, MAX_BODY_LEN - 1);
+
+    #[block]
+    {
+      assert!(Pallet::<T>::rebase(
+        &title(),
+        script,
+        ArticleVersion(0),
+        ArticleVersion(MAX_REBASE_DEPTH)
+      )
+      .is_ok());
+    }
+  }
+
+  #[benchmark]
+  fn set_rate_limits() {
+    let limits = RateLimits { versions_per_title: 1, title_window: 1, edits_per_session: 1 };
+
+    #[extrinsic_call]
+    _(RawOrigin::Root, limits);
+
+    assert_eq!(Pallet::<T>::rate_limits(), limits);
+  }
+
+  #[benchmark]
+  fn set_protected() {
+    let caller: Public = whitelisted_caller();
+    Pallet::<T>::add_article(RawOrigin::Signed(caller).into(), title(), None, add_script(1))
+      .unwrap();
+
+    #[extrinsic_call]
+    _(RawOrigin::Root, title(), true);
+
+    assert!(Pallet::<T>::protected(title()).is_some());
+  }
+
+  // Accepts the shortest version possible, as adding the version is weighed by `add_version`.
+  #[benchmark]
+  fn accept_version() {
+    let reviewer = pending_version::<T>();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(reviewer), title(), 0);
+
+    assert_eq!(Pallet::<T>::last_version(title()), Some(ArticleVersion(1)));
+  }
+
+  #[benchmark]
+  fn reject_version() {
+    let reviewer = pending_version::<T>();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(reviewer), title(), 0);
+
+    assert_eq!(Pallet::<T>::pending_count(title()), 0);
+  }
+
+  impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
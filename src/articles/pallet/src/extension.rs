@@ -0,0 +1,85 @@
+use core::marker::PhantomData;
+
+use scale::{Encode, Decode};
+use scale_info::TypeInfo;
+
+use frame_support::traits::IsSubType;
+
+use sp_runtime::{
+  traits::{DispatchInfoOf, SignedExtension},
+  transaction_validity::{
+    InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+  },
+};
+
+use crate::{Call, Config, Pallet};
+
+/// The custom `InvalidTransaction` code of transactions over the rate limits.
+pub const RATE_LIMITED: u8 = 1;
+
+/// Rejects transactions adding versions over the rate limits of the Articles pallet when they're
+/// about to be included, so they're dropped from the transaction pool rather than included to fail.
+///
+/// The limits are checked in `pre_dispatch`, against the counts of the block being built, rather
+/// than when transactions are validated, so the pool isn't throttled to one transaction per account
+/// and per title.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckRateLimits<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckRateLimits<T> {
+  pub fn new() -> Self {
+    Self(PhantomData)
+  }
+}
+
+impl<T: Config + Send + Sync> Default for CheckRateLimits<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Config + Send + Sync> core::fmt::Debug for CheckRateLimits<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    write!(f, "CheckRateLimits")
+  }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckRateLimits<T>
+where
+  T::RuntimeCall: IsSubType<Call<T>>,
+{
+  const IDENTIFIER: &'static str = "CheckRateLimits";
+  type AccountId = T::AccountId;
+  type Call = T::RuntimeCall;
+  type AdditionalSigned = ();
+  type Pre = ();
+
+  fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+    Ok(())
+  }
+
+  fn validate(
+    &self,
+    _: &Self::AccountId,
+    _: &Self::Call,
+    _: &DispatchInfoOf<Self::Call>,
+    _: usize,
+  ) -> TransactionValidity {
+    Ok(ValidTransaction::default())
+  }
+
+  fn pre_dispatch(
+    self,
+    who: &Self::AccountId,
+    call: &Self::Call,
+    _: &DispatchInfoOf<Self::Call>,
+    _: usize,
+  ) -> Result<(), TransactionValidityError> {
+    if let Some(call) = call.is_sub_type() {
+      Pallet::<T>::check_rate_limits(who, call)
+        .map_err(|_| InvalidTransaction::Custom(RATE_LIMITED))?;
+    }
+    Ok(())
+  }
+}
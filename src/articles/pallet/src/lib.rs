@@ -6,20 +6,140 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+mod migrations;
+pub use migrations::MigrationCursor;
+
+mod extension;
+pub use extension::{CheckRateLimits, RATE_LIMITED};
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+use frame_support::weights::Weight;
+
+use wikiblocks_primitives::{Article, ArticleVersion, Title};
+
+/// Handles an article being moved to another title, for pallets storing data per version.
+pub trait OnArticleMoved {
+  /// Called with the canonical titles once every version of the article was moved.
+  fn on_article_moved(from: &Title, to: &Title, last_version: ArticleVersion);
+
+  /// The weight of moving an article with the given amount of versions.
+  fn weight(versions: u32) -> Weight;
+}
+
+impl OnArticleMoved for () {
+  fn on_article_moved(_: &Title, _: &Title, _: ArticleVersion) {}
+
+  fn weight(_: u32) -> Weight {
+    Weight::zero()
+  }
+}
+
+/// Handles a version being added to an article, for pallets depending on its latest versions.
+pub trait OnVersionAdded {
+  /// Called with the new version, under the canonical title, once it was added.
+  fn on_version_added(article: &Article);
+
+  /// The most handling a new version may weigh.
+  fn weight() -> Weight;
+}
+
+impl OnVersionAdded for () {
+  fn on_version_added(_: &Article) {}
+
+  fn weight() -> Weight {
+    Weight::zero()
+  }
+}
+
+/// Provides an account which may review versions, for benchmarks.
+///
+/// It's implemented by the `Config::Reviewers` type, as only it knows who reviews.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper {
+  /// Returns an account `Config::Reviewers` contains, setting up whatever it's a reviewer for.
+  fn reviewer() -> sp_core::sr25519::Public;
+}
+
+// Every account reviews, so any one does.
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper for frame_support::traits::Everything {
+  fn reviewer() -> sp_core::sr25519::Public {
+    frame_benchmarking::account("reviewer", 0, 0)
+  }
+}
+
 #[allow(clippy::cast_possible_truncation)]
 #[frame_support::pallet]
 pub mod pallet {
   use frame_system::pallet_prelude::*;
-  use frame_support::pallet_prelude::*;
+  use frame_support::{
+    pallet_prelude::*,
+    traits::{Contains, UnixTime},
+  };
 
   use sp_core::sr25519::Public;
-  use sp_std::vec;
+  use sp_io::hashing::blake2_256;
+  use sp_runtime::SaturatedConversion;
+  use sp_std::{vec, vec::Vec};
 
-  use wikiblocks_primitives::{ArticleVersion, OpCode, Script, Title, Article, MAX_DATA_LEN};
+  use super::{OnArticleMoved, OnVersionAdded, WeightInfo};
+
+  use wikiblocks_primitives::{
+    ArticleVersion, CursorUnit, EditSummary, OpCode, PendingVersion, RateLimits, Script,
+    ScriptError, Title, Article, VersionInfo, unit_len, CHECKPOINT_INTERVAL,
+    MAX_ARTICLE_CATEGORIES, MAX_DATA_LEN, MAX_PAGE_LEN, MAX_PENDING_VERSIONS, MAX_REBASE_DEPTH,
+    MAX_REDIRECT_DEPTH, MAX_RENDER_DEPTH, MAX_SCRIPT_LEN,
+  };
 
   #[pallet::config]
   pub trait Config: frame_system::Config<AccountId = Public> {
     type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+    type WeightInfo: WeightInfo;
+    type OnArticleMoved: OnArticleMoved;
+    type OnVersionAdded: OnVersionAdded;
+    type Time: UnixTime;
+    /// The current session, which the versions an account adds are limited per.
+    type CurrentSession: Get<u32>;
+    /// The accounts with enough stake to review edits of protected articles.
+    type Reviewers: Contains<Public>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper: crate::BenchmarkHelper;
+  }
+
+  #[pallet::genesis_config]
+  #[derive(Clone, PartialEq, Eq, Debug, Encode, Decode)]
+  pub struct GenesisConfig<T: Config> {
+    pub rate_limits: RateLimits,
+    /// The titles whose versions are reviewed before they're added, which don't have to exist yet.
+    pub protected_titles: Vec<Title>,
+    pub _ignore: PhantomData<T>,
+  }
+
+  impl<T: Config> Default for GenesisConfig<T> {
+    fn default() -> Self {
+      GenesisConfig {
+        rate_limits: Default::default(),
+        protected_titles: Default::default(),
+        _ignore: Default::default(),
+      }
+    }
+  }
+
+  #[pallet::genesis_build]
+  impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+    fn build(&self) {
+      assert!(self.rate_limits.title_window != 0, "rate limits must have a title window");
+      EditRateLimits::<T>::set(self.rate_limits);
+      for title in &self.protected_titles {
+        let title = title.canonical().expect("protected title wasn't valid");
+        ProtectedTitles::<T>::set(&title, Some(()));
+      }
+    }
   }
 
   #[pallet::error]
@@ -31,45 +151,695 @@ pub mod pallet {
     StorageFull,
     TooManyVersions,
     ArticleTooBig,
+    CursorOutOfBounds,
+    EmptyArticle,
+    InvalidUtf8,
+    UnsupportedCursorUnit,
+    ConfusableTitle,
+    RedirectCycle,
+    TooManyRedirects,
+    InvalidVersionCount,
+    AlreadyTagged,
+    NotTagged,
+    TooManyCategories,
+    EditConflict,
+    TitleRateLimited,
+    AccountRateLimited,
+    InvalidRateLimits,
+    TooManyPendingVersions,
+    UnknownPendingVersion,
+    NotReviewer,
+    SelfReview,
+    HasPendingVersions,
+    RedirectAlreadyExist,
+    RenderTooDeep,
+    NotAuthor,
   }
 
   #[pallet::event]
-  pub enum Event<T: Config> {}
+  #[pallet::generate_deposit(fn deposit_event)]
+  pub enum Event<T: Config> {
+    ArticleCreated { article: Article, author: Public },
+    VersionAdded { article: Article, author: Public },
+    RedirectAdded { from: Title, to: Title, author: Public },
+    ArticleMoved { from: Title, to: Title, author: Option<Public> },
+    ArticleTagged { title: Title, category: Title, author: Public },
+    ArticleUntagged { title: Title, category: Title, author: Public },
+    Reverted { article: Article, to: ArticleVersion, author: Public },
+    RateLimitsSet { limits: RateLimits },
+    ProtectionSet { title: Title, protected: bool },
+    VersionPending { title: Title, id: u32, author: Public },
+    VersionAccepted { article: Article, id: u32, author: Public, reviewer: Public },
+    VersionRejected { title: Title, id: u32, author: Public, reviewer: Public },
+  }
+
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 
   #[pallet::pallet]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(PhantomData<T>);
 
+  /// The position of every canonical title in the order the articles were created in.
   #[pallet::storage]
-  #[pallet::getter(fn titles)]
-  pub type Titles<T: Config> = StorageValue<_, BoundedVec<Title, ConstU32<1_000_000>>, ValueQuery>;
+  #[pallet::getter(fn title_index)]
+  pub type Titles<T: Config> = StorageMap<_, Blake2_128Concat, Title, u32, OptionQuery>;
+
+  /// The canonical titles, keyed by their position in `Titles`.
+  #[pallet::storage]
+  pub type TitlesByIndex<T: Config> = StorageMap<_, Identity, u32, Title, OptionQuery>;
+
+  #[pallet::storage]
+  #[pallet::getter(fn title_count)]
+  pub type TitleCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+  /// The migration spread over several blocks in progress, if any, and how far it got.
+  ///
+  /// The storage is only partially migrated until it's done, so the runtime shouldn't dispatch
+  /// calls reading it in the meantime.
+  #[pallet::storage]
+  #[pallet::getter(fn migration)]
+  pub type Migration<T: Config> = StorageValue<_, crate::MigrationCursor, OptionQuery>;
 
   /// Stores the last article version. If this returns Let's say `ArticleVersion(5)` that means
   /// there are versions 0, 1, 2, 3, 4, 5 for the article.
+  ///
+  /// Articles are stored under the canonical form of their title (see `Title::canonical`), which
+  /// is also the title of every `Article` key.
   #[pallet::storage]
   #[pallet::getter(fn last_version)]
   pub type LastVersion<T: Config> =
     StorageMap<_, Blake2_128Concat, Title, ArticleVersion, OptionQuery>;
 
+  /// The title an article was created with, keyed by its canonical title.
+  #[pallet::storage]
+  #[pallet::getter(fn display_title)]
+  pub type DisplayTitles<T: Config> = StorageMap<_, Blake2_128Concat, Title, Title, OptionQuery>;
+
+  /// The canonical title using a given confusable skeleton, keyed by the hash of the skeleton.
+  #[pallet::storage]
+  #[pallet::getter(fn skeleton_title)]
+  pub type Skeletons<T: Config> = StorageMap<_, Identity, [u8; 32], Title, OptionQuery>;
+
+  /// The title a title without an article leads to, keyed by its canonical title.
+  ///
+  /// Redirects may lead to other redirects, such as when the article they lead to was moved, up to
+  /// `MAX_REDIRECT_DEPTH` redirects deep.
+  #[pallet::storage]
+  #[pallet::getter(fn redirect)]
+  pub type Redirects<T: Config> = StorageMap<_, Blake2_128Concat, Title, Title, OptionQuery>;
+
+  /// The most redirects followed to reach an article from a title redirecting to it, keyed by its
+  /// canonical title.
+  ///
+  /// Moving an article makes every redirect leading to it one redirect deeper, so articles already
+  /// `MAX_REDIRECT_DEPTH` redirects deep can't be moved.
+  #[pallet::storage]
+  #[pallet::getter(fn redirect_depth)]
+  pub type RedirectDepths<T: Config> = StorageMap<_, Blake2_128Concat, Title, u32, ValueQuery>;
+
+  /// The canonical titles of the articles in every category, keyed by the canonical category name.
+  #[pallet::storage]
+  pub type Categories<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, Title, Blake2_128Concat, Title, (), OptionQuery>;
+
+  /// The categories of every article, keyed by its canonical title.
+  #[pallet::storage]
+  pub type ArticleCategories<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, Title, Blake2_128Concat, Title, (), OptionQuery>;
+
+  /// The amount of articles in every category.
+  #[pallet::storage]
+  #[pallet::getter(fn category_size)]
+  pub type CategorySizes<T: Config> = StorageMap<_, Blake2_128Concat, Title, u32, ValueQuery>;
+
+  /// The scripts of the last `MAX_REBASE_DEPTH` versions of every article, which edits may be
+  /// rebased over, of the versions `MAX_RENDER_DEPTH` deep, which reverts copy, and of every
+  /// version whose script isn't indexed.
+  ///
+  /// The scripts of versions added by `add_article` and `add_version` are indexed along with
+  /// their extrinsic, under the hash in `ScriptHashes`, and are removed from here once they can't
+  /// be rebased over anymore. Scripts made by the runtime, for reverts, rebased edits and accepted
+  /// versions, aren't part of any extrinsic, so they're kept here, as are scripts from before
+  /// indexing.
   #[pallet::storage]
   #[pallet::getter(fn articles)]
   pub type Articles<T: Config> = StorageMap<_, Blake2_128Concat, Article, Script, OptionQuery>;
 
+  /// The blake2-256 hash and encoded length of the extrinsic which added every version whose
+  /// script is indexed along with it.
+  ///
+  /// The script isn't at either end of the extrinsic, so the whole extrinsic is indexed. Nodes
+  /// keeping the indexed data of blocks return it by its hash, the script being the one of its
+  /// call.
+  #[pallet::storage]
+  #[pallet::getter(fn script_hash)]
+  pub type ScriptHashes<T: Config> =
+    StorageMap<_, Blake2_128Concat, Article, ([u8; 32], u32), OptionQuery>;
+
+  /// The length of the rendered body of every version, in characters, letting new versions be
+  /// validated without rendering the versions they reference.
+  #[pallet::storage]
+  #[pallet::getter(fn body_len)]
+  pub type BodyLengths<T: Config> = StorageMap<_, Blake2_128Concat, Article, u32, OptionQuery>;
+
+  /// Full-text checkpoints of versions, which rendering starts from instead of replaying the
+  /// scripts the version is built on top of.
+  ///
+  /// A checkpoint was stored for every `CHECKPOINT_INTERVAL`th version, and for every version
+  /// which would otherwise replay more than `MAX_RENDER_DEPTH` scripts to render, until scripts
+  /// were indexed. The runtime can't render versions with indexed scripts, so versions which would
+  /// replay more than `MAX_RENDER_DEPTH` scripts are instead added as full text, which is stored
+  /// like any other script and is the checkpoint rendering starts from.
+  #[pallet::storage]
+  #[pallet::getter(fn checkpoint)]
+  pub type Checkpoints<T: Config> = StorageMap<_, Blake2_128Concat, Article, Script, OptionQuery>;
+
+  /// The amount of scripts replayed to render a version, not counting its own, which is zero for
+  /// versions with a checkpoint or without references.
+  ///
+  /// A version referenced by several versions in the chain is counted once per reference, making
+  /// this an upper bound.
+  ///
+  /// Versions may not be more than `MAX_RENDER_DEPTH` scripts deep.
+  #[pallet::storage]
+  #[pallet::getter(fn render_depth)]
+  pub type RenderDepths<T: Config> = StorageMap<_, Blake2_128Concat, Article, u32, ValueQuery>;
+
   #[pallet::storage]
   #[pallet::getter(fn authors)]
   pub type Authors<T: Config> = StorageMap<_, Blake2_128Concat, Article, Public, OptionQuery>;
 
+  /// The record of how every version came to be, for versions added since they were recorded.
+  #[pallet::storage]
+  #[pallet::getter(fn version_info)]
+  pub type VersionInfos<T: Config> =
+    StorageMap<_, Blake2_128Concat, Article, VersionInfo, OptionQuery>;
+
+  /// The amount of versions every account authored.
+  #[pallet::storage]
+  #[pallet::getter(fn contribution_count)]
+  pub type ContributionCounts<T: Config> = StorageMap<_, Blake2_128Concat, Public, u32, ValueQuery>;
+
+  /// The versions every account authored, in the order they were added, keyed by the account and
+  /// their position among its versions.
+  ///
+  /// Versions are stored with the position of their article in `Titles`, which is kept when the
+  /// article is moved, rather than with its title.
+  #[pallet::storage]
+  pub type AuthorContributions<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    Public,
+    Twox64Concat,
+    u32,
+    (u32, ArticleVersion),
+    OptionQuery,
+  >;
+
+  /// The limits on how often articles may be edited.
+  #[pallet::storage]
+  #[pallet::getter(fn rate_limits)]
+  pub type EditRateLimits<T: Config> = StorageValue<_, RateLimits, ValueQuery>;
+
+  /// The window of `RateLimits::title_window` blocks the versions of a title were last counted in,
+  /// and how many it got within it, keyed by the canonical title.
+  #[pallet::storage]
+  #[pallet::getter(fn title_edits)]
+  pub type TitleEdits<T: Config> = StorageMap<_, Blake2_128Concat, Title, (u64, u32), OptionQuery>;
+
+  /// The session the versions an account added were last counted in, and how many it added
+  /// within it.
+  #[pallet::storage]
+  #[pallet::getter(fn account_edits)]
+  pub type AccountEdits<T: Config> =
+    StorageMap<_, Blake2_128Concat, Public, (u32, u32), OptionQuery>;
+
+  /// The articles whose versions are reviewed before they're added, keyed by their canonical
+  /// title.
+  #[pallet::storage]
+  #[pallet::getter(fn protected)]
+  pub type ProtectedTitles<T: Config> = StorageMap<_, Blake2_128Concat, Title, (), OptionQuery>;
+
+  /// The versions of protected articles waiting for review, keyed by the canonical title and the
+  /// ID the version was submitted under.
+  #[pallet::storage]
+  #[pallet::getter(fn pending_version)]
+  pub type PendingVersions<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, Title, Identity, u32, PendingVersion, OptionQuery>;
+
+  /// The amount of versions of every article waiting for review.
+  #[pallet::storage]
+  #[pallet::getter(fn pending_count)]
+  pub type PendingCounts<T: Config> = StorageMap<_, Blake2_128Concat, Title, u32, ValueQuery>;
+
+  /// The ID the next version submitted for review of every article gets.
+  #[pallet::storage]
+  pub type NextPendingIds<T: Config> = StorageMap<_, Blake2_128Concat, Title, u32, ValueQuery>;
+
+  // The window a title's versions are counted in and their count, and the session an account's
+  // versions are counted in and their count.
+  pub(crate) type EditCounts = ((u64, u32), (u32, u32));
+
   impl<T: Config> Pallet<T> {
-    // TODO: this can be optimized to O(1) using maps
+    /// Returns if an article exists under the canonical form of the title.
     pub fn title_exist(title: &Title) -> bool {
-      Self::titles().iter().any(|t| t == title)
+      title.canonical().is_ok_and(LastVersion::<T>::contains_key)
+    }
+
+    /// Returns the canonical title of the article the title leads to, following its redirects.
+    pub fn resolve(title: &Title) -> Option<Title> {
+      let mut title = title.canonical().ok()?;
+      for _ in 0 .. MAX_REDIRECT_DEPTH {
+        if LastVersion::<T>::contains_key(&title) {
+          return Some(title);
+        }
+        title = Self::redirect(&title)?;
+      }
+      LastVersion::<T>::contains_key(&title).then_some(title)
+    }
+
+    /// Returns up to `limit` canonical titles, in the order the articles were created in, starting
+    /// with the title at position `start`.
+    ///
+    /// At most `MAX_PAGE_LEN` titles are returned.
+    pub fn titles(start: u32, limit: u32) -> Vec<Title> {
+      let end = start.saturating_add(limit.min(MAX_PAGE_LEN)).min(Self::title_count());
+      (start .. end).filter_map(TitlesByIndex::<T>::get).collect()
+    }
+
+    /// Returns up to `limit` canonical titles of the articles in a category, in storage order,
+    /// starting after the title `after`.
+    ///
+    /// At most `MAX_PAGE_LEN` titles are returned.
+    pub fn category_members(category: &Title, after: Option<Title>, limit: u32) -> Vec<Title> {
+      let Ok(category) = category.canonical() else { return vec![] };
+      let limit = usize::try_from(limit.min(MAX_PAGE_LEN)).unwrap();
+      match after {
+        Some(after) => {
+          let after = Categories::<T>::hashed_key_for(&category, after);
+          Categories::<T>::iter_key_prefix_from(&category, after).take(limit).collect()
+        }
+        None => Categories::<T>::iter_key_prefix(&category).take(limit).collect(),
+      }
+    }
+
+    /// Returns the canonical names of the categories of an article, following redirects.
+    pub fn categories(title: &Title) -> Vec<Title> {
+      match Self::resolve(title) {
+        Some(title) => ArticleCategories::<T>::iter_key_prefix(title).collect(),
+        None => vec![],
+      }
+    }
+
+    /// Returns the IDs of the versions of an article waiting for review, in the order they were
+    /// submitted in.
+    pub fn pending_versions(title: &Title) -> Vec<u32> {
+      let Ok(title) = title.canonical() else { return vec![] };
+      let mut ids = PendingVersions::<T>::iter_key_prefix(&title).collect::<Vec<_>>();
+      ids.sort_unstable();
+      ids
+    }
+
+    /// Returns if an account may review the versions of protected articles, for its stake.
+    ///
+    /// Authoring versions doesn't make an account a reviewer, as accounts are free to create.
+    pub fn is_reviewer(account: &Public) -> bool {
+      T::Reviewers::contains(account)
+    }
+
+    /// Returns up to `limit` versions an account authored, in the order they were added, starting
+    /// with the version at position `start` among them.
+    ///
+    /// Versions are returned under the current title of their article. At most `MAX_PAGE_LEN`
+    /// versions are returned.
+    pub fn contributions(account: Public, start: u32, limit: u32) -> Vec<Article> {
+      let end =
+        start.saturating_add(limit.min(MAX_PAGE_LEN)).min(Self::contribution_count(account));
+      (start .. end)
+        .filter_map(|i| {
+          let (index, version) = AuthorContributions::<T>::get(account, i)?;
+          Some(Article::new(TitlesByIndex::<T>::get(index)?, version))
+        })
+        .collect()
+    }
+
+    // Appends the version to the contributions of its author.
+    pub(crate) fn index_contribution(article: &Article, author: Public) -> Result<(), Error<T>> {
+      let index = Self::title_index(article.title()).ok_or(Error::<T>::InvalidTitle)?;
+      let i = Self::contribution_count(author);
+      ContributionCounts::<T>::set(author, i.checked_add(1).ok_or(Error::<T>::StorageFull)?);
+      AuthorContributions::<T>::set(author, i, Some((index, article.version())));
+      Ok(())
+    }
+
+    // Appends the canonical title to the index.
+    pub(crate) fn index_title(title: &Title) -> Result<(), Error<T>> {
+      let index = Self::title_count();
+      TitleCount::<T>::set(index.checked_add(1).ok_or(Error::<T>::StorageFull)?);
+      Titles::<T>::set(title, Some(index));
+      TitlesByIndex::<T>::set(index, Some(title.clone()));
+      Ok(())
+    }
+
+    /// The key of the confusable skeleton of a canonical title.
+    pub fn skeleton_key(title: &Title) -> [u8; 32] {
+      blake2_256(&title.skeleton().unwrap_or_default())
+    }
+
+    /// Renders the body of the given article version, which should use the canonical title.
+    ///
+    /// Rendering starts from the closest checkpoints rather than the first version. Versions built
+    /// on top of scripts which are only indexed can't be rendered.
+    pub fn render(article: &Article) -> Result<Vec<u8>, ScriptError> {
+      wikiblocks_primitives::render(article.version(), |version| {
+        let article = Article::new(article.title().clone(), version);
+        Self::checkpoint(&article).or_else(|| Self::articles(&article))
+      })
+    }
+
+    // The render depth of a new version built with the script.
+    pub(crate) fn script_render_depth(title: &Title, script: &Script) -> u32 {
+      let mut references = script.references().map(|version| version.0).collect::<Vec<_>>();
+      references.sort_unstable();
+      references.dedup();
+      references.into_iter().fold(0, |depth, version| {
+        let article = Article::new(title.clone(), ArticleVersion(version));
+        depth.saturating_add(Self::render_depth(article)).saturating_add(1)
+      })
+    }
+
+    /// Stores the render depth of a new version, failing if it's deeper than `MAX_RENDER_DEPTH`.
+    ///
+    /// The runtime doesn't keep the scripts indexed versions are built on top of, so it can't
+    /// render them to checkpoint them. Versions which would be deeper have to be added as full
+    /// text instead, which is their own checkpoint.
+    pub(crate) fn track_render_depth(article: &Article, depth: u32) -> Result<(), Error<T>> {
+      if depth > MAX_RENDER_DEPTH {
+        Err(Error::<T>::RenderTooDeep)?;
+      }
+      RenderDepths::<T>::set(article, depth);
+      Ok(())
+    }
+
+    /// Stores the render depth of a version from before scripts were indexed, storing a checkpoint
+    /// of it if it's due one.
+    ///
+    /// Returns if a checkpoint was stored.
+    pub(crate) fn store_checkpoint(article: &Article, depth: u32) -> bool {
+      let due = (article.version().0 % CHECKPOINT_INTERVAL == 0) || (depth > MAX_RENDER_DEPTH);
+      if (depth != 0) && due {
+        // versions which don't render, from before scripts were simulated, can't be checkpointed
+        let checkpoint = Self::render(article).ok().and_then(|body| Script::full_text(&body).ok());
+        if let Some(checkpoint) = checkpoint {
+          Checkpoints::<T>::set(article, Some(checkpoint));
+          RenderDepths::<T>::remove(article);
+          return true;
+        }
+      }
+      RenderDepths::<T>::set(article, depth);
+      false
+    }
+
+    // Versions added before the lengths were tracked don't have one stored, so they're rendered.
+    // Those which don't render to valid UTF-8 can't be referenced by scripts counting characters.
+    fn rendered_len(article: &Article) -> Option<usize> {
+      match Self::body_len(article) {
+        Some(len) => Some(usize::try_from(len).unwrap()),
+        None => unit_len(CursorUnit::Char, &Self::render(article).ok()?).ok(),
+      }
+    }
+
+    // Scripts written today count characters and only add UTF-8 text.
+    fn validate_text(script: &Script) -> Result<(), Error<T>> {
+      if script.unit() != CursorUnit::Char {
+        Err(Error::<T>::UnsupportedCursorUnit)?;
+      }
+      for opcode in script.data() {
+        if let OpCode::Add(body) = opcode {
+          if core::str::from_utf8(body.data()).is_err() {
+            Err(Error::<T>::InvalidUtf8)?;
+          }
+        }
+      }
+      Ok(())
+    }
+
+    // The weight of adding the script, without any checkpoint it may cause.
+    fn script_weight(script: &Script, summary: &Option<EditSummary>, new_article: bool) -> Weight {
+      let len = u32::try_from(script.encoded_size().saturating_add(summary.encoded_size()))
+        .unwrap_or(u32::MAX);
+      if new_article {
+        T::WeightInfo::add_article(len)
+      } else {
+        T::WeightInfo::add_version(len, u32::try_from(script.data().len()).unwrap())
+          .saturating_add(T::OnVersionAdded::weight())
+      }
+    }
+
+    /// Rebases a script written on top of the `base` version onto the last version of an article,
+    /// over one version at a time.
+    ///
+    /// `title` is expected to be canonical.
+    pub(crate) fn rebase(
+      title: &Title,
+      mut script: Script,
+      base: ArticleVersion,
+      last_version: ArticleVersion,
+    ) -> Result<Script, Error<T>> {
+      if (base.0 > last_version.0) || ((last_version.0 - base.0) > MAX_REBASE_DEPTH) {
+        Err(Error::<T>::EditConflict)?;
+      }
+
+      let len = |version| {
+        Self::rendered_len(&Article::new(title.clone(), version)).ok_or(Error::<T>::EditConflict)
+      };
+      let mut base_len = len(base)?;
+      for version in (base.0 + 1) ..= last_version.0 {
+        let (base, onto) = (ArticleVersion(version - 1), ArticleVersion(version));
+        let onto_script =
+          Self::articles(Article::new(title.clone(), onto)).ok_or(Error::<T>::EditConflict)?;
+        let onto_len = len(onto)?;
+        script = script
+          .rebase(base, base_len, &onto_script, onto, onto_len)
+          .ok_or(Error::<T>::EditConflict)?;
+        base_len = onto_len;
+      }
+      Ok(script)
+    }
+
+    // The most a version may weigh, without any checkpoint it may cause. Rebasing a script may add
+    // opcodes to it, up to `MAX_SCRIPT_LEN`, each encoded in at most 8 bytes.
+    fn add_version_weight(script: &Script, summary: &Option<EditSummary>, rebase: bool) -> Weight {
+      if !rebase {
+        return Self::script_weight(script, summary, false);
+      }
+      let len = u32::try_from(script.encoded_size().saturating_add(summary.encoded_size()))
+        .unwrap_or(u32::MAX)
+        .saturating_add(8 * MAX_SCRIPT_LEN);
+      T::WeightInfo::add_version(len, MAX_SCRIPT_LEN)
+        .saturating_add(T::WeightInfo::rebase())
+        .saturating_add(T::OnVersionAdded::weight())
+    }
+
+    // The counts the title and the account reach with another version, failing if either already
+    // reached its limit.
+    fn counted_edit(author: &Public, title: &Title) -> Result<EditCounts, Error<T>> {
+      let limits = Self::rate_limits();
+
+      let block = frame_system::Pallet::<T>::block_number().saturated_into::<u64>();
+      let window = block / limits.title_window.max(1);
+      let edits = Self::title_edits(title)
+        .filter(|(counted_in, _)| *counted_in == window)
+        .map_or(0, |(_, edits)| edits);
+      if edits >= limits.versions_per_title {
+        Err(Error::<T>::TitleRateLimited)?;
+      }
+      let title_edits = (window, edits + 1);
+
+      let session = T::CurrentSession::get();
+      let edits = Self::account_edits(author)
+        .filter(|(counted_in, _)| *counted_in == session)
+        .map_or(0, |(_, edits)| edits);
+      if edits >= limits.edits_per_session {
+        Err(Error::<T>::AccountRateLimited)?;
+      }
+
+      Ok((title_edits, (session, edits + 1)))
+    }
+
+    /// Counts a version an account adds to an article, failing if it's over the rate limits.
+    ///
+    /// Reverts and moves are counted like any other version, so they can't be used to flood a title
+    /// either.
+    /// `title` is expected to be canonical.
+    pub(crate) fn count_edit(author: &Public, title: &Title) -> Result<(), Error<T>> {
+      let (title_edits, account_edits) = Self::counted_edit(author, title)?;
+      TitleEdits::<T>::set(title, Some(title_edits));
+      AccountEdits::<T>::set(author, Some(account_edits));
+      Ok(())
     }
 
-    fn validate_add_article_script(title: &Title, script: &Script) -> Result<(), Error<T>> {
-      // check title doesn't already exist
-      if Self::title_exist(title) {
+    /// Checks a call is within the rate limits, without counting it.
+    ///
+    /// Calls which don't add versions, or which fail otherwise, are left to fail when dispatched.
+    pub(crate) fn check_rate_limits(author: &Public, call: &Call<T>) -> Result<(), Error<T>> {
+      let (Call::add_article { title, .. } |
+      Call::add_version { title, .. } |
+      Call::move_article { from: title, .. } |
+      Call::revert { title, .. }) = call
+      else {
+        return Ok(());
+      };
+      let Ok(title) = title.canonical() else { return Ok(()) };
+      Self::counted_edit(author, &title)?;
+      Ok(())
+    }
+
+    // Queues a version of a protected article for review, once it's validated.
+    fn submit_for_review(
+      title: Title,
+      author: Public,
+      base: ArticleVersion,
+      summary: Option<EditSummary>,
+      script: Script,
+    ) -> DispatchResultWithPostInfo {
+      Self::validate_add_version_script(&title, &script)?;
+      Self::validate_summary(&summary)?;
+      if Self::script_render_depth(&title, &script) > MAX_RENDER_DEPTH {
+        Err(Error::<T>::RenderTooDeep)?;
+      }
+      let weight = Self::script_weight(&script, &summary, false);
+
+      let pending = Self::pending_count(&title);
+      if pending >= MAX_PENDING_VERSIONS {
+        Err(Error::<T>::TooManyPendingVersions)?;
+      }
+      let id = NextPendingIds::<T>::get(&title);
+      NextPendingIds::<T>::set(&title, id.checked_add(1).ok_or(Error::<T>::StorageFull)?);
+      PendingCounts::<T>::set(&title, pending + 1);
+
+      let block = frame_system::Pallet::<T>::block_number().saturated_into();
+      let version = PendingVersion { author: author.into(), base, script, summary, block };
+      PendingVersions::<T>::set(&title, id, Some(version));
+
+      Self::deposit_event(Event::VersionPending { title, id, author });
+      Ok(Some(weight).into())
+    }
+
+    // The most accepting a version may weigh, as its script may have to be rebased.
+    fn accept_version_weight() -> Weight {
+      T::WeightInfo::accept_version()
+        .saturating_add(T::WeightInfo::add_version(MAX_DATA_LEN, MAX_SCRIPT_LEN))
+        .saturating_add(T::WeightInfo::rebase())
+        .saturating_add(T::OnVersionAdded::weight())
+    }
+
+    // Edit summaries are text, like the scripts they describe.
+    fn validate_summary(summary: &Option<EditSummary>) -> Result<(), Error<T>> {
+      if summary.as_ref().is_some_and(|summary| core::str::from_utf8(summary.data()).is_err()) {
+        Err(Error::<T>::InvalidUtf8)?;
+      }
+      Ok(())
+    }
+
+    // Records how the version came to be.
+    fn record_version(
+      article: &Article,
+      author: Public,
+      parent: Option<ArticleVersion>,
+      size: u32,
+      summary: Option<EditSummary>,
+    ) {
+      let info = VersionInfo {
+        author: author.into(),
+        block: frame_system::Pallet::<T>::block_number().saturated_into(),
+        time: T::Time::now().as_millis().saturated_into(),
+        parent,
+        size,
+        summary,
+      };
+      VersionInfos::<T>::set(article, Some(info));
+    }
+
+    // Stores the script of a new version. Scripts taken from the extrinsic as-is are indexed, and
+    // only kept in state while edits may be rebased over them.
+    pub(crate) fn store_script(article: &Article, script: Script, indexed: bool) {
+      if indexed {
+        // the call isn't at the end of the extrinsic, so the whole extrinsic is indexed
+        let extrinsic = frame_system::Pallet::<T>::extrinsic_index().unwrap_or_default();
+        let data = frame_system::Pallet::<T>::extrinsic_data(extrinsic);
+        let hash = blake2_256(&data);
+        let len = u32::try_from(data.len()).unwrap();
+        sp_io::transaction_index::index(extrinsic, len, hash);
+        ScriptHashes::<T>::set(article, Some((hash, len)));
+      }
+      Articles::<T>::set(article, Some(script));
+
+      // drop the script of the version edits can no longer be rebased over, unless reverts to it
+      // copy it
+      if let Some(version) = article.version().0.checked_sub(MAX_REBASE_DEPTH) {
+        let old = Article::new(article.title().clone(), ArticleVersion(version));
+        if ScriptHashes::<T>::contains_key(&old) && (Self::render_depth(&old) < MAX_RENDER_DEPTH) {
+          Articles::<T>::remove(&old);
+        }
+      }
+    }
+
+    /// Inserts a validated script as the next version of an article, returning the new version.
+    ///
+    /// `indexed` is whether the script is the one from the extrinsic. `title` is expected to be
+    /// canonical.
+    fn insert_version(
+      title: Title,
+      script: Script,
+      indexed: bool,
+      len: u32,
+      author: Public,
+      summary: Option<EditSummary>,
+    ) -> Result<Article, Error<T>> {
+      let depth = Self::script_render_depth(&title, &script);
+      let parent = script.references().next();
+
+      // update the versions
+      // we can unwrap here since the script was validated, so we have the title hence a version
+      let version = ArticleVersion(
+        Self::last_version(&title).unwrap().0.checked_add(1).ok_or(Error::<T>::TooManyVersions)?,
+      );
+
+      // construct the article
+      let article = Article::new(title, version);
+      Self::track_render_depth(&article, depth)?;
+
+      // update last version
+      LastVersion::<T>::set(article.title(), Some(article.version()));
+
+      // insert the body for the version
+      BodyLengths::<T>::set(&article, Some(len));
+      Self::store_script(&article, script, indexed);
+
+      // insert the author
+      Authors::<T>::set(&article, Some(author));
+      Self::record_version(&article, author, parent, len, summary);
+      Self::index_contribution(&article, author)?;
+
+      T::OnVersionAdded::on_version_added(&article);
+      Ok(article)
+    }
+
+    /// Validates the script, returning the length of the body it adds.
+    ///
+    /// `title` is expected to be canonical.
+    fn validate_add_article_script(title: &Title, script: &Script) -> Result<u32, Error<T>> {
+      // check title doesn't already exist, as an article or a redirect
+      if LastVersion::<T>::contains_key(title) || Redirects::<T>::contains_key(title) {
         Err(Error::<T>::TitleAlreadyExist)?;
       }
 
+      // nor one which looks the same
+      if Skeletons::<T>::contains_key(Self::skeleton_key(title)) {
+        Err(Error::<T>::ConfusableTitle)?;
+      }
+
       // first Opcode should add a body
       let mut iter = script.data().iter();
       let opcode = iter.next().ok_or(Error::<T>::InvalidScript)?;
@@ -82,18 +852,18 @@ pub mod pallet {
         Err(Error::<T>::InvalidScript)?;
       }
 
-      if title.data().is_empty() {
-        Err(Error::<T>::InvalidScript)?;
-      }
-
       if body.data().is_empty() {
         Err(Error::<T>::InvalidScript)?;
       }
 
-      Ok(())
+      Self::validate_text(script)?;
+      Ok(u32::try_from(unit_len(CursorUnit::Char, body.data()).unwrap()).unwrap())
     }
 
-    fn validate_add_version_script(title: &Title, script: &Script) -> Result<(), Error<T>> {
+    /// Validates the script, returning the length of the body it produces.
+    ///
+    /// `title` is expected to be canonical.
+    fn validate_add_version_script(title: &Title, script: &Script) -> Result<u32, Error<T>> {
       // check the total "data" within the script.
       if script.encode().len() > usize::try_from(MAX_DATA_LEN).unwrap() {
         Err(Error::<T>::ArticleTooBig)?;
@@ -104,13 +874,12 @@ pub mod pallet {
         Err(Error::<T>::InvalidScript)?;
       }
 
+      Self::validate_text(script)?;
+
       // make sure the title exist
-      if !Self::title_exist(title) {
-        Err(Error::<T>::InvalidTitle)?;
-      }
+      let last_version = Self::last_version(title).ok_or(Error::<T>::InvalidTitle)?;
 
       // verify the opcodes
-      let last_version = Self::last_version(title).ok_or(Error::<T>::InvalidTitle)?;
       let mut reference_in_hand = false;
       for opcode in script.data() {
         // add version script can't have a Title opcode
@@ -132,64 +901,506 @@ pub mod pallet {
         }
       }
 
-      Ok(())
+      // run the script against the referenced versions
+      let len = script
+        .simulate(|version| Self::rendered_len(&Article::new(title.clone(), version)))
+        .map_err(|e| match e {
+          ScriptError::UnknownReference(_) | ScriptError::InvalidReference(_) => {
+            Error::<T>::InvalidReference
+          }
+          ScriptError::MissingReference | ScriptError::UnexpectedOpCode => {
+            Error::<T>::InvalidScript
+          }
+          ScriptError::CursorOutOfBounds => Error::<T>::CursorOutOfBounds,
+          ScriptError::BodyTooBig => Error::<T>::ArticleTooBig,
+          ScriptError::InvalidUtf8 => Error::<T>::InvalidUtf8,
+        })?;
+
+      // the new version can't be empty
+      if len == 0 {
+        Err(Error::<T>::EmptyArticle)?;
+      }
+
+      Ok(u32::try_from(len).unwrap())
+    }
+  }
+
+  #[pallet::hooks]
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+      crate::migrations::step::<T>()
+    }
+
+    fn on_runtime_upgrade() -> Weight {
+      crate::migrations::migrate::<T>()
     }
   }
 
   #[pallet::call]
   impl<T: Config> Pallet<T> {
     #[pallet::call_index(0)]
-    #[pallet::weight((0, DispatchClass::Normal))] // TODO
-    pub fn add_article(origin: OriginFor<T>, title: Title, script: Script) -> DispatchResult {
+    /// Creates an article.
+    #[pallet::weight(Pallet::<T>::script_weight(script, summary, true))]
+    pub fn add_article(
+      origin: OriginFor<T>,
+      title: Title,
+      summary: Option<EditSummary>,
+      script: Script,
+    ) -> DispatchResult {
       let from = ensure_signed(origin)?;
 
       // validate the script and title
-      Self::validate_add_article_script(&title, &script)?;
+      let canonical = title.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+      let len = Self::validate_add_article_script(&canonical, &script)?;
+      Self::validate_summary(&summary)?;
+      Self::count_edit(&from, &canonical)?;
 
-      // try inserting the title
-      if Titles::<T>::try_append(&title).is_err() {
-        Err(Error::<T>::StorageFull)?;
-      }
+      // insert the title
+      Self::index_title(&canonical)?;
+      Skeletons::<T>::set(Self::skeleton_key(&canonical), Some(canonical.clone()));
+      DisplayTitles::<T>::set(&canonical, Some(title));
 
       // make the article
-      let article = Article::new(title, ArticleVersion(0));
+      let article = Article::new(canonical, ArticleVersion(0));
 
       // update last version
       LastVersion::<T>::set(article.title(), Some(article.version()));
 
       // insert the body
-      Articles::<T>::set(&article, Some(script));
+      BodyLengths::<T>::set(&article, Some(len));
+      Self::store_script(&article, script, true);
 
       // insert the author
-      Authors::<T>::set(article, Some(from));
+      Authors::<T>::set(&article, Some(from));
+      Self::record_version(&article, from, None, len, summary);
+      Self::index_contribution(&article, from)?;
+
+      Self::deposit_event(Event::ArticleCreated { article, author: from });
       Ok(())
     }
 
+    /// Adds a version to an article, written on top of `expected_last_version`.
+    ///
+    /// Fails with `EditConflict` if another version was added since, unless `rebase` is set and
+    /// the script can be rebased over every version added since, which is only possible when
+    /// none of them edited the same parts of the body.
+    ///
+    /// Versions of protected articles are queued in `PendingVersions` until a reviewer accepts
+    /// them, when they're rebased over the versions accepted meanwhile regardless of `rebase`.
+    ///
+    /// Fails with `RenderTooDeep` if the version would replay more than `MAX_RENDER_DEPTH` scripts
+    /// to render, in which case it has to be added as full text.
     #[pallet::call_index(1)]
-    #[pallet::weight((0, DispatchClass::Normal))] // TODO
-    pub fn add_version(origin: OriginFor<T>, title: Title, script: Script) -> DispatchResult {
+    #[pallet::weight(Pallet::<T>::add_version_weight(script, summary, *rebase))]
+    pub fn add_version(
+      origin: OriginFor<T>,
+      title: Title,
+      summary: Option<EditSummary>,
+      expected_last_version: ArticleVersion,
+      rebase: bool,
+      script: Script,
+    ) -> DispatchResultWithPostInfo {
       let from = ensure_signed(origin)?;
 
+      // make sure the script was written on top of the last version
+      let title = title.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+      let last_version = Self::last_version(&title).ok_or(Error::<T>::InvalidTitle)?;
+      Self::count_edit(&from, &title)?;
+
+      // versions of protected articles wait for review instead
+      if Self::protected(&title).is_some() {
+        if expected_last_version.0 > last_version.0 {
+          Err(Error::<T>::InvalidReference)?;
+        }
+        return Self::submit_for_review(title, from, expected_last_version, summary, script);
+      }
+
+      let rebased = last_version != expected_last_version;
+      let script = if !rebased {
+        script
+      } else if rebase {
+        Self::rebase(&title, script, expected_last_version, last_version)?
+      } else {
+        Err(Error::<T>::EditConflict)?
+      };
+
       // validate the script
-      Self::validate_add_version_script(&title, &script)?;
+      let len = Self::validate_add_version_script(&title, &script)?;
+      Self::validate_summary(&summary)?;
+      let weight = Self::script_weight(&script, &summary, false);
 
-      // update the versions
-      // we can unwrap here since we pass the validation, so we have the title hence a version
-      let version = ArticleVersion(
-        Self::last_version(&title).unwrap().0.checked_add(1).ok_or(Error::<T>::TooManyVersions)?,
-      );
+      let article = Self::insert_version(title, script, !rebased, len, from, summary)?;
+      Self::deposit_event(Event::VersionAdded { article, author: from });
 
-      // construct the article
-      let article = Article::new(title, version);
+      // only pay for the rebase if it happened
+      if rebased {
+        return Ok(Some(weight.saturating_add(T::WeightInfo::rebase())).into());
+      }
+      Ok(Some(weight).into())
+    }
 
-      // update last version
-      LastVersion::<T>::set(article.title(), Some(article.version()));
+    /// Makes a title without an article lead to an article, which only its author may do.
+    ///
+    /// Redirects can't be replaced or removed, so letting anyone add them would let anyone take
+    /// any free title for good.
+    #[pallet::call_index(2)]
+    #[pallet::weight(T::WeightInfo::add_redirect())]
+    pub fn add_redirect(origin: OriginFor<T>, from: Title, to: Title) -> DispatchResult {
+      let author = ensure_signed(origin)?;
+      let from = from.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+      let to = to.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
 
-      // insert the body for the version
-      Articles::<T>::set(&article, Some(script));
+      // articles are only redirected by moving them
+      if LastVersion::<T>::contains_key(&from) {
+        Err(Error::<T>::TitleAlreadyExist)?;
+      }
+      if Redirects::<T>::contains_key(&from) {
+        Err(Error::<T>::RedirectAlreadyExist)?;
+      }
 
-      // insert the author
-      Authors::<T>::set(article, Some(from));
+      // follow the target to its article, making sure it doesn't lead back to this redirect
+      let mut target = to;
+      let mut depth = 1;
+      while !LastVersion::<T>::contains_key(&target) {
+        if target == from {
+          Err(Error::<T>::RedirectCycle)?;
+        }
+        target = Self::redirect(&target).ok_or(Error::<T>::InvalidTitle)?;
+        depth += 1;
+        if depth > MAX_REDIRECT_DEPTH {
+          Err(Error::<T>::TooManyRedirects)?;
+        }
+      }
+      if Self::authors(Article::new(target.clone(), ArticleVersion(0))) != Some(author) {
+        Err(Error::<T>::NotAuthor)?;
+      }
+
+      // a title which looks like another one may only lead to the same article
+      let skeleton = Self::skeleton_key(&from);
+      match Self::skeleton_title(skeleton) {
+        Some(lookalike) => {
+          if (lookalike != from) && (Self::resolve(&lookalike).as_ref() != Some(&target)) {
+            Err(Error::<T>::ConfusableTitle)?;
+          }
+        }
+        None => Skeletons::<T>::set(skeleton, Some(from.clone())),
+      }
+
+      // point straight at the article, keeping chains short
+      Redirects::<T>::set(&from, Some(target.clone()));
+      RedirectDepths::<T>::mutate(&target, |depth| *depth = (*depth).max(1));
+
+      Self::deposit_event(Event::RedirectAdded { from, to: target, author });
+      Ok(())
+    }
+
+    /// Moves every version of an article to another title, leaving a redirect behind.
+    ///
+    /// `versions` is the amount of versions the article has, which the weight is charged for. The
+    /// new title may be one redirecting to the article, in which case the redirect is replaced.
+    ///
+    /// The redirects leading to the old title are left as they are, one redirect further from the
+    /// article, so this fails with `TooManyRedirects` if that'd make them deeper than
+    /// `MAX_REDIRECT_DEPTH`.
+    ///
+    /// Protected articles may only be moved by root or a reviewer. Moves are counted toward the
+    /// rate limits like versions, unless root makes them, in which case `ArticleMoved` has no
+    /// author.
+    #[pallet::call_index(3)]
+    #[pallet::weight(
+      T::WeightInfo::move_article(*versions).saturating_add(T::OnArticleMoved::weight(*versions))
+    )]
+    pub fn move_article(
+      origin: OriginFor<T>,
+      from: Title,
+      to: Title,
+      versions: u32,
+    ) -> DispatchResultWithPostInfo {
+      let author = ensure_signed_or_root(origin)?;
+      let canonical_from = from.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+      let canonical_to = to.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+
+      let last_version = Self::last_version(&canonical_from).ok_or(Error::<T>::InvalidTitle)?;
+      if let Some(author) = &author {
+        if Self::protected(&canonical_from).is_some() && !Self::is_reviewer(author) {
+          Err(Error::<T>::NotReviewer)?;
+        }
+        Self::count_edit(author, &canonical_from)?;
+      }
+      let moved = last_version.0.checked_add(1).ok_or(Error::<T>::TooManyVersions)?;
+      // versions waiting for review are written for the title they were submitted to
+      if Self::pending_count(&canonical_from) != 0 {
+        Err(Error::<T>::HasPendingVersions)?;
+      }
+      if moved > versions {
+        Err(Error::<T>::InvalidVersionCount)?;
+      }
+      let weight =
+        T::WeightInfo::move_article(moved).saturating_add(T::OnArticleMoved::weight(moved));
+
+      // changing how the title is displayed doesn't move anything
+      if canonical_to == canonical_from {
+        DisplayTitles::<T>::set(&canonical_to, Some(to));
+        Self::deposit_event(Event::ArticleMoved { from: canonical_from, to: canonical_to, author });
+        return Ok(Some(T::WeightInfo::move_article(0)).into());
+      }
+
+      // the new title must be free, or redirect to this article
+      if LastVersion::<T>::contains_key(&canonical_to) ||
+        (Redirects::<T>::contains_key(&canonical_to) &&
+          (Self::resolve(&canonical_to).as_ref() != Some(&canonical_from)))
+      {
+        Err(Error::<T>::TitleAlreadyExist)?;
+      }
+
+      // the redirects leading here will lead to the redirect left behind
+      let depth = Self::redirect_depth(&canonical_from).saturating_add(1);
+      if depth > MAX_REDIRECT_DEPTH {
+        Err(Error::<T>::TooManyRedirects)?;
+      }
+
+      // nor look like another title
+      let skeleton = Self::skeleton_key(&canonical_to);
+      match Self::skeleton_title(skeleton) {
+        Some(lookalike) => {
+          if (lookalike != canonical_to) &&
+            (Self::resolve(&lookalike).as_ref() != Some(&canonical_from))
+          {
+            Err(Error::<T>::ConfusableTitle)?;
+          }
+        }
+        None => Skeletons::<T>::set(skeleton, Some(canonical_to.clone())),
+      }
+
+      // move every version
+      for version in 0 ..= last_version.0 {
+        let old = Article::new(canonical_from.clone(), ArticleVersion(version));
+        let new = Article::new(canonical_to.clone(), ArticleVersion(version));
+        Articles::<T>::swap(&old, &new);
+        ScriptHashes::<T>::swap(&old, &new);
+        BodyLengths::<T>::swap(&old, &new);
+        Checkpoints::<T>::swap(&old, &new);
+        RenderDepths::<T>::swap(&old, &new);
+        Authors::<T>::swap(&old, &new);
+        VersionInfos::<T>::swap(&old, &new);
+      }
+      LastVersion::<T>::remove(&canonical_from);
+      LastVersion::<T>::set(&canonical_to, Some(last_version));
+      TitleEdits::<T>::swap(&canonical_from, &canonical_to);
+      ProtectedTitles::<T>::swap(&canonical_from, &canonical_to);
+      NextPendingIds::<T>::swap(&canonical_from, &canonical_to);
+      DisplayTitles::<T>::remove(&canonical_from);
+      DisplayTitles::<T>::set(&canonical_to, Some(to));
+
+      // keep the article's position in the index
+      if let Some(index) = Titles::<T>::take(&canonical_from) {
+        Titles::<T>::set(&canonical_to, Some(index));
+        TitlesByIndex::<T>::set(index, Some(canonical_to.clone()));
+      }
+
+      // keep the article's categories
+      let categories = ArticleCategories::<T>::drain_prefix(&canonical_from).collect::<Vec<_>>();
+      for (category, ()) in categories {
+        Categories::<T>::remove(&category, &canonical_from);
+        Categories::<T>::insert(&category, &canonical_to, ());
+        ArticleCategories::<T>::insert(&canonical_to, category, ());
+      }
+
+      // leave a redirect behind
+      Redirects::<T>::remove(&canonical_to);
+      Redirects::<T>::set(&canonical_from, Some(canonical_to.clone()));
+      RedirectDepths::<T>::remove(&canonical_from);
+      RedirectDepths::<T>::set(&canonical_to, depth);
+
+      T::OnArticleMoved::on_article_moved(&canonical_from, &canonical_to, last_version);
+
+      Self::deposit_event(Event::ArticleMoved { from: canonical_from, to: canonical_to, author });
+      Ok(Some(weight).into())
+    }
+
+    /// Adds an article to a category, creating the category if it's empty.
+    #[pallet::call_index(4)]
+    #[pallet::weight(T::WeightInfo::tag())]
+    pub fn tag(origin: OriginFor<T>, title: Title, category: Title) -> DispatchResult {
+      let author = ensure_signed(origin)?;
+      let title = Self::resolve(&title).ok_or(Error::<T>::InvalidTitle)?;
+      let category = category.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+
+      if Categories::<T>::contains_key(&category, &title) {
+        Err(Error::<T>::AlreadyTagged)?;
+      }
+      let count = ArticleCategories::<T>::iter_key_prefix(&title).count();
+      if count >= usize::try_from(MAX_ARTICLE_CATEGORIES).unwrap() {
+        Err(Error::<T>::TooManyCategories)?;
+      }
+
+      CategorySizes::<T>::mutate(&category, |size| *size = size.saturating_add(1));
+      Categories::<T>::insert(&category, &title, ());
+      ArticleCategories::<T>::insert(&title, &category, ());
+
+      Self::deposit_event(Event::ArticleTagged { title, category, author });
+      Ok(())
+    }
+
+    /// Removes an article from a category.
+    #[pallet::call_index(5)]
+    #[pallet::weight(T::WeightInfo::untag())]
+    pub fn untag(origin: OriginFor<T>, title: Title, category: Title) -> DispatchResult {
+      let author = ensure_signed(origin)?;
+      let title = Self::resolve(&title).ok_or(Error::<T>::InvalidTitle)?;
+      let category = category.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+
+      if Categories::<T>::take(&category, &title).is_none() {
+        Err(Error::<T>::NotTagged)?;
+      }
+      ArticleCategories::<T>::remove(&title, &category);
+      CategorySizes::<T>::mutate_exists(&category, |size| {
+        *size = size.and_then(|size| size.checked_sub(1)).filter(|size| *size != 0);
+      });
+
+      Self::deposit_event(Event::ArticleUntagged { title, category, author });
+      Ok(())
+    }
+
+    /// Adds a version with the same body as an existing version, which only references it.
+    ///
+    /// Reverts of protected articles are queued in `PendingVersions` like any other version.
+    ///
+    /// Versions `MAX_RENDER_DEPTH` scripts deep can't be referenced, so reverts to them copy their
+    /// script instead, rendering from the same versions they do.
+    #[pallet::call_index(6)]
+    #[pallet::weight(T::WeightInfo::revert().saturating_add(T::OnVersionAdded::weight()))]
+    pub fn revert(
+      origin: OriginFor<T>,
+      title: Title,
+      to_version: ArticleVersion,
+    ) -> DispatchResultWithPostInfo {
+      let author = ensure_signed(origin)?;
+      let title = title.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+
+      let last_version = Self::last_version(&title).ok_or(Error::<T>::InvalidTitle)?;
+      if to_version.0 > last_version.0 {
+        Err(Error::<T>::InvalidReference)?;
+      }
+      Self::count_edit(&author, &title)?;
+
+      // copy the entire body of the version
+      let target = Article::new(title.clone(), to_version);
+      let len = Self::rendered_len(&target).ok_or(Error::<T>::InvalidReference)?;
+      let len = u32::try_from(len).unwrap();
+      let script = if Self::render_depth(&target) < MAX_RENDER_DEPTH {
+        Script::new(vec![OpCode::Reference(to_version), OpCode::Cp(len)])
+          .map_err(|_| Error::<T>::InvalidScript)?
+      } else {
+        // the scripts of versions as deep as allowed are kept for this
+        Self::articles(&target).ok_or(Error::<T>::RenderTooDeep)?
+      };
+
+      if Self::protected(&title).is_some() {
+        return Self::submit_for_review(title, author, last_version, None, script);
+      }
+
+      let article = Self::insert_version(title, script, false, len, author, None)?;
+      Self::deposit_event(Event::Reverted { article, to: to_version, author });
+      Ok(().into())
+    }
+
+    /// Sets the limits on how often articles may be edited.
+    #[pallet::call_index(7)]
+    #[pallet::weight(T::WeightInfo::set_rate_limits())]
+    pub fn set_rate_limits(origin: OriginFor<T>, limits: RateLimits) -> DispatchResult {
+      ensure_root(origin)?;
+      if limits.title_window == 0 {
+        Err(Error::<T>::InvalidRateLimits)?;
+      }
+      EditRateLimits::<T>::set(limits);
+      Self::deposit_event(Event::RateLimitsSet { limits });
+      Ok(())
+    }
+
+    /// Sets if the versions of an article are reviewed before they're added.
+    #[pallet::call_index(8)]
+    #[pallet::weight(T::WeightInfo::set_protected())]
+    pub fn set_protected(origin: OriginFor<T>, title: Title, protected: bool) -> DispatchResult {
+      ensure_root(origin)?;
+      let title = title.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+      if !LastVersion::<T>::contains_key(&title) {
+        Err(Error::<T>::InvalidTitle)?;
+      }
+
+      ProtectedTitles::<T>::set(&title, protected.then_some(()));
+      Self::deposit_event(Event::ProtectionSet { title, protected });
+      Ok(())
+    }
+
+    /// Accepts a version waiting for review, adding it to its article.
+    ///
+    /// The script is rebased over the versions added since it was submitted, failing with
+    /// `EditConflict` if it can't be. Scripts which don't reference the version they were
+    /// submitted on top of, as reverts, don't build on it and are added as they are.
+    #[pallet::call_index(9)]
+    #[pallet::weight(Pallet::<T>::accept_version_weight())]
+    pub fn accept_version(
+      origin: OriginFor<T>,
+      title: Title,
+      id: u32,
+    ) -> DispatchResultWithPostInfo {
+      let reviewer = ensure_signed(origin)?;
+      let title = title.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+      if !Self::is_reviewer(&reviewer) {
+        Err(Error::<T>::NotReviewer)?;
+      }
+
+      let pending =
+        PendingVersions::<T>::take(&title, id).ok_or(Error::<T>::UnknownPendingVersion)?;
+      let author = Public::from(pending.author);
+      if author == reviewer {
+        Err(Error::<T>::SelfReview)?;
+      }
+      PendingCounts::<T>::mutate(&title, |count| *count = count.saturating_sub(1));
+
+      let last_version = Self::last_version(&title).ok_or(Error::<T>::InvalidTitle)?;
+      let rebased = (last_version != pending.base) &&
+        pending.script.references().any(|version| version == pending.base);
+      let script = if rebased {
+        Self::rebase(&title, pending.script, pending.base, last_version)?
+      } else {
+        pending.script
+      };
+      let len = Self::validate_add_version_script(&title, &script)?;
+      let weight = T::WeightInfo::accept_version().saturating_add(Self::script_weight(
+        &script,
+        &pending.summary,
+        false,
+      ));
+
+      let article = Self::insert_version(title, script, false, len, author, pending.summary)?;
+      Self::deposit_event(Event::VersionAccepted { article, id, author, reviewer });
+
+      if rebased {
+        return Ok(Some(weight.saturating_add(T::WeightInfo::rebase())).into());
+      }
+      Ok(Some(weight).into())
+    }
+
+    /// Rejects a version waiting for review, which its author may also do to withdraw it.
+    #[pallet::call_index(10)]
+    #[pallet::weight(T::WeightInfo::reject_version())]
+    pub fn reject_version(origin: OriginFor<T>, title: Title, id: u32) -> DispatchResult {
+      let reviewer = ensure_signed(origin)?;
+      let title = title.canonical().map_err(|_| Error::<T>::InvalidTitle)?;
+
+      let pending =
+        PendingVersions::<T>::get(&title, id).ok_or(Error::<T>::UnknownPendingVersion)?;
+      let author = Public::from(pending.author);
+      if (reviewer != author) && !Self::is_reviewer(&reviewer) {
+        Err(Error::<T>::NotReviewer)?;
+      }
+
+      PendingVersions::<T>::remove(&title, id);
+      PendingCounts::<T>::mutate(&title, |count| *count = count.saturating_sub(1));
+
+      Self::deposit_event(Event::VersionRejected { title, id, author, reviewer });
       Ok(())
     }
   }
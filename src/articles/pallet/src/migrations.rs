@@ -0,0 +1,346 @@
+use frame_support::{pallet_prelude::*, traits::StorageVersion};
+
+use scale::{Encode, Decode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+use sp_std::vec::Vec;
+
+use wikiblocks_primitives::{
+  Article, ArticleVersion, CursorUnit, OpCode, Script, Title, MAX_RENDER_DEPTH, MAX_SCRIPT_LEN,
+};
+
+use crate::pallet::*;
+
+mod v2 {
+  use super::*;
+
+  /// The titles, in the form the articles were created with, before v3.
+  #[frame_support::storage_alias]
+  pub(super) type Titles<T: Config> =
+    StorageValue<Pallet<T>, BoundedVec<Title, ConstU32<1_000_000>>, ValueQuery>;
+}
+
+/// The most steps taken per block by migrations spread over several blocks, a step migrating a
+/// version or skipping an article.
+pub(crate) const MIGRATION_STEPS: u32 = 128;
+
+/// How far the migration spread over several blocks in progress got.
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub enum MigrationCursor {
+  /// Moving articles to their canonical titles for v2, by their position in the titles they were
+  /// created with, first keeping the titles already canonical, then moving the other articles a
+  /// version at a time.
+  CanonicalTitles { moving: bool, position: u32, to: Option<(Title, ArticleVersion)> },
+  /// Checkpointing versions for v4, by the position of their article in `Titles` and their
+  /// version.
+  Checkpoints { index: u32, version: u32 },
+  /// Indexing versions by their author for v5, by the position of their article in `Titles` and
+  /// their version.
+  Contributions { index: u32, version: u32 },
+}
+
+impl MigrationCursor {
+  // The storage version the migration upgrades to.
+  fn version(&self) -> u16 {
+    match self {
+      MigrationCursor::CanonicalTitles { .. } => 2,
+      MigrationCursor::Checkpoints { .. } => 4,
+      MigrationCursor::Contributions { .. } => 5,
+    }
+  }
+}
+
+/// Runs the migrations due, in order, until one spread over several blocks, which is started for
+/// `step` to run. The storage is only upgraded further once it's done, including when it was
+/// already in progress.
+pub(crate) fn migrate<T: Config>() -> Weight {
+  let mut weight = T::DbWeight::get().reads(2);
+  if Migration::<T>::exists() {
+    return weight;
+  }
+  let version = StorageVersion::get::<Pallet<T>>();
+  if version < 1 {
+    weight += v1::<T>();
+  }
+  if version < 2 {
+    Migration::<T>::set(Some(MigrationCursor::CanonicalTitles {
+      moving: false,
+      position: 0,
+      to: None,
+    }));
+    return weight + T::DbWeight::get().writes(1);
+  }
+  if version < 3 {
+    weight += v3::<T>();
+  }
+  if version < 4 {
+    Migration::<T>::set(Some(MigrationCursor::Checkpoints { index: 0, version: 0 }));
+    return weight + T::DbWeight::get().writes(1);
+  }
+  if version < 5 {
+    Migration::<T>::set(Some(MigrationCursor::Contributions { index: 0, version: 0 }));
+    return weight + T::DbWeight::get().writes(1);
+  }
+  weight
+}
+
+/// Takes up to `MIGRATION_STEPS` steps of the migration in progress, running the migrations due
+/// after it once it's done.
+pub(crate) fn step<T: Config>() -> Weight {
+  let mut weight = T::DbWeight::get().reads(1);
+  let Some(mut cursor) = Migration::<T>::get() else { return weight };
+  let titles = match cursor {
+    MigrationCursor::CanonicalTitles { .. } => {
+      weight += T::DbWeight::get().reads(1);
+      v2::Titles::<T>::get().into_inner()
+    }
+    MigrationCursor::Checkpoints { .. } | MigrationCursor::Contributions { .. } => Vec::new(),
+  };
+
+  for _ in 0 .. MIGRATION_STEPS {
+    let version = cursor.version();
+    let next = match cursor {
+      MigrationCursor::CanonicalTitles { moving, position, to } => {
+        v2::<T>(&titles, moving, position, to, &mut weight)
+      }
+      MigrationCursor::Checkpoints { index, version } => v4::<T>(index, version, &mut weight),
+      MigrationCursor::Contributions { index, version } => v5::<T>(index, version, &mut weight),
+    };
+    let Some(next) = next else {
+      Migration::<T>::kill();
+      StorageVersion::new(version).put::<Pallet<T>>();
+      return weight + T::DbWeight::get().writes(2) + migrate::<T>();
+    };
+    cursor = next;
+  }
+
+  Migration::<T>::set(Some(cursor));
+  weight + T::DbWeight::get().writes(1)
+}
+
+/// Scripts were stored without their cursor unit before v1. They counted bytes and edited the
+/// bodies they reference in place, as scripts counting bytes still do (see `OpCode`).
+///
+/// The body lengths were measured in bytes as well, so they're removed. The lengths of versions
+/// without one stored are calculated when they're referenced.
+fn v1<T: Config>() -> Weight {
+  let mut scripts = 0;
+  Articles::<T>::translate::<BoundedVec<OpCode, ConstU32<MAX_SCRIPT_LEN>>, _>(|_, opcodes| {
+    scripts += 1;
+    Some(Script::with_unit(CursorUnit::Byte, opcodes.into_inner()).unwrap())
+  });
+  let lengths = BodyLengths::<T>::clear(u32::MAX, None).unique;
+
+  StorageVersion::new(1).put::<Pallet<T>>();
+  let items = scripts + u64::from(lengths);
+  T::DbWeight::get().reads_writes(items, items + 1)
+}
+
+// Appends " (n)" to the title.
+fn with_suffix(title: &Title, n: u32) -> Option<Title> {
+  let mut digits = Vec::new();
+  let mut rest = n;
+  loop {
+    digits.push(b'0' + u8::try_from(rest % 10).unwrap());
+    rest /= 10;
+    if rest == 0 {
+      break;
+    }
+  }
+  digits.reverse();
+
+  Title::new([title.data(), b" (", &digits, b")"].concat()).ok()
+}
+
+/// Articles were stored under the exact title they were created with before v2. They're moved to
+/// their canonical title, a version at a time over as many blocks as it takes.
+///
+/// If multiple titles have the same canonical form, the first one created keeps it unless one of
+/// them already was in its canonical form. The rest are moved to their canonical title suffixed
+/// with " (2)", " (3)", etc. Titles which don't have a canonical form, as they aren't valid UTF-8,
+/// aren't moved.
+///
+/// The titles which were already canonical are kept first, so the titles they have don't look
+/// free to the ones moved after. Titles which aren't canonical can't be the canonical title, or a
+/// suffixed one, another article is moved to, so the articles not moved yet are never in the way.
+fn v2<T: Config>(
+  titles: &[Title],
+  moving: bool,
+  position: u32,
+  to: Option<(Title, ArticleVersion)>,
+  weight: &mut Weight,
+) -> Option<MigrationCursor> {
+  let db = T::DbWeight::get();
+  let cursor = |moving, position, to| MigrationCursor::CanonicalTitles { moving, position, to };
+  match (moving, to) {
+    (false, _) => {
+      let Some(title) = titles.get(usize::try_from(position).unwrap()) else {
+        return Some(cursor(true, 0, None));
+      };
+      let next = cursor(false, position + 1, None);
+      *weight += db.reads(1);
+      if !LastVersion::<T>::contains_key(title) ||
+        (title.canonical().as_ref().is_ok_and(|canonical| canonical != title))
+      {
+        return Some(next);
+      }
+
+      DisplayTitles::<T>::set(title, Some(title.clone()));
+      *weight += db.writes(1) + claim_skeleton::<T>(title, title);
+      Some(next)
+    }
+
+    (true, None) => {
+      let title = titles.get(usize::try_from(position).unwrap())?;
+      let next = cursor(true, position + 1, None);
+      *weight += db.reads(1);
+      let Some(last_version) = LastVersion::<T>::get(title) else { return Some(next) };
+      let Ok(canonical) = title.canonical() else { return Some(next) };
+      if canonical == *title {
+        return Some(next);
+      }
+
+      let mut key = canonical.clone();
+      let mut n = 1;
+      while LastVersion::<T>::contains_key(&key) {
+        *weight += db.reads(1);
+        n += 1;
+        let Some(suffixed) = with_suffix(&canonical, n) else {
+          // there's no room for a suffix, so leave the article where it was
+          key = title.clone();
+          break;
+        };
+        key = suffixed;
+      }
+
+      DisplayTitles::<T>::set(&key, Some(title.clone()));
+      *weight += db.writes(1) + claim_skeleton::<T>(title, &key);
+      if key == *title {
+        return Some(next);
+      }
+      LastVersion::<T>::remove(title);
+      LastVersion::<T>::set(&key, Some(last_version));
+      *weight += db.writes(2);
+      Some(cursor(true, position, Some((key, ArticleVersion(0)))))
+    }
+
+    (true, Some((key, version))) => {
+      let title = titles.get(usize::try_from(position).unwrap())?;
+      let from = Article::new(title.clone(), version);
+      let to = Article::new(key.clone(), version);
+      Articles::<T>::swap(&from, &to);
+      Authors::<T>::swap(&from, &to);
+      BodyLengths::<T>::swap(&from, &to);
+      *weight += db.reads_writes(7, 6);
+
+      if Some(version) == LastVersion::<T>::get(&key) {
+        return Some(cursor(true, position + 1, None));
+      }
+      Some(cursor(true, position, Some((key, ArticleVersion(version.0 + 1)))))
+    }
+  }
+}
+
+// Keeps the confusable skeleton of an article's title for the canonical title it's stored under,
+// unless another article already has it.
+fn claim_skeleton<T: Config>(title: &Title, key: &Title) -> Weight {
+  if title.skeleton().is_none() {
+    return Weight::zero();
+  }
+  let skeleton = Pallet::<T>::skeleton_key(key);
+  if Skeletons::<T>::contains_key(skeleton) {
+    return T::DbWeight::get().reads(1);
+  }
+  Skeletons::<T>::set(skeleton, Some(key.clone()));
+  T::DbWeight::get().reads_writes(1, 1)
+}
+
+/// Titles were stored in a single list before v3, in the form the articles were created with.
+/// They're moved to an index of canonical titles, keeping their order.
+fn v3<T: Config>() -> Weight {
+  let titles = v2::Titles::<T>::take();
+  let mut reads = 1;
+  let mut writes = 1;
+  for title in titles {
+    // find the title the article was moved to by v2
+    let canonical = title.canonical().unwrap_or_else(|_| title.clone());
+    let mut key = canonical.clone();
+    let mut n = 1;
+    loop {
+      reads += 1;
+      if Pallet::<T>::display_title(&key).as_ref() == Some(&title) {
+        break;
+      }
+      n += 1;
+      match with_suffix(&canonical, n) {
+        Some(suffixed) if LastVersion::<T>::contains_key(&suffixed) => key = suffixed,
+        _ => {
+          key = title.clone();
+          break;
+        }
+      }
+    }
+
+    Pallet::<T>::index_title(&key).unwrap();
+    writes += 3;
+  }
+
+  StorageVersion::new(3).put::<Pallet<T>>();
+  T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Versions weren't checkpointed before v4. The render depth of every version is calculated, and
+/// checkpoints are stored for the versions due one, article by article a version at a time, oldest
+/// first so later versions render from the checkpoints of the earlier ones.
+fn v4<T: Config>(index: u32, version: u32, weight: &mut Weight) -> Option<MigrationCursor> {
+  let (article, next) = next_version::<T>(index, version, weight)?;
+  let next = Some(MigrationCursor::Checkpoints { index: next.0, version: next.1 });
+  let Some(article) = article else { return next };
+
+  *weight += T::DbWeight::get().reads(1);
+  let Some(script) = Articles::<T>::get(&article) else { return next };
+  let references = u64::try_from(script.references().count()).unwrap();
+  let depth = Pallet::<T>::script_render_depth(article.title(), &script);
+  if Pallet::<T>::store_checkpoint(&article, depth) {
+    // rendering reads up to every version the checkpoint is of
+    *weight += T::DbWeight::get().reads(u64::from(MAX_RENDER_DEPTH) * 2);
+  }
+  *weight += T::DbWeight::get().reads_writes(references, 1);
+  next
+}
+
+// The version at the cursor of a migration going through every version of every article, if it
+// exists, and the cursor of the version after it. Returns `None` once every article was gone
+// through.
+fn next_version<T: Config>(
+  index: u32,
+  version: u32,
+  weight: &mut Weight,
+) -> Option<(Option<Article>, (u32, u32))> {
+  *weight += T::DbWeight::get().reads(3);
+  if index >= Pallet::<T>::title_count() {
+    return None;
+  }
+  let next_article = Some((None, (index + 1, 0)));
+  let Some(title) = TitlesByIndex::<T>::get(index) else { return next_article };
+  let Some(last_version) = LastVersion::<T>::get(&title) else { return next_article };
+  let next = if version < last_version.0 { (index, version + 1) } else { (index + 1, 0) };
+  Some((Some(Article::new(title, ArticleVersion(version))), next))
+}
+
+/// Versions weren't indexed by their author before v5. The versions of every article are added to
+/// the contributions of their authors, article by article in the order they were created, a
+/// version at a time.
+fn v5<T: Config>(index: u32, version: u32, weight: &mut Weight) -> Option<MigrationCursor> {
+  let (article, next) = next_version::<T>(index, version, weight)?;
+  let next = Some(MigrationCursor::Contributions { index: next.0, version: next.1 });
+  let Some(article) = article else { return next };
+
+  *weight += T::DbWeight::get().reads(1);
+  let Some(author) = Authors::<T>::get(&article) else { return next };
+  let i = Pallet::<T>::contribution_count(author);
+  ContributionCounts::<T>::set(author, i + 1);
+  AuthorContributions::<T>::set(author, i, Some((index, article.version())));
+  *weight += T::DbWeight::get().reads_writes(1, 2);
+  next
+}
@@ -3,11 +3,12 @@
 use super::*;
 
 use frame_support::{
-  construct_runtime,
-  traits::{ConstU32, ConstU64},
+  construct_runtime, parameter_types,
+  traits::{ConstU32, ConstU64, Contains, UnixTime},
 };
 
-use sp_core::{H256, sr25519::Public};
+use sp_core::{H256, Pair, sr25519::Public};
+use wikiblocks_primitives::{insecure_pair_from_name, RateLimits, Title};
 use sp_runtime::{
   traits::{BlakeTwo256, IdentityLookup},
   BuildStorage,
@@ -25,6 +26,28 @@ construct_runtime!(
   }
 );
 
+// Blocks are six seconds apart, starting from the Unix epoch.
+pub struct MockTime;
+impl UnixTime for MockTime {
+  fn now() -> core::time::Duration {
+    core::time::Duration::from_secs(System::block_number() * 6)
+  }
+}
+
+parameter_types! {
+  pub static Session: u32 = 0;
+  // Deriving the key is slow, so it's derived once.
+  pub static Reviewer: Public = insecure_pair_from_name("reviewer").public();
+}
+
+// Only the account named "reviewer" reviews, as if it had stake.
+pub struct MockReviewers;
+impl Contains<Public> for MockReviewers {
+  fn contains(account: &Public) -> bool {
+    *account == Reviewer::get()
+  }
+}
+
 impl frame_system::Config for Test {
   type BaseCallFilter = frame_support::traits::Everything;
   type BlockWeights = ();
@@ -53,12 +76,40 @@ impl frame_system::Config for Test {
 
 impl Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = ();
+  type OnArticleMoved = ();
+  type OnVersionAdded = ();
+  type Time = MockTime;
+  type CurrentSession = Session;
+  type Reviewers = MockReviewers;
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = MockReviewers;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::BenchmarkHelper for MockReviewers {
+  fn reviewer() -> Public {
+    Reviewer::get()
+  }
 }
 
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
-  let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+  let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+
+  // most tests edit far more than anyone may on the chain, so they're left unlimited
+  articles::GenesisConfig::<Test> {
+    rate_limits: RateLimits {
+      versions_per_title: u32::MAX,
+      title_window: 1,
+      edits_per_session: u32::MAX,
+    },
+    protected_titles: vec![Title::new(b"main page".to_vec()).unwrap()],
+    _ignore: Default::default(),
+  }
+  .assimilate_storage(&mut t)
+  .unwrap();
 
   let mut ext = sp_io::TestExternalities::new(t);
-  ext.execute_with(|| System::set_block_number(0));
+  ext.execute_with(|| System::set_block_number(1));
   ext
 }
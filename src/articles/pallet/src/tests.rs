@@ -1,11 +1,25 @@
-use crate::{mock::*, pallet};
+use crate::{
+  mock::*, pallet, migrations::MIGRATION_STEPS, CheckRateLimits, MigrationCursor, RATE_LIMITED,
+};
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+  assert_noop, assert_ok,
+  storage::{storage_prefix, unhashed},
+  traits::{OnInitialize, OnRuntimeUpgrade, StorageVersion},
+};
 use frame_system::RawOrigin;
 
-use sp_core::Pair;
+use scale::Encode;
+
+use sp_core::{hashing::blake2_256, sr25519::Public, Pair};
+use sp_runtime::{
+  traits::{BadOrigin, SignedExtension},
+  transaction_validity::InvalidTransaction,
+};
 use wikiblocks_primitives::{
-  insecure_pair_from_name, Article, ArticleVersion, Body, OpCode, Script, Title,
+  insecure_pair_from_name, Article, ArticleVersion, Body, CursorUnit, Direction, EditSummary,
+  OpCode, RateLimits, Script, Title, VersionInfo, MAX_ARTICLE_CATEGORIES, MAX_PENDING_VERSIONS,
+  MAX_REBASE_DEPTH, MAX_REDIRECT_DEPTH, MAX_RENDER_DEPTH,
 };
 
 #[test]
@@ -20,12 +34,13 @@ fn add_article() {
     assert_ok!(Articles::add_article(
       RawOrigin::Signed(user).into(),
       title.clone(),
+      None,
       script.clone()
     ));
     let article = Article::new(title, ArticleVersion(0));
 
     // check that titles have 1 item that is correct
-    let titles = Articles::titles();
+    let titles = Articles::titles(0, 10);
     assert_eq!(titles, vec![(*article.title()).clone()]);
 
     // check that we have the version for it
@@ -37,8 +52,13 @@ fn add_article() {
     assert_eq!(in_chain_script, script);
 
     // check the author is right
-    let author = Articles::authors(article).unwrap();
+    let author = Articles::authors(&article).unwrap();
     assert_eq!(author, user);
+
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::ArticleCreated {
+      article,
+      author: user,
+    }));
   })
 }
 
@@ -51,7 +71,7 @@ fn add_version() {
 
     // add a title first
     let script = Script::new(vec![OpCode::Add(body.clone())]).unwrap();
-    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), script));
+    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script));
 
     // add a new version for it
     let body2 =
@@ -59,17 +79,20 @@ fn add_version() {
     let script = Script::new(vec![
       OpCode::Reference(ArticleVersion(0)),
       OpCode::Cp(body.data().len().try_into().unwrap()), // copy all data from the ref version.
-      OpCode::Add(body2),                                // continue by adding the body2 data
+      OpCode::Add(body2.clone()),                        // continue by adding the body2 data
     ])
     .unwrap();
     assert_ok!(Articles::add_version(
       RawOrigin::Signed(user).into(),
       title.clone(),
+      None,
+      ArticleVersion(0),
+      false,
       script.clone()
     ));
 
     // check that titles have 1 item that is correct
-    let titles = Articles::titles();
+    let titles = Articles::titles(0, 10);
     assert_eq!(titles, vec![title.clone()]);
 
     // check that we have 2 version of it
@@ -77,8 +100,21 @@ fn add_version() {
     assert_eq!(last_version, ArticleVersion(1)); // 0, 1
 
     // check that we have a body for the version
-    let in_chain_script = Articles::articles(Article::new(title, ArticleVersion(1))).unwrap();
+    let article = Article::new(title, ArticleVersion(1));
+    let in_chain_script = Articles::articles(&article).unwrap();
     assert_eq!(in_chain_script, script);
+    assert_eq!(
+      Articles::body_len(&article).unwrap(),
+      u32::try_from(body.data().len() + body2.data().len()).unwrap()
+    );
+
+    // check the version renders on top of the first one
+    assert_eq!(Articles::render(&article).unwrap(), [body.data(), body2.data()].concat());
+
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::VersionAdded {
+      article,
+      author: user,
+    }));
   })
 }
 
@@ -92,7 +128,7 @@ fn add_article_invalid_script() {
     // script can't be empty
     let script = Script::new(vec![]).unwrap();
     assert_noop!(
-      Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), script),
+      Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script),
       pallet::Error::<Test>::InvalidScript
     );
 
@@ -100,7 +136,7 @@ fn add_article_invalid_script() {
     let opcodes = vec![OpCode::Title(title.clone())];
     let script = Script::new(opcodes).unwrap();
     assert_noop!(
-      Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), script),
+      Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script),
       pallet::Error::<Test>::InvalidScript
     );
 
@@ -108,7 +144,7 @@ fn add_article_invalid_script() {
     let opcodes = vec![OpCode::Title(title.clone()), OpCode::Add(body.clone())];
     let script = Script::new(opcodes).unwrap();
     assert_noop!(
-      Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), script),
+      Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script),
       pallet::Error::<Test>::InvalidScript
     );
 
@@ -117,28 +153,28 @@ fn add_article_invalid_script() {
     let opcodes = vec![OpCode::Add(body.clone())];
     let script = Script::new(opcodes).unwrap();
     assert_noop!(
-      Articles::add_article(RawOrigin::Signed(user).into(), empty_title, script),
-      pallet::Error::<Test>::InvalidScript
+      Articles::add_article(RawOrigin::Signed(user).into(), empty_title, None, script),
+      pallet::Error::<Test>::InvalidTitle
     );
 
     // can't have empty body
     let opcodes = vec![OpCode::Add(Body::new(vec![]).unwrap())];
     let script = Script::new(opcodes).unwrap();
     assert_noop!(
-      Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), script),
+      Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script),
       pallet::Error::<Test>::InvalidScript
     );
 
     // valid script
     let opcodes = vec![OpCode::Add(body.clone())];
     let script = Script::new(opcodes).unwrap();
-    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), script));
+    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script));
 
     // can't add article with the same title
     let body = Body::new("this is second body".as_bytes().to_vec()).unwrap();
     let script = Script::new(vec![OpCode::Add(body)]).unwrap();
     assert_noop!(
-      Articles::add_article(RawOrigin::Signed(user).into(), title, script),
+      Articles::add_article(RawOrigin::Signed(user).into(), title, None, script),
       pallet::Error::<Test>::TitleAlreadyExist
     );
   })
@@ -154,12 +190,19 @@ fn add_version_invalid_script() {
     // add a valid article first
     let opcodes = vec![OpCode::Add(body.clone())];
     let script = Script::new(opcodes).unwrap();
-    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), script));
+    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script));
 
     // can't have empty Script
     let script = Script::new(vec![]).unwrap();
     assert_noop!(
-      Articles::add_version(RawOrigin::Signed(user).into(), title.clone(), script),
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(0),
+        false,
+        script
+      ),
       pallet::Error::<Test>::InvalidScript
     );
 
@@ -170,6 +213,9 @@ fn add_version_invalid_script() {
       Articles::add_version(
         RawOrigin::Signed(user).into(),
         Title::new("invalid title".as_bytes().to_vec()).unwrap(),
+        None,
+        ArticleVersion(0),
+        false,
         script
       ),
       pallet::Error::<Test>::InvalidTitle
@@ -178,7 +224,14 @@ fn add_version_invalid_script() {
     // can't have a title opcode
     let script = Script::new(vec![OpCode::Title(title.clone()), add_opcode.clone()]).unwrap();
     assert_noop!(
-      Articles::add_version(RawOrigin::Signed(user).into(), title.clone(), script),
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(0),
+        false,
+        script
+      ),
       pallet::Error::<Test>::InvalidScript
     );
 
@@ -186,15 +239,1341 @@ fn add_version_invalid_script() {
     let script =
       Script::new(vec![OpCode::Reference(ArticleVersion(2)), add_opcode.clone()]).unwrap();
     assert_noop!(
-      Articles::add_version(RawOrigin::Signed(user).into(), title.clone(), script),
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(0),
+        false,
+        script
+      ),
       pallet::Error::<Test>::InvalidReference
     );
 
     // can't have an opcode that requires a reference without the reference opcode
     let script = Script::new(vec![OpCode::Cp(20), OpCode::End]).unwrap();
     assert_noop!(
-      Articles::add_version(RawOrigin::Signed(user).into(), title.clone(), script),
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(0),
+        false,
+        script
+      ),
       pallet::Error::<Test>::InvalidScript
     );
+
+    // can't move the cursor past the end of the reference
+    let script = Script::new(vec![
+      OpCode::Reference(ArticleVersion(0)),
+      OpCode::End,
+      OpCode::MvCr(Direction::Right, 1),
+      add_opcode.clone(),
+    ])
+    .unwrap();
+    assert_noop!(
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(0),
+        false,
+        script
+      ),
+      pallet::Error::<Test>::CursorOutOfBounds
+    );
+
+    // can't copy more than the reference has
+    let len = u32::try_from(body.data().len()).unwrap();
+    let script =
+      Script::new(vec![OpCode::Reference(ArticleVersion(0)), OpCode::Cp(len + 1)]).unwrap();
+    assert_noop!(
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(0),
+        false,
+        script
+      ),
+      pallet::Error::<Test>::CursorOutOfBounds
+    );
+
+    // can't delete more than the reference has
+    let script =
+      Script::new(vec![OpCode::Reference(ArticleVersion(0)), OpCode::Del(len + 1), add_opcode])
+        .unwrap();
+    assert_noop!(
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(0),
+        false,
+        script
+      ),
+      pallet::Error::<Test>::CursorOutOfBounds
+    );
+
+    // can't produce an empty article
+    let script = Script::new(vec![OpCode::Reference(ArticleVersion(0)), OpCode::Del(len)]).unwrap();
+    assert_noop!(
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title,
+        None,
+        ArticleVersion(0),
+        false,
+        script
+      ),
+      pallet::Error::<Test>::EmptyArticle
+    );
+  })
+}
+
+#[test]
+fn add_version_unicode() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let title = Title::new("百科全书".as_bytes().to_vec()).unwrap();
+    let body = Body::new("Вікіпедія — вільна енциклопедія".as_bytes().to_vec()).unwrap();
+
+    let script = Script::new(vec![OpCode::Add(body)]).unwrap();
+    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script));
+    assert_eq!(Articles::body_len(Article::new(title.clone(), ArticleVersion(0))), Some(31));
+
+    // the cursor counts characters, so this replaces "вільна" with "відкрита"
+    let script = Script::new(vec![
+      OpCode::Reference(ArticleVersion(0)),
+      OpCode::Cp(12),
+      OpCode::Add(Body::new("відкрита".as_bytes().to_vec()).unwrap()),
+      OpCode::Del(18),
+      OpCode::Cp(13),
+    ])
+    .unwrap();
+    assert_ok!(Articles::add_version(
+      RawOrigin::Signed(user).into(),
+      title.clone(),
+      None,
+      ArticleVersion(0),
+      false,
+      script
+    ));
+
+    let article = Article::new(title.clone(), ArticleVersion(1));
+    assert_eq!(Articles::render(&article).unwrap(), "Вікіпедія — відкрита енциклопедія".as_bytes());
+    assert_eq!(Articles::body_len(&article), Some(33));
+
+    // bodies must be valid UTF-8
+    let invalid = OpCode::Add(Body::new(vec![0xD0]).unwrap());
+    assert_noop!(
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(1),
+        false,
+        Script::new(vec![invalid.clone()]).unwrap()
+      ),
+      pallet::Error::<Test>::InvalidUtf8
+    );
+    assert_noop!(
+      Articles::add_article(
+        RawOrigin::Signed(user).into(),
+        Title::new(b"another title".to_vec()).unwrap(),
+        None,
+        Script::new(vec![invalid]).unwrap()
+      ),
+      pallet::Error::<Test>::InvalidUtf8
+    );
+
+    // new scripts can't count bytes
+    let script = Script::with_unit(
+      CursorUnit::Byte,
+      vec![OpCode::Reference(ArticleVersion(0)), OpCode::Cp(1)],
+    )
+    .unwrap();
+    assert_noop!(
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title,
+        None,
+        ArticleVersion(1),
+        false,
+        script
+      ),
+      pallet::Error::<Test>::UnsupportedCursorUnit
+    );
+  })
+}
+
+// Runs the migrations due, returning the amount of blocks they were spread over.
+fn migrate() -> u64 {
+  Articles::on_runtime_upgrade();
+  let mut blocks = 0;
+  while Articles::migration().is_some() {
+    blocks += 1;
+    Articles::on_initialize(blocks);
+  }
+  blocks
+}
+
+#[test]
+fn migrate_cursor_units() {
+  new_test_ext().execute_with(|| {
+    let title = Title::new("example title".as_bytes().to_vec()).unwrap();
+    let opcodes =
+      vec![OpCode::Add(Body::new("this is an example article".as_bytes().to_vec()).unwrap())];
+
+    // store a script the way it was stored before the cursor unit was added
+    let article = Article::new(title, ArticleVersion(0));
+    unhashed::put(&pallet::Articles::<Test>::hashed_key_for(&article), &opcodes);
+    pallet::BodyLengths::<Test>::set(&article, Some(26));
+    StorageVersion::new(0).put::<Articles>();
+
+    migrate();
+    assert_eq!(
+      Articles::articles(&article).unwrap(),
+      Script::with_unit(CursorUnit::Byte, opcodes).unwrap()
+    );
+    assert_eq!(Articles::body_len(&article), None);
+    assert_eq!(StorageVersion::get::<Articles>(), StorageVersion::new(5));
+  })
+}
+
+#[test]
+fn canonical_titles() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let title = |title: &str| Title::new(title.as_bytes().to_vec()).unwrap();
+    let script = || {
+      Script::new(vec![OpCode::Add(Body::new(b"the capital of France".to_vec()).unwrap())]).unwrap()
+    };
+
+    assert_ok!(Articles::add_article(
+      RawOrigin::Signed(user).into(),
+      title("Paris"),
+      None,
+      script()
+    ));
+    assert!(Articles::title_exist(&title(" paris")));
+    assert_eq!(Articles::last_version(title("paris")), Some(ArticleVersion(0)));
+    assert_eq!(Articles::display_title(title("paris")), Some(title("Paris")));
+
+    // the same title in another form
+    assert_noop!(
+      Articles::add_article(RawOrigin::Signed(user).into(), title("paris "), None, script()),
+      pallet::Error::<Test>::TitleAlreadyExist
+    );
+    // a title which looks the same
+    assert_noop!(
+      Articles::add_article(RawOrigin::Signed(user).into(), title("P\u{430}ris"), None, script()),
+      pallet::Error::<Test>::ConfusableTitle
+    );
+
+    // versions can be added under any form of the title
+    let script = Script::new(vec![OpCode::Reference(ArticleVersion(0)), OpCode::Cp(3)]).unwrap();
+    assert_ok!(Articles::add_version(
+      RawOrigin::Signed(user).into(),
+      title("Paris\n"),
+      None,
+      ArticleVersion(0),
+      false,
+      script
+    ));
+    assert_eq!(Articles::last_version(title("paris")), Some(ArticleVersion(1)));
+    assert_eq!(Articles::render(&Article::new(title("paris"), ArticleVersion(1))).unwrap(), b"the");
+    assert_eq!(Articles::authors(Article::new(title("paris"), ArticleVersion(1))), Some(user));
+  })
+}
+
+#[test]
+fn migrate_canonical_titles() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let title = |title: &str| Title::new(title.as_bytes().to_vec()).unwrap();
+    let script = Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap();
+
+    // store the articles the way they were stored before titles were canonical, with more versions
+    // than are moved per block
+    let titles = [title("Paris"), title("paris"), title("Rome ")];
+    for (raw, last_version) in titles.iter().zip([MIGRATION_STEPS, 0, 0]) {
+      for version in 0 ..= last_version {
+        let article = Article::new(raw.clone(), ArticleVersion(version));
+        pallet::Articles::<Test>::set(&article, Some(script.clone()));
+        pallet::Authors::<Test>::set(&article, Some(user));
+      }
+      pallet::LastVersion::<Test>::set(raw, Some(ArticleVersion(last_version)));
+    }
+    unhashed::put(&storage_prefix(b"Articles", b"Titles"), &titles.to_vec());
+    StorageVersion::new(1).put::<Articles>();
+
+    // the articles are moved over several blocks, before the later migrations run
+    Articles::on_runtime_upgrade();
+    Articles::on_initialize(1);
+    assert!(matches!(
+      Articles::migration(),
+      Some(MigrationCursor::CanonicalTitles { moving: true, .. })
+    ));
+    assert_eq!(StorageVersion::get::<Articles>(), StorageVersion::new(1));
+    migrate();
+    assert_eq!(StorageVersion::get::<Articles>(), StorageVersion::new(5));
+
+    // "paris" was already canonical, so it keeps its title
+    assert_eq!(Articles::display_title(title("paris")), Some(title("paris")));
+    assert_eq!(Articles::display_title(title("paris (2)")), Some(title("Paris")));
+    assert_eq!(Articles::last_version(title("Paris")), None);
+    assert_eq!(Articles::last_version(title("paris (2)")), Some(ArticleVersion(MIGRATION_STEPS)));
+    for version in 0 ..= MIGRATION_STEPS {
+      let article = Article::new(title("paris (2)"), ArticleVersion(version));
+      assert_eq!(Articles::articles(&article).as_ref(), Some(&script));
+      assert_eq!(Articles::articles(Article::new(title("Paris"), ArticleVersion(version))), None);
+    }
+    assert_eq!(Articles::authors(Article::new(title("rome"), ArticleVersion(0))), Some(user));
+    assert_eq!(Articles::authors(Article::new(title("Rome "), ArticleVersion(0))), None);
+
+    // the titles are indexed in the order they were created in
+    assert_eq!(Articles::titles(0, 10), vec![title("paris (2)"), title("paris"), title("rome")]);
+    assert_eq!(Articles::title_index(title("rome")), Some(2));
+    assert_eq!(Articles::title_count(), 3);
+    assert!(!unhashed::exists(&storage_prefix(b"Articles", b"Titles")));
+
+    // lookalikes of migrated titles can't be added
+    assert_noop!(
+      Articles::add_article(
+        RawOrigin::Signed(user).into(),
+        title("R\u{43e}me"),
+        None,
+        Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap()
+      ),
+      pallet::Error::<Test>::ConfusableTitle
+    );
+  })
+}
+
+#[test]
+fn list_titles() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let script = Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap();
+
+    let mut titles = vec![];
+    for i in 0 .. 25 {
+      let title = Title::new(format!("title {i}").into_bytes()).unwrap();
+      assert_ok!(Articles::add_article(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        script.clone()
+      ));
+      titles.push(title);
+    }
+    assert_eq!(Articles::title_count(), 25);
+
+    // page through them
+    let mut listed = vec![];
+    let mut start = 0;
+    loop {
+      let page = Articles::titles(start, 10);
+      if page.is_empty() {
+        break;
+      }
+      start += u32::try_from(page.len()).unwrap();
+      listed.extend(page);
+    }
+    assert_eq!(listed, titles);
+
+    assert_eq!(Articles::titles(20, 10), titles[20 ..]);
+    assert_eq!(Articles::titles(30, 10), vec![]);
+    assert_eq!(Articles::titles(u32::MAX, u32::MAX), vec![]);
+  })
+}
+
+#[test]
+fn indexed_scripts() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let title = Title::new(b"example title".to_vec()).unwrap();
+    let article = |version| Article::new(title.clone(), ArticleVersion(version));
+    // every extrinsic is noted before it's applied, and is distinguished here by its script
+    let note = |script: &Script| System::note_extrinsic(script.encode());
+    let add_version = |version: u32, script: Script| {
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(version - 1),
+        false,
+        script,
+      )
+    };
+
+    let script = Script::new(vec![OpCode::Add(Body::new(b"a".to_vec()).unwrap())]).unwrap();
+    note(&script);
+    assert_ok!(Articles::add_article(
+      RawOrigin::Signed(user).into(),
+      title.clone(),
+      None,
+      script.clone()
+    ));
+    let mut scripts = vec![(script, true)];
+
+    // every version copies the last one and adds a character to it
+    let edit = |version, len| {
+      Script::new(vec![
+        OpCode::Reference(ArticleVersion(version - 1)),
+        OpCode::Cp(len),
+        OpCode::Add(Body::new(b"b".to_vec()).unwrap()),
+      ])
+      .unwrap()
+    };
+    for version in 1 ..= MAX_RENDER_DEPTH {
+      note(&edit(version, version));
+      assert_ok!(add_version(version, edit(version, version)));
+      assert_eq!(Articles::render_depth(article(version)), version);
+      scripts.push((edit(version, version), true));
+    }
+
+    // the runtime can't render the versions to checkpoint them, so deeper ones are full text
+    assert_noop!(add_version(17, edit(17, 17)), pallet::Error::<Test>::RenderTooDeep);
+    let body = [b"a".to_vec(), vec![b'b'; 16]].concat();
+    let full_text = Script::full_text(&body).unwrap();
+    note(&full_text);
+    assert_ok!(add_version(17, full_text.clone()));
+    assert_eq!(Articles::render_depth(article(17)), 0);
+    scripts.push((full_text, true));
+
+    // reverts aren't part of their extrinsic, so they're kept in state
+    System::note_extrinsic(b"revert".to_vec());
+    assert_ok!(Articles::revert(RawOrigin::Signed(user).into(), title.clone(), ArticleVersion(17)));
+    assert_eq!(Articles::render_depth(article(18)), 1);
+    let revert = Script::new(vec![OpCode::Reference(ArticleVersion(17)), OpCode::Cp(17)]).unwrap();
+    scripts.push((revert, false));
+    for version in 19 ..= 30 {
+      note(&edit(version, version - 2));
+      assert_ok!(add_version(version, edit(version, version - 2)));
+      scripts.push((edit(version, version - 2), true));
+    }
+
+    for (version, (script, indexed)) in (0 ..= 30).zip(&scripts) {
+      let data = script.encode();
+      let hash = (blake2_256(&data), u32::try_from(data.len()).unwrap());
+      assert_eq!(Articles::script_hash(article(version)), indexed.then_some(hash));
+      // only the indexed scripts which can't be rebased over anymore are dropped from state, unless
+      // reverts copy them
+      let kept = !indexed ||
+        (version > (30 - MAX_REBASE_DEPTH)) ||
+        (Articles::render_depth(article(version)) == MAX_RENDER_DEPTH);
+      assert_eq!(Articles::articles(article(version)).as_ref(), kept.then_some(script));
+      assert!(Articles::checkpoint(article(version)).is_none());
+    }
+  })
+}
+
+#[test]
+fn migrate_checkpoints() {
+  new_test_ext().execute_with(|| {
+    let title = Title::new(b"example title".to_vec()).unwrap();
+    let article = |version| Article::new(title.clone(), ArticleVersion(version));
+
+    // store a chain of versions the way they were stored before they were checkpointed
+    let script = Script::new(vec![OpCode::Add(Body::new(b"a".to_vec()).unwrap())]).unwrap();
+    pallet::Articles::<Test>::set(article(0), Some(script));
+    for version in 1 ..= 20 {
+      let script = Script::new(vec![
+        OpCode::Reference(ArticleVersion(version - 1)),
+        OpCode::Cp(version),
+        OpCode::Add(Body::new(b"b".to_vec()).unwrap()),
+      ])
+      .unwrap();
+      pallet::Articles::<Test>::set(article(version), Some(script));
+    }
+    pallet::LastVersion::<Test>::set(&title, Some(ArticleVersion(20)));
+    // and an article with more versions than are checkpointed per block
+    let other = Title::new(b"other title".to_vec()).unwrap();
+    for version in 0 ..= MIGRATION_STEPS {
+      let script = Script::new(vec![OpCode::Add(Body::new(b"c".to_vec()).unwrap())]).unwrap();
+      pallet::Articles::<Test>::set(
+        Article::new(other.clone(), ArticleVersion(version)),
+        Some(script),
+      );
+    }
+    pallet::LastVersion::<Test>::set(&other, Some(ArticleVersion(MIGRATION_STEPS)));
+    for title in [&title, &other] {
+      Articles::index_title(title).unwrap();
+    }
+    StorageVersion::new(3).put::<Articles>();
+
+    Articles::on_runtime_upgrade();
+    Articles::on_initialize(1);
+    assert!(matches!(Articles::migration(), Some(MigrationCursor::Checkpoints { index: 1, .. })));
+    migrate();
+    assert_eq!(StorageVersion::get::<Articles>(), StorageVersion::new(5));
+
+    assert!(Articles::checkpoint(article(16)).is_none());
+    assert!(Articles::checkpoint(article(17)).is_some());
+    assert_eq!(Articles::render_depth(article(16)), 16);
+    assert_eq!(Articles::render_depth(article(20)), 3);
+    assert_eq!(Articles::render(&article(20)).unwrap(), [b"a".to_vec(), vec![b'b'; 20]].concat());
+    let other = Article::new(other, ArticleVersion(MIGRATION_STEPS));
+    assert!(Articles::checkpoint(&other).is_none());
+    assert_eq!(Articles::render_depth(&other), 0);
+  })
+}
+
+#[test]
+fn redirects() {
+  new_test_ext().execute_with(|| {
+    let user = || RawOrigin::Signed(insecure_pair_from_name("user").public()).into();
+    let title = |title: &str| Title::new(title.as_bytes().to_vec()).unwrap();
+    let script = || Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap();
+
+    assert_ok!(Articles::add_article(user(), title("Paris"), None, script()));
+    assert_ok!(Articles::add_article(user(), title("Rome"), None, script()));
+
+    assert_ok!(Articles::add_redirect(user(), title("Paname"), title("Paris")));
+    assert_eq!(Articles::redirect(title("paname")), Some(title("paris")));
+    assert_eq!(Articles::resolve(&title("Paname")), Some(title("paris")));
+    assert_eq!(Articles::resolve(&title("Paris")), Some(title("paris")));
+    assert_eq!(Articles::resolve(&title("Lutece")), None);
+
+    // redirects to redirects point straight at the article
+    assert_ok!(Articles::add_redirect(user(), title("Lutece"), title("paname")));
+    assert_eq!(Articles::redirect(title("lutece")), Some(title("paris")));
+
+    // neither redirects nor articles can be replaced by a redirect
+    assert_noop!(
+      Articles::add_redirect(user(), title("Lutece"), title("Rome")),
+      pallet::Error::<Test>::RedirectAlreadyExist
+    );
+    assert_eq!(Articles::resolve(&title("Lutece")), Some(title("paris")));
+    assert_eq!(Articles::redirect_depth(title("paris")), 1);
+    assert_noop!(
+      Articles::add_redirect(user(), title("Paris"), title("Rome")),
+      pallet::Error::<Test>::TitleAlreadyExist
+    );
+    assert_noop!(
+      Articles::add_article(user(), title("Paname"), None, script()),
+      pallet::Error::<Test>::TitleAlreadyExist
+    );
+
+    // the target must lead to an article without leading back
+    assert_noop!(
+      Articles::add_redirect(user(), title("Lyon"), title("Marseille")),
+      pallet::Error::<Test>::InvalidTitle
+    );
+    assert_noop!(
+      Articles::add_redirect(user(), title("Lyon"), title("Lyon")),
+      pallet::Error::<Test>::RedirectCycle
+    );
+    pallet::Redirects::<Test>::set(title("paname"), Some(title("lutece")));
+    pallet::Redirects::<Test>::set(title("lutece"), Some(title("paname")));
+    assert_noop!(
+      Articles::add_redirect(user(), title("Lyon"), title("Lutece")),
+      pallet::Error::<Test>::TooManyRedirects
+    );
+    assert_eq!(Articles::resolve(&title("Lutece")), None);
+
+    // a title which looks like another one may only redirect to the same article
+    assert_noop!(
+      Articles::add_redirect(user(), title("R\u{43e}me"), title("Paris")),
+      pallet::Error::<Test>::ConfusableTitle
+    );
+    assert_ok!(Articles::add_redirect(user(), title("R\u{43e}me"), title("Rome")));
+
+    // only the author of the article may redirect titles to it
+    let other = insecure_pair_from_name("other").public();
+    assert_noop!(
+      Articles::add_redirect(RawOrigin::Signed(other).into(), title("Roma"), title("Rome")),
+      pallet::Error::<Test>::NotAuthor
+    );
+    assert_ok!(Articles::add_article(
+      RawOrigin::Signed(other).into(),
+      title("Lyon"),
+      None,
+      script()
+    ));
+    assert_ok!(Articles::add_redirect(
+      RawOrigin::Signed(other).into(),
+      title("Lugdunum"),
+      title("Lyon")
+    ));
+    assert_eq!(Articles::resolve(&title("Lugdunum")), Some(title("lyon")));
+  })
+}
+
+#[test]
+fn move_article() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let title = |title: &str| Title::new(title.as_bytes().to_vec()).unwrap();
+    let script = Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap();
+
+    assert_ok!(Articles::add_article(
+      RawOrigin::Signed(user).into(),
+      title("Rome"),
+      None,
+      script.clone()
+    ));
+    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title("Paris"), None, script));
+    let script = Script::new(vec![OpCode::Reference(ArticleVersion(0)), OpCode::Cp(2)]).unwrap();
+    assert_ok!(Articles::add_version(
+      RawOrigin::Signed(user).into(),
+      title("Paris"),
+      None,
+      ArticleVersion(0),
+      false,
+      script
+    ));
+
+    // every version has to be paid for
+    assert_noop!(
+      Articles::move_article(RawOrigin::Signed(user).into(), title("Paris"), title("Paname"), 1),
+      pallet::Error::<Test>::InvalidVersionCount
+    );
+    assert_noop!(
+      Articles::move_article(RawOrigin::Signed(user).into(), title("Paris"), title("Rome"), 2),
+      pallet::Error::<Test>::TitleAlreadyExist
+    );
+
+    assert_ok!(Articles::move_article(
+      RawOrigin::Signed(user).into(),
+      title("Paris"),
+      title("Paris, France"),
+      2
+    ));
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::ArticleMoved {
+      from: title("paris"),
+      to: title("paris, France"),
+      author: Some(user),
+    }));
+
+    // the versions are under the new title, with the old one redirecting to it
+    let moved = Article::new(title("paris, France"), ArticleVersion(1));
+    assert_eq!(Articles::last_version(title("paris")), None);
+    assert_eq!(Articles::last_version(title("paris, France")), Some(ArticleVersion(1)));
+    assert_eq!(Articles::display_title(title("paris, France")), Some(title("Paris, France")));
+    assert_eq!(Articles::render(&moved).unwrap(), b"bo");
+    assert_eq!(Articles::authors(&moved), Some(user));
+    assert_eq!(Articles::articles(Article::new(title("paris"), ArticleVersion(0))), None);
+    assert_eq!(Articles::resolve(&title("Paris")), Some(title("paris, France")));
+    assert_eq!(Articles::titles(0, 10), vec![title("rome"), title("paris, France")]);
+
+    // the article can be moved back over its redirect
+    assert_ok!(Articles::move_article(
+      RawOrigin::Signed(user).into(),
+      title("paris, France"),
+      title("Paris"),
+      2
+    ));
+    assert_eq!(Articles::last_version(title("paris")), Some(ArticleVersion(1)));
+    assert_eq!(Articles::redirect(title("paris")), None);
+    assert_eq!(Articles::resolve(&title("Paris, France")), Some(title("paris")));
+
+    // or just be displayed differently
+    assert_ok!(Articles::move_article(
+      RawOrigin::Signed(user).into(),
+      title("Paris"),
+      title("paris"),
+      2
+    ));
+    assert_eq!(Articles::display_title(title("paris")), Some(title("paris")));
+
+    // every move makes the redirects leading to the article one redirect deeper
+    let mut from = title("paris");
+    for i in (Articles::redirect_depth(title("paris")) + 1) ..= MAX_REDIRECT_DEPTH {
+      let to = title(&format!("paris {i}"));
+      assert_ok!(Articles::move_article(RawOrigin::Signed(user).into(), from, to.clone(), 2));
+      from = to;
+    }
+    assert_eq!(Articles::redirect_depth(&from), MAX_REDIRECT_DEPTH);
+    assert_eq!(Articles::resolve(&title("Paris, France")), Some(from.clone()));
+    assert_noop!(
+      Articles::move_article(RawOrigin::Signed(user).into(), from, title("Lutece"), 2),
+      pallet::Error::<Test>::TooManyRedirects
+    );
+
+    // protected articles are only moved by root or reviewers
+    let reviewer = insecure_pair_from_name("reviewer").public();
+    assert_ok!(Articles::set_protected(RawOrigin::Root.into(), title("Rome"), true));
+    assert_noop!(
+      Articles::move_article(RawOrigin::Signed(user).into(), title("Rome"), title("Roma"), 1),
+      pallet::Error::<Test>::NotReviewer
+    );
+    assert_ok!(Articles::move_article(
+      RawOrigin::Signed(reviewer).into(),
+      title("Rome"),
+      title("Roma"),
+      1
+    ));
+    assert_ok!(Articles::move_article(RawOrigin::Root.into(), title("Roma"), title("Rome"), 1));
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::ArticleMoved {
+      from: title("roma"),
+      to: title("rome"),
+      author: None,
+    }));
+    assert!(Articles::protected(title("rome")).is_some());
+  })
+}
+
+#[test]
+fn categories() {
+  new_test_ext().execute_with(|| {
+    let user = || RawOrigin::Signed(insecure_pair_from_name("user").public()).into();
+    let title = |title: &str| Title::new(title.as_bytes().to_vec()).unwrap();
+    let script = || Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap();
+
+    let mut cities = vec![];
+    for i in 0 .. 25 {
+      let city = title(&format!("city {i}"));
+      assert_ok!(Articles::add_article(user(), city.clone(), None, script()));
+      assert_ok!(Articles::tag(user(), city.clone(), title("Cities")));
+      cities.push(city);
+    }
+    assert_eq!(Articles::category_size(title("cities")), 25);
+    assert_eq!(Articles::categories(&title("City 0")), vec![title("cities")]);
+    assert_noop!(
+      Articles::tag(user(), title("city 0"), title("cities")),
+      pallet::Error::<Test>::AlreadyTagged
+    );
+    assert_noop!(
+      Articles::tag(user(), title("Atlantis"), title("cities")),
+      pallet::Error::<Test>::InvalidTitle
+    );
+
+    // page through the members
+    let mut listed = vec![];
+    let mut after = None;
+    loop {
+      let page = Articles::category_members(&title("Cities"), after, 10);
+      if page.is_empty() {
+        break;
+      }
+      after = page.last().cloned();
+      listed.extend(page);
+    }
+    listed.sort_by_key(|title| title.data().to_vec());
+    cities.sort_by_key(|title| title.data().to_vec());
+    assert_eq!(listed, cities);
+
+    // untagging removes the article from the category
+    assert_ok!(Articles::untag(user(), title("City 0"), title("cities")));
+    assert_eq!(Articles::category_size(title("cities")), 24);
+    assert_eq!(Articles::categories(&title("city 0")), vec![]);
+    assert!(!Articles::category_members(&title("cities"), None, 100).contains(&title("city 0")));
+    assert_noop!(
+      Articles::untag(user(), title("city 0"), title("cities")),
+      pallet::Error::<Test>::NotTagged
+    );
+
+    // there's a limit to how many categories an article may have
+    for i in 0 .. MAX_ARTICLE_CATEGORIES {
+      assert_ok!(Articles::tag(user(), title("city 0"), title(&format!("category {i}"))));
+    }
+    assert_noop!(
+      Articles::tag(user(), title("city 0"), title("cities")),
+      pallet::Error::<Test>::TooManyCategories
+    );
+
+    // the categories follow the article when it's moved
+    assert_ok!(Articles::move_article(user(), title("city 1"), title("Town 1"), 1));
+    assert_eq!(Articles::categories(&title("city 1")), vec![title("cities")]);
+    let members = Articles::category_members(&title("cities"), None, 100);
+    assert!(members.contains(&title("town 1")) && !members.contains(&title("city 1")));
+    assert_eq!(Articles::category_size(title("cities")), 24);
+  })
+}
+
+#[test]
+fn version_info() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let title = Title::new(b"Paris".to_vec()).unwrap();
+    let summary = |summary: &[u8]| Some(EditSummary::new(summary.to_vec()).unwrap());
+
+    let script =
+      Script::new(vec![OpCode::Add(Body::new(b"the capital".to_vec()).unwrap())]).unwrap();
+    assert_ok!(Articles::add_article(
+      RawOrigin::Signed(user).into(),
+      title.clone(),
+      summary(b"new article"),
+      script
+    ));
+    let article = Article::new(Title::new(b"paris".to_vec()).unwrap(), ArticleVersion(0));
+    assert_eq!(
+      Articles::version_info(&article),
+      Some(VersionInfo {
+        author: user.into(),
+        block: 1,
+        time: 6_000,
+        parent: None,
+        size: 11,
+        summary: summary(b"new article"),
+      })
+    );
+
+    // versions record the version they were made on top of
+    System::set_block_number(2);
+    let script = Script::new(vec![
+      OpCode::Reference(ArticleVersion(0)),
+      OpCode::Cp(3),
+      OpCode::Add(Body::new(" city".as_bytes().to_vec()).unwrap()),
+    ])
+    .unwrap();
+    assert_ok!(Articles::add_version(
+      RawOrigin::Signed(user).into(),
+      title.clone(),
+      None,
+      ArticleVersion(0),
+      false,
+      script
+    ));
+    let article = Article::new(article.title().clone(), ArticleVersion(1));
+    assert_eq!(
+      Articles::version_info(&article),
+      Some(VersionInfo {
+        author: user.into(),
+        block: 2,
+        time: 12_000,
+        parent: Some(ArticleVersion(0)),
+        size: 8,
+        summary: None,
+      })
+    );
+
+    // summaries are text
+    let script = Script::new(vec![OpCode::Reference(ArticleVersion(1)), OpCode::Cp(3)]).unwrap();
+    assert_noop!(
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title,
+        summary(&[0xff]),
+        ArticleVersion(1),
+        false,
+        script
+      ),
+      pallet::Error::<Test>::InvalidUtf8
+    );
+  })
+}
+
+#[test]
+fn revert() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let title = Title::new(b"paris".to_vec()).unwrap();
+
+    let script =
+      Script::new(vec![OpCode::Add(Body::new(b"the capital".to_vec()).unwrap())]).unwrap();
+    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script));
+    let vandalism =
+      Script::new(vec![OpCode::Add(Body::new(b"vandalized".to_vec()).unwrap())]).unwrap();
+    assert_ok!(Articles::add_version(
+      RawOrigin::Signed(user).into(),
+      title.clone(),
+      None,
+      ArticleVersion(0),
+      false,
+      vandalism
+    ));
+
+    assert_noop!(
+      Articles::revert(RawOrigin::Signed(user).into(), title.clone(), ArticleVersion(2)),
+      pallet::Error::<Test>::InvalidReference
+    );
+
+    let reverter = insecure_pair_from_name("reverter").public();
+    assert_ok!(Articles::revert(
+      RawOrigin::Signed(reverter).into(),
+      title.clone(),
+      ArticleVersion(0)
+    ));
+    let article = Article::new(title.clone(), ArticleVersion(2));
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::Reverted {
+      article: article.clone(),
+      to: ArticleVersion(0),
+      author: reverter,
+    }));
+
+    // the new version only references the old one
+    assert_eq!(
+      Articles::articles(&article),
+      Some(Script::new(vec![OpCode::Reference(ArticleVersion(0)), OpCode::Cp(11)]).unwrap())
+    );
+    assert_eq!(Articles::render(&article).unwrap(), b"the capital");
+    assert_eq!(Articles::body_len(&article), Some(11));
+    assert_eq!(Articles::version_info(&article).unwrap().parent, Some(ArticleVersion(0)));
+    assert_eq!(Articles::authors(&article), Some(reverter));
+  })
+}
+
+#[test]
+fn revert_deep_versions() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let title = Title::new(b"paris".to_vec()).unwrap();
+    let article = |version| Article::new(title.clone(), ArticleVersion(version));
+    let add_version = |version: u32, script: Script| {
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(version - 1),
+        false,
+        script,
+      )
+    };
+
+    // a version 17 scripts deep, followed by enough versions for edits not to rebase over it
+    let script = Script::new(vec![OpCode::Add(Body::new(b"a".to_vec()).unwrap())]).unwrap();
+    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script));
+    let edit = |version, len| {
+      Script::new(vec![
+        OpCode::Reference(ArticleVersion(version - 1)),
+        OpCode::Cp(len),
+        OpCode::Add(Body::new(b"b".to_vec()).unwrap()),
+      ])
+      .unwrap()
+    };
+    for version in 1 ..= MAX_RENDER_DEPTH {
+      assert_ok!(add_version(version, edit(version, version)));
+    }
+    let deep = article(MAX_RENDER_DEPTH);
+    assert_eq!(Articles::render_depth(&deep), MAX_RENDER_DEPTH);
+    let body = [b"a".to_vec(), vec![b'b'; 16]].concat();
+    for version in (MAX_RENDER_DEPTH + 1) ..= (MAX_RENDER_DEPTH + MAX_REBASE_DEPTH) {
+      assert_ok!(add_version(version, Script::full_text(&body).unwrap()));
+    }
+    assert_eq!(Articles::articles(&deep), Some(edit(MAX_RENDER_DEPTH, MAX_RENDER_DEPTH)));
+
+    // referencing it would be too deep, so the revert copies its script
+    assert_ok!(Articles::revert(
+      RawOrigin::Signed(user).into(),
+      title.clone(),
+      ArticleVersion(MAX_RENDER_DEPTH)
+    ));
+    let reverted = article(MAX_RENDER_DEPTH + MAX_REBASE_DEPTH + 1);
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::Reverted {
+      article: reverted.clone(),
+      to: ArticleVersion(MAX_RENDER_DEPTH),
+      author: user,
+    }));
+    assert_eq!(Articles::articles(&reverted), Some(edit(MAX_RENDER_DEPTH, MAX_RENDER_DEPTH)));
+    assert_eq!(Articles::render_depth(&reverted), MAX_RENDER_DEPTH);
+    assert_eq!(Articles::body_len(&reverted), Some(17));
+
+    // as do reverts to the revert
+    assert_ok!(Articles::revert(RawOrigin::Signed(user).into(), title.clone(), reverted.version()));
+    assert_eq!(
+      Articles::articles(article(reverted.version().0 + 1)),
+      Some(edit(MAX_RENDER_DEPTH, MAX_RENDER_DEPTH))
+    );
+  })
+}
+
+#[test]
+fn edit_conflicts() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let title = Title::new(b"fox".to_vec()).unwrap();
+    let add = |data: &str| OpCode::Add(Body::new(data.as_bytes().to_vec()).unwrap());
+    let add_version = |script: Vec<OpCode>, expected: u32, rebase: bool| {
+      Articles::add_version(
+        RawOrigin::Signed(user).into(),
+        title.clone(),
+        None,
+        ArticleVersion(expected),
+        rebase,
+        Script::new(script).unwrap(),
+      )
+    };
+
+    let script = Script::new(vec![add("the quick brown fox")]).unwrap();
+    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title.clone(), None, script));
+
+    // replace "quick" with "slow"
+    let base = OpCode::Reference(ArticleVersion(0));
+    let slow = vec![
+      base.clone(),
+      OpCode::Cp(4),
+      add("slow"),
+      OpCode::MvCr(Direction::Right, 9),
+      OpCode::Cp(10),
+    ];
+    assert_ok!(add_version(slow, 0, false));
+
+    // an edit of the first version is rejected now
+    let dog = vec![base.clone(), OpCode::Cp(16), add("dog")];
+    assert_noop!(add_version(dog.clone(), 0, false), pallet::Error::<Test>::EditConflict);
+    // as are edits claiming to be on top of versions which don't exist
+    let copy = vec![OpCode::Reference(ArticleVersion(1)), OpCode::Cp(1)];
+    assert_noop!(add_version(copy, 2, true), pallet::Error::<Test>::EditConflict);
+
+    // unless it's rebased, as it doesn't touch the same part of the body
+    assert_ok!(add_version(dog, 0, true));
+    let article = Article::new(title.clone(), ArticleVersion(2));
+    assert_eq!(Articles::render(&article).unwrap(), b"the slow brown dog");
+    assert_eq!(Articles::body_len(&article), Some(18));
+    assert_eq!(Articles::version_info(&article).unwrap().parent, Some(ArticleVersion(1)));
+
+    // edits of the same part of the body can't be rebased
+    let quick =
+      vec![base, OpCode::Cp(4), add("fast"), OpCode::MvCr(Direction::Right, 9), OpCode::Cp(10)];
+    assert_noop!(add_version(quick, 0, true), pallet::Error::<Test>::EditConflict);
+  })
+}
+
+#[test]
+fn contributions() {
+  new_test_ext().execute_with(|| {
+    let alice = insecure_pair_from_name("alice").public();
+    let bob = insecure_pair_from_name("bob").public();
+    let title = |title: &str| Title::new(title.as_bytes().to_vec()).unwrap();
+    let script = || Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap();
+
+    assert_ok!(Articles::add_article(
+      RawOrigin::Signed(alice).into(),
+      title("Paris"),
+      None,
+      script()
+    ));
+    assert_ok!(Articles::add_article(RawOrigin::Signed(bob).into(), title("Rome"), None, script()));
+    assert_ok!(Articles::add_version(
+      RawOrigin::Signed(alice).into(),
+      title("Rome"),
+      None,
+      ArticleVersion(0),
+      false,
+      script()
+    ));
+    assert_ok!(Articles::revert(
+      RawOrigin::Signed(alice).into(),
+      title("Paris"),
+      ArticleVersion(0)
+    ));
+
+    let article = |name: &str, version| Article::new(title(name), ArticleVersion(version));
+    assert_eq!(Articles::contribution_count(alice), 3);
+    assert_eq!(
+      Articles::contributions(alice, 0, 10),
+      vec![article("paris", 0), article("rome", 1), article("paris", 1)]
+    );
+    assert_eq!(Articles::contributions(alice, 1, 1), vec![article("rome", 1)]);
+    assert_eq!(Articles::contributions(alice, 3, 10), vec![]);
+    assert_eq!(Articles::contributions(bob, 0, 10), vec![article("rome", 0)]);
+
+    // contributions follow their article when it's moved
+    assert_ok!(Articles::move_article(
+      RawOrigin::Signed(bob).into(),
+      title("Rome"),
+      title("Roma"),
+      2
+    ));
+    assert_eq!(Articles::contributions(bob, 0, 10), vec![article("roma", 0)]);
+  })
+}
+
+#[test]
+fn migrate_contributions() {
+  new_test_ext().execute_with(|| {
+    let alice = insecure_pair_from_name("alice").public();
+    let bob = insecure_pair_from_name("bob").public();
+    let title = Title::new(b"paris".to_vec()).unwrap();
+    let article = |version| Article::new(title.clone(), ArticleVersion(version));
+
+    // store versions the way they were stored before they were indexed by author, more than are
+    // indexed per block
+    pallet::TitleCount::<Test>::set(1);
+    pallet::Titles::<Test>::set(&title, Some(0));
+    pallet::TitlesByIndex::<Test>::set(0, Some(title.clone()));
+    pallet::LastVersion::<Test>::set(&title, Some(ArticleVersion(MIGRATION_STEPS)));
+    for (version, author) in [(0, alice), (1, bob), (2, alice)] {
+      pallet::Authors::<Test>::set(article(version), Some(author));
+    }
+    for version in 3 ..= MIGRATION_STEPS {
+      pallet::Authors::<Test>::set(article(version), Some(bob));
+    }
+    StorageVersion::new(4).put::<Articles>();
+
+    assert_eq!(migrate(), 2);
+    assert_eq!(StorageVersion::get::<Articles>(), StorageVersion::new(5));
+
+    assert_eq!(Articles::contributions(alice, 0, 10), vec![article(0), article(2)]);
+    assert_eq!(Articles::contributions(bob, 0, 2), vec![article(1), article(3)]);
+    assert_eq!(Articles::contribution_count(bob), MIGRATION_STEPS - 1);
+  })
+}
+
+#[test]
+fn rate_limits() {
+  new_test_ext().execute_with(|| {
+    let alice = insecure_pair_from_name("alice").public();
+    let bob = insecure_pair_from_name("bob").public();
+    let paris = Title::new(b"paris".to_vec()).unwrap();
+    let roma = Title::new(b"roma".to_vec()).unwrap();
+    let script = || Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap();
+    let add_version = |author: Public, title: &Title, expected: u32| {
+      let script = Script::new(vec![
+        OpCode::Reference(ArticleVersion(expected)),
+        OpCode::Cp(4),
+        OpCode::Add(Body::new(b"!".to_vec()).unwrap()),
+      ])
+      .unwrap();
+      Articles::add_version(
+        RawOrigin::Signed(author).into(),
+        title.clone(),
+        None,
+        ArticleVersion(expected),
+        false,
+        script,
+      )
+    };
+
+    // only governance may set the limits, which need a title window
+    let limits = RateLimits { versions_per_title: 2, title_window: 10, edits_per_session: 3 };
+    assert_noop!(Articles::set_rate_limits(RawOrigin::Signed(alice).into(), limits), BadOrigin);
+    assert_noop!(
+      Articles::set_rate_limits(RawOrigin::Root.into(), RateLimits { title_window: 0, ..limits }),
+      pallet::Error::<Test>::InvalidRateLimits
+    );
+    assert_ok!(Articles::set_rate_limits(RawOrigin::Root.into(), limits));
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::RateLimitsSet { limits }));
+    assert_eq!(Articles::rate_limits(), limits);
+
+    // a title gets at most two versions every ten blocks, whoever adds them
+    assert_ok!(Articles::add_article(
+      RawOrigin::Signed(alice).into(),
+      paris.clone(),
+      None,
+      script()
+    ));
+    assert_ok!(add_version(alice, &paris, 0));
+    assert_noop!(add_version(bob, &paris, 1), pallet::Error::<Test>::TitleRateLimited);
+
+    // as are reverts and moves
+    assert_noop!(
+      Articles::revert(RawOrigin::Signed(bob).into(), paris.clone(), ArticleVersion(0)),
+      pallet::Error::<Test>::TitleRateLimited
+    );
+    let paname = Title::new(b"paname".to_vec()).unwrap();
+    assert_noop!(
+      Articles::move_article(RawOrigin::Signed(bob).into(), paris.clone(), paname.clone(), 2),
+      pallet::Error::<Test>::TitleRateLimited
+    );
+
+    // transactions the pallet would reject aren't included, and any transaction is let in the pool
+    let pre_dispatch = |author: Public, call: pallet::Call<Test>| {
+      CheckRateLimits::<Test>::new().pre_dispatch(
+        &author,
+        &RuntimeCall::Articles(call),
+        &Default::default(),
+        0,
+      )
+    };
+    let edit = |expected| pallet::Call::<Test>::add_version {
+      title: paris.clone(),
+      summary: None,
+      expected_last_version: ArticleVersion(expected),
+      rebase: false,
+      script: script(),
+    };
+    let revert =
+      pallet::Call::<Test>::revert { title: paris.clone(), to_version: ArticleVersion(0) };
+    assert_eq!(pre_dispatch(bob, edit(1)), Err(InvalidTransaction::Custom(RATE_LIMITED).into()));
+    assert_eq!(
+      pre_dispatch(bob, revert.clone()),
+      Err(InvalidTransaction::Custom(RATE_LIMITED).into())
+    );
+    let move_article =
+      pallet::Call::<Test>::move_article { from: paris.clone(), to: paname, versions: 2 };
+    assert_eq!(
+      pre_dispatch(bob, move_article),
+      Err(InvalidTransaction::Custom(RATE_LIMITED).into())
+    );
+    assert_eq!(
+      CheckRateLimits::<Test>::new().validate(
+        &bob,
+        &RuntimeCall::Articles(edit(1)),
+        &Default::default(),
+        0
+      ),
+      Ok(Default::default())
+    );
+
+    // the next window of blocks allows more versions
+    System::set_block_number(10);
+    assert_ok!(pre_dispatch(bob, edit(1)));
+    assert_ok!(pre_dispatch(bob, revert));
+    assert_ok!(pre_dispatch(
+      bob,
+      pallet::Call::<Test>::tag { title: paris.clone(), category: roma.clone() }
+    ));
+    assert_ok!(Articles::revert(RawOrigin::Signed(bob).into(), paris.clone(), ArticleVersion(0)));
+    assert_ok!(add_version(alice, &paris, 2));
+    assert_eq!(Articles::title_edits(&paris), Some((1, 2)));
+
+    // while an account adds at most three versions per session
+    assert_eq!(Articles::account_edits(alice), Some((0, 3)));
+    assert!(pre_dispatch(
+      alice,
+      pallet::Call::<Test>::add_article { title: roma.clone(), summary: None, script: script() }
+    )
+    .is_err());
+    assert_noop!(
+      Articles::add_article(RawOrigin::Signed(alice).into(), roma.clone(), None, script()),
+      pallet::Error::<Test>::AccountRateLimited
+    );
+    Session::set(1);
+    assert_ok!(Articles::add_article(RawOrigin::Signed(alice).into(), roma, None, script()));
+    assert_eq!(Articles::account_edits(alice), Some((1, 1)));
+  })
+}
+
+#[test]
+fn pending_versions() {
+  new_test_ext().execute_with(|| {
+    let alice = insecure_pair_from_name("alice").public();
+    let bob = insecure_pair_from_name("bob").public();
+    let carol = insecure_pair_from_name("carol").public();
+    let reviewer = insecure_pair_from_name("reviewer").public();
+    let paris = Title::new(b"paris".to_vec()).unwrap();
+    let add_version = |author: Public, expected: u32, text: &[u8]| {
+      let script = Script::new(vec![
+        OpCode::Reference(ArticleVersion(expected)),
+        OpCode::Cp(11),
+        OpCode::Add(Body::new(text.to_vec()).unwrap()),
+      ])
+      .unwrap();
+      Articles::add_version(
+        RawOrigin::Signed(author).into(),
+        paris.clone(),
+        None,
+        ArticleVersion(expected),
+        false,
+        script,
+      )
+    };
+
+    assert_ok!(Articles::add_article(
+      RawOrigin::Signed(alice).into(),
+      paris.clone(),
+      None,
+      Script::new(vec![OpCode::Add(Body::new(b"the capital".to_vec()).unwrap())]).unwrap()
+    ));
+
+    // titles protected at genesis don't have to exist yet
+    assert!(Articles::protected(Title::new(b"main page".to_vec()).unwrap()).is_some());
+
+    // only governance protects articles, which must exist
+    assert_noop!(
+      Articles::set_protected(RawOrigin::Signed(alice).into(), paris.clone(), true),
+      BadOrigin
+    );
+    assert_noop!(
+      Articles::set_protected(RawOrigin::Root.into(), Title::new(b"roma".to_vec()).unwrap(), true),
+      pallet::Error::<Test>::InvalidTitle
+    );
+    assert_ok!(Articles::set_protected(RawOrigin::Root.into(), paris.clone(), true));
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::ProtectionSet {
+      title: paris.clone(),
+      protected: true,
+    }));
+
+    // versions of protected articles wait for review
+    assert_ok!(add_version(bob, 0, b" of france"));
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::VersionPending {
+      title: paris.clone(),
+      id: 0,
+      author: bob,
+    }));
+    assert_ok!(add_version(alice, 0, b" city"));
+    assert_eq!(Articles::last_version(&paris), Some(ArticleVersion(0)));
+    assert_eq!(Articles::pending_versions(&paris), vec![0, 1]);
+    assert_eq!(Articles::pending_count(&paris), 2);
+    assert_eq!(Articles::pending_version(&paris, 0).unwrap().base, ArticleVersion(0));
+
+    // the article can't be moved away from them
+    assert_noop!(
+      Articles::move_article(
+        RawOrigin::Signed(reviewer).into(),
+        paris.clone(),
+        Title::new(b"paname".to_vec()).unwrap(),
+        1
+      ),
+      pallet::Error::<Test>::HasPendingVersions
+    );
+
+    // only reviewers accept versions, and never their own
+    assert_noop!(
+      Articles::accept_version(RawOrigin::Signed(carol).into(), paris.clone(), 0),
+      pallet::Error::<Test>::NotReviewer
+    );
+    assert_noop!(
+      Articles::accept_version(RawOrigin::Signed(reviewer).into(), paris.clone(), 2),
+      pallet::Error::<Test>::UnknownPendingVersion
+    );
+    assert_ok!(Articles::accept_version(RawOrigin::Signed(reviewer).into(), paris.clone(), 0));
+    let article = Article::new(paris.clone(), ArticleVersion(1));
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::VersionAccepted {
+      article: article.clone(),
+      id: 0,
+      author: bob,
+      reviewer,
+    }));
+    assert_eq!(Articles::render(&article).unwrap(), b"the capital of france");
+    assert_eq!(Articles::authors(&article), Some(bob));
+    assert_eq!(Articles::pending_versions(&paris), vec![1]);
+
+    // authoring versions doesn't make an account a reviewer, and reviewers never review their own
+    pallet::ContributionCounts::<Test>::set(carol, 1000);
+    assert!(!Articles::is_reviewer(&carol));
+    assert_ok!(add_version(reviewer, 1, b"!"));
+    assert_noop!(
+      Articles::accept_version(RawOrigin::Signed(reviewer).into(), paris.clone(), 2),
+      pallet::Error::<Test>::SelfReview
+    );
+
+    // authors may withdraw their versions, which only reviewers may otherwise reject
+    assert_noop!(
+      Articles::reject_version(RawOrigin::Signed(bob).into(), paris.clone(), 1),
+      pallet::Error::<Test>::NotReviewer
+    );
+    assert_ok!(Articles::reject_version(RawOrigin::Signed(alice).into(), paris.clone(), 1));
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::VersionRejected {
+      title: paris.clone(),
+      id: 1,
+      author: alice,
+      reviewer: alice,
+    }));
+    assert_ok!(Articles::reject_version(RawOrigin::Signed(reviewer).into(), paris.clone(), 2));
+    assert!(Articles::pending_versions(&paris).is_empty());
+    assert_eq!(Articles::pending_count(&paris), 0);
+
+    // reverts wait for review too, and are added as they are once versions were accepted since
+    assert_ok!(Articles::revert(RawOrigin::Signed(carol).into(), paris.clone(), ArticleVersion(0)));
+    System::assert_last_event(RuntimeEvent::Articles(pallet::Event::VersionPending {
+      title: paris.clone(),
+      id: 3,
+      author: carol,
+    }));
+    assert_eq!(Articles::last_version(&paris), Some(ArticleVersion(1)));
+    assert_ok!(add_version(bob, 1, b"!"));
+    assert_ok!(Articles::accept_version(RawOrigin::Signed(reviewer).into(), paris.clone(), 4));
+    assert_ok!(Articles::accept_version(RawOrigin::Signed(reviewer).into(), paris.clone(), 3));
+    let article = Article::new(paris.clone(), ArticleVersion(3));
+    assert_eq!(Articles::render(&article).unwrap(), b"the capital");
+    assert_eq!(Articles::authors(&article), Some(carol));
+
+    // the queue of every article is bounded
+    for _ in 0 .. MAX_PENDING_VERSIONS {
+      assert_ok!(add_version(bob, 1, b"!"));
+    }
+    assert_noop!(add_version(bob, 1, b"!"), pallet::Error::<Test>::TooManyPendingVersions);
+
+    // unprotected articles take versions right away
+    assert_ok!(Articles::set_protected(RawOrigin::Root.into(), paris.clone(), false));
+    assert_ok!(add_version(bob, 3, b"!"));
+    assert_eq!(Articles::last_version(&paris), Some(ArticleVersion(4)));
   })
 }
@@ -0,0 +1,228 @@
+//! Weights for the Articles pallet.
+//!
+//! Generated by running the benchmarks in `benchmarking.rs` natively in the mock runtime of the
+//! pallet, over 10 steps of every component repeated 5 times, with a state backend counting the
+//! storage accesses as the benchmarking state of the node does. The execution times and proof sizes
+//! are of native code over the in-memory trie of the mock, not of the wasm runtime on the reference
+//! hardware, so they are to be replaced by running `wikiblocks-node benchmark pallet --chain dev
+//! --pallet articles --extrinsic '*' --steps 50 --repeat 20 --output
+//! src/articles/pallet/src/weights.rs` on it.
+
+use core::marker::PhantomData;
+
+use frame_support::{
+  traits::Get,
+  weights::{constants::RocksDbWeight, Weight},
+};
+
+pub trait WeightInfo {
+  fn add_article(l: u32) -> Weight;
+  fn add_version(l: u32, o: u32) -> Weight;
+  fn add_redirect() -> Weight;
+  fn move_article(v: u32) -> Weight;
+  fn tag() -> Weight;
+  fn untag() -> Weight;
+  fn revert() -> Weight;
+  fn rebase() -> Weight;
+  fn set_rate_limits() -> Weight;
+  fn set_protected() -> Weight;
+  fn accept_version() -> Weight;
+  fn reject_version() -> Weight;
+}
+
+/// Weights for the Articles pallet, using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+  // Reads: AccountEdits, ContributionCounts, EditRateLimits, LastVersion, Redirects, Skeletons,
+  // TitleCount, TitleEdits
+  // Writes: AccountEdits, Articles, AuthorContributions, Authors, BodyLengths, ContributionCounts,
+  // DisplayTitles, LastVersion, ScriptHashes, Skeletons, TitleCount, TitleEdits, Titles,
+  // TitlesByIndex, VersionInfos
+  // The range of component `l` is `[1, 1047576]`.
+  fn add_article(l: u32) -> Weight {
+    Weight::from_parts(448_730_538, 122)
+      .saturating_add(Weight::from_parts(3_472, 0).saturating_mul(l.into()))
+      .saturating_add(T::DbWeight::get().reads(8))
+      .saturating_add(T::DbWeight::get().writes(15))
+  }
+
+  // Reads: AccountEdits, BodyLengths (16 keys), ContributionCounts, EditRateLimits, LastVersion,
+  // ProtectedTitles, RenderDepths (16 keys), ScriptHashes, TitleEdits, Titles
+  // Writes: AccountEdits, Articles, AuthorContributions, Authors, BodyLengths, ContributionCounts,
+  // LastVersion, RenderDepths, ScriptHashes, TitleEdits, VersionInfos
+  // The range of component `l` is `[1, 1040576]`.
+  // The range of component `o` is `[1, 1000]`.
+  fn add_version(l: u32, o: u32) -> Weight {
+    Weight::from_parts(4_298_191_000, 16_351)
+      .saturating_add(Weight::from_parts(1_716, 0).saturating_mul(l.into()))
+      .saturating_add(Weight::from_parts(5_086_206, 15).saturating_mul(o.into()))
+      .saturating_add(T::DbWeight::get().reads(26))
+      .saturating_add(T::DbWeight::get().writes(11))
+  }
+
+  // Reads: LastVersion (9 keys), RedirectDepths, Redirects (8 keys), Skeletons
+  // Writes: RedirectDepths, Redirects, Skeletons
+  fn add_redirect() -> Weight {
+    Weight::from_parts(419_561_000, 16_470)
+      .saturating_add(T::DbWeight::get().reads(19))
+      .saturating_add(T::DbWeight::get().writes(3))
+  }
+
+  // Reads: ArticleCategories (33 keys), Articles (2000 keys), Authors (2000 keys), BodyLengths
+  // (2000 keys), Checkpoints (2000 keys), LastVersion (twice), NextPendingIds (twice),
+  // PendingCounts, ProtectedTitles (twice), RedirectDepths, Redirects, RenderDepths (2000 keys),
+  // ScriptHashes (2000 keys), Skeletons, TitleEdits (twice), Titles, VersionInfos (2000 keys)
+  // Writes: ArticleCategories (64 keys), Articles (2000 keys), Authors (2000 keys), BodyLengths
+  // (2000 keys), Categories (64 keys), Checkpoints (2000 keys), DisplayTitles (twice), LastVersion
+  // (twice), NextPendingIds (twice), ProtectedTitles (twice), RedirectDepths (twice), Redirects
+  // (twice), RenderDepths (2000 keys), ScriptHashes (2000 keys), Skeletons, TitleEdits (twice),
+  // Titles (twice), TitlesByIndex, VersionInfos (2000 keys)
+  // The range of component `v` is `[1, 1000]`.
+  fn move_article(v: u32) -> Weight {
+    Weight::from_parts(1_696_995_000, 42_825)
+      .saturating_add(Weight::from_parts(196_193_381, 4_389).saturating_mul(v.into()))
+      .saturating_add(T::DbWeight::get().reads(46))
+      .saturating_add(T::DbWeight::get().reads(14).saturating_mul(v.into()))
+      .saturating_add(T::DbWeight::get().writes(146))
+      .saturating_add(T::DbWeight::get().writes(14).saturating_mul(v.into()))
+  }
+
+  // Reads: ArticleCategories (32 keys), Categories, CategorySizes, LastVersion (9 keys), Redirects
+  // (8 keys)
+  // Writes: ArticleCategories, Categories, CategorySizes
+  fn tag() -> Weight {
+    Weight::from_parts(427_238_000, 54_975)
+      .saturating_add(T::DbWeight::get().reads(51))
+      .saturating_add(T::DbWeight::get().writes(3))
+  }
+
+  // Reads: Categories, CategorySizes, LastVersion (9 keys), Redirects (8 keys)
+  // Writes: ArticleCategories, Categories, CategorySizes
+  fn untag() -> Weight {
+    Weight::from_parts(292_459_000, 21_639)
+      .saturating_add(T::DbWeight::get().reads(19))
+      .saturating_add(T::DbWeight::get().writes(3))
+  }
+
+  // Reads: AccountEdits, BodyLengths, ContributionCounts, EditRateLimits, LastVersion,
+  // ProtectedTitles, RenderDepths, TitleEdits, Titles
+  // Writes: AccountEdits, Articles, AuthorContributions, Authors, BodyLengths, ContributionCounts,
+  // LastVersion, RenderDepths, ScriptHashes, TitleEdits, VersionInfos
+  fn revert() -> Weight {
+    Weight::from_parts(174_635_000, 5_141)
+      .saturating_add(T::DbWeight::get().reads(9))
+      .saturating_add(T::DbWeight::get().writes(11))
+  }
+
+  // Reads: Articles (8 keys), BodyLengths (9 keys)
+  fn rebase() -> Weight {
+    Weight::from_parts(120_922_000, 18_827).saturating_add(T::DbWeight::get().reads(17))
+  }
+
+  // Writes: EditRateLimits
+  fn set_rate_limits() -> Weight {
+    Weight::from_parts(11_753_000, 0).saturating_add(T::DbWeight::get().writes(1))
+  }
+
+  // Reads: LastVersion
+  // Writes: ProtectedTitles
+  fn set_protected() -> Weight {
+    Weight::from_parts(92_989_000, 1_467)
+      .saturating_add(T::DbWeight::get().reads(1))
+      .saturating_add(T::DbWeight::get().writes(1))
+  }
+
+  // Reads: ContributionCounts, LastVersion, PendingCounts, PendingVersions, Titles
+  // Writes: Articles, AuthorContributions, Authors, BodyLengths, ContributionCounts, LastVersion,
+  // PendingCounts, PendingVersions, RenderDepths, ScriptHashes, VersionInfos
+  fn accept_version() -> Weight {
+    Weight::from_parts(176_783_000, 5_086)
+      .saturating_add(T::DbWeight::get().reads(5))
+      .saturating_add(T::DbWeight::get().writes(11))
+  }
+
+  // Reads: PendingCounts, PendingVersions
+  // Writes: PendingCounts, PendingVersions
+  fn reject_version() -> Weight {
+    Weight::from_parts(109_420_000, 2_703)
+      .saturating_add(T::DbWeight::get().reads(2))
+      .saturating_add(T::DbWeight::get().writes(2))
+  }
+}
+
+// For tests, and backwards compatibility
+impl WeightInfo for () {
+  fn add_article(l: u32) -> Weight {
+    Weight::from_parts(448_730_538, 122)
+      .saturating_add(Weight::from_parts(3_472, 0).saturating_mul(l.into()))
+      .saturating_add(RocksDbWeight::get().reads(8))
+      .saturating_add(RocksDbWeight::get().writes(15))
+  }
+
+  fn add_version(l: u32, o: u32) -> Weight {
+    Weight::from_parts(4_298_191_000, 16_351)
+      .saturating_add(Weight::from_parts(1_716, 0).saturating_mul(l.into()))
+      .saturating_add(Weight::from_parts(5_086_206, 15).saturating_mul(o.into()))
+      .saturating_add(RocksDbWeight::get().reads(26))
+      .saturating_add(RocksDbWeight::get().writes(11))
+  }
+
+  fn add_redirect() -> Weight {
+    Weight::from_parts(419_561_000, 16_470)
+      .saturating_add(RocksDbWeight::get().reads(19))
+      .saturating_add(RocksDbWeight::get().writes(3))
+  }
+
+  fn move_article(v: u32) -> Weight {
+    Weight::from_parts(1_696_995_000, 42_825)
+      .saturating_add(Weight::from_parts(196_193_381, 4_389).saturating_mul(v.into()))
+      .saturating_add(RocksDbWeight::get().reads(46))
+      .saturating_add(RocksDbWeight::get().reads(14).saturating_mul(v.into()))
+      .saturating_add(RocksDbWeight::get().writes(146))
+      .saturating_add(RocksDbWeight::get().writes(14).saturating_mul(v.into()))
+  }
+
+  fn tag() -> Weight {
+    Weight::from_parts(427_238_000, 54_975)
+      .saturating_add(RocksDbWeight::get().reads(51))
+      .saturating_add(RocksDbWeight::get().writes(3))
+  }
+
+  fn untag() -> Weight {
+    Weight::from_parts(292_459_000, 21_639)
+      .saturating_add(RocksDbWeight::get().reads(19))
+      .saturating_add(RocksDbWeight::get().writes(3))
+  }
+
+  fn revert() -> Weight {
+    Weight::from_parts(174_635_000, 5_141)
+      .saturating_add(RocksDbWeight::get().reads(9))
+      .saturating_add(RocksDbWeight::get().writes(11))
+  }
+
+  fn rebase() -> Weight {
+    Weight::from_parts(120_922_000, 18_827).saturating_add(RocksDbWeight::get().reads(17))
+  }
+
+  fn set_rate_limits() -> Weight {
+    Weight::from_parts(11_753_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+  }
+
+  fn set_protected() -> Weight {
+    Weight::from_parts(92_989_000, 1_467)
+      .saturating_add(RocksDbWeight::get().reads(1))
+      .saturating_add(RocksDbWeight::get().writes(1))
+  }
+
+  fn accept_version() -> Weight {
+    Weight::from_parts(176_783_000, 5_086)
+      .saturating_add(RocksDbWeight::get().reads(5))
+      .saturating_add(RocksDbWeight::get().writes(11))
+  }
+
+  fn reject_version() -> Weight {
+    Weight::from_parts(109_420_000, 2_703)
+      .saturating_add(RocksDbWeight::get().reads(2))
+      .saturating_add(RocksDbWeight::get().writes(2))
+  }
+}
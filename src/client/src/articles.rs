@@ -1,36 +1,541 @@
-use scale::Encode;
+use std::collections::HashMap;
+
+use scale::{Encode, Decode};
 
 use sp_core::sr25519::Public;
 
-use wikiblocks_abi::primitives::{Article, Script, Title};
+use wikiblocks_abi::primitives::{
+  render, Article, ArticleVersion, EditSummary, PendingVersion, RateLimits, Script, Title,
+  VersionInfo,
+};
+use wikiblocks_abi::primitives::{MAX_PAGE_LEN, MAX_REDIRECT_DEPTH};
 pub use wikiblocks_abi::coins::primitives;
 
-use crate::{TemporalWikiblocks, WikiblocksError};
+use crate::{Wikiblocks, TemporalWikiblocks, WikiblocksError, Transaction};
 
 const PALLET: &str = "Articles";
 
+pub type ArticlesEvent = wikiblocks_abi::articles::Event;
+
 #[derive(Clone, Copy)]
 pub struct WikiblocksArticles<'a>(pub(crate) &'a TemporalWikiblocks<'a>);
 impl<'a> WikiblocksArticles<'a> {
-  pub fn add_article(title: Title, script: Script) -> wikiblocks_abi::Call {
-    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::add_article { title, script })
+  pub fn add_article(
+    title: Title,
+    script: Script,
+    summary: Option<EditSummary>,
+  ) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::add_article {
+      title,
+      script,
+      summary,
+    })
   }
 
-  pub fn add_version(title: Title, script: Script) -> wikiblocks_abi::Call {
-    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::add_version { title, script })
+  /// Adds a version written on top of `expected_last_version`, which fails with an edit conflict
+  /// if another version was added since. With `rebase`, the version is instead rebased over the
+  /// versions added since if none of them edited the same parts of the body.
+  ///
+  /// `check_edit` reports whether the version is still the last one before this is published.
+  ///
+  /// Versions which would replay more than `MAX_RENDER_DEPTH` scripts to render fail with
+  /// `RenderTooDeep`, and have to be added as full text instead.
+  pub fn add_version(
+    title: Title,
+    script: Script,
+    summary: Option<EditSummary>,
+    expected_last_version: ArticleVersion,
+    rebase: bool,
+  ) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::add_version {
+      title,
+      script,
+      summary,
+      expected_last_version,
+      rebase,
+    })
   }
 
-  pub async fn article(&self, article: Article) -> Result<Option<Script>, WikiblocksError> {
+  /// Makes a title without an article lead to an article, which only its author may do.
+  pub fn add_redirect(from: Title, to: Title) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::add_redirect { from, to })
+  }
+
+  /// Moves an article to another title. `versions` is the amount of versions the article has.
+  pub fn move_article(from: Title, to: Title, versions: u32) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::move_article {
+      from,
+      to,
+      versions,
+    })
+  }
+
+  /// Adds a version with the same body as an existing version of the article.
+  pub fn revert(title: Title, to_version: ArticleVersion) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::revert { title, to_version })
+  }
+
+  /// Accepts a version of a protected article waiting for review, which only reviewers may do.
+  pub fn accept_version(title: Title, id: u32) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::accept_version { title, id })
+  }
+
+  /// Rejects a version of a protected article waiting for review, or withdraws it if it's from
+  /// the signer.
+  pub fn reject_version(title: Title, id: u32) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::reject_version { title, id })
+  }
+
+  pub fn tag(title: Title, category: Title) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::tag { title, category })
+  }
+
+  pub fn untag(title: Title, category: Title) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Articles(wikiblocks_abi::articles::Call::untag { title, category })
+  }
+
+  pub async fn article_created_events(&self) -> Result<Vec<ArticlesEvent>, WikiblocksError> {
     self
       .0
-      .storage(PALLET, "Articles", (sp_core::hashing::blake2_128(&article.encode()), article))
+      .events(|event| {
+        if let wikiblocks_abi::Event::Articles(event) = event {
+          if matches!(event, ArticlesEvent::ArticleCreated { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
       .await
   }
 
-  pub async fn author(&self, article: Article) -> Result<Option<Public>, WikiblocksError> {
+  pub async fn version_added_events(&self) -> Result<Vec<ArticlesEvent>, WikiblocksError> {
+    self
+      .0
+      .events(|event| {
+        if let wikiblocks_abi::Event::Articles(event) = event {
+          if matches!(event, ArticlesEvent::VersionAdded { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
+      .await
+  }
+
+  pub async fn redirect_added_events(&self) -> Result<Vec<ArticlesEvent>, WikiblocksError> {
+    self
+      .0
+      .events(|event| {
+        if let wikiblocks_abi::Event::Articles(event) = event {
+          if matches!(event, ArticlesEvent::RedirectAdded { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
+      .await
+  }
+
+  pub async fn article_moved_events(&self) -> Result<Vec<ArticlesEvent>, WikiblocksError> {
+    self
+      .0
+      .events(|event| {
+        if let wikiblocks_abi::Event::Articles(event) = event {
+          if matches!(event, ArticlesEvent::ArticleMoved { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
+      .await
+  }
+
+  pub async fn reverted_events(&self) -> Result<Vec<ArticlesEvent>, WikiblocksError> {
+    self
+      .0
+      .events(|event| {
+        if let wikiblocks_abi::Event::Articles(event) = event {
+          if matches!(event, ArticlesEvent::Reverted { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
+      .await
+  }
+
+  /// Returns the canonical form of a title, which articles are stored under.
+  pub fn canonical(title: &Title) -> Result<Title, WikiblocksError> {
+    title.canonical().map_err(WikiblocksError::InvalidTitle)
+  }
+
+  /// Returns the title a title redirects to, if it's a redirect.
+  pub async fn redirect(&self, title: &Title) -> Result<Option<Title>, WikiblocksError> {
+    let title = Self::canonical(title)?;
+    self
+      .0
+      .storage(PALLET, "Redirects", (sp_core::hashing::blake2_128(&title.encode()), title))
+      .await
+  }
+
+  async fn stored_last_version(
+    &self,
+    title: &Title,
+  ) -> Result<Option<ArticleVersion>, WikiblocksError> {
+    self
+      .0
+      .storage(PALLET, "LastVersion", (sp_core::hashing::blake2_128(&title.encode()), title))
+      .await
+  }
+
+  /// Checks an edit written on top of `expected_last_version` can be added without rebasing it,
+  /// returning `WikiblocksError::EditConflict` if another version was added since.
+  ///
+  /// Versions are added under the title itself, so this doesn't follow redirects.
+  pub async fn check_edit(
+    &self,
+    title: &Title,
+    expected_last_version: ArticleVersion,
+  ) -> Result<(), WikiblocksError> {
+    let title = Self::canonical(title)?;
+    let last = self
+      .stored_last_version(&title)
+      .await?
+      .ok_or(WikiblocksError::InvalidTitle("title doesn't have an article"))?;
+    if last != expected_last_version {
+      Err(WikiblocksError::EditConflict { expected: expected_last_version, last })?;
+    }
+    Ok(())
+  }
+
+  /// Returns the canonical title of the article a title leads to, following its redirects.
+  pub async fn resolve(&self, title: &Title) -> Result<Option<Title>, WikiblocksError> {
+    let mut title = Self::canonical(title)?;
+    for _ in 0 .. MAX_REDIRECT_DEPTH {
+      if self.stored_last_version(&title).await?.is_some() {
+        return Ok(Some(title));
+      }
+      let Some(target) = self.redirect(&title).await? else { return Ok(None) };
+      title = target;
+    }
+    Ok(self.stored_last_version(&title).await?.map(|_| title))
+  }
+
+  async fn resolve_article(&self, article: &Article) -> Result<Option<Article>, WikiblocksError> {
+    Ok(self.resolve(article.title()).await?.map(|title| Article::new(title, article.version())))
+  }
+
+  /// Returns the last version of an article, looking it up by any form of its title or any title
+  /// redirecting to it.
+  pub async fn last_version(
+    &self,
+    title: &Title,
+  ) -> Result<Option<ArticleVersion>, WikiblocksError> {
+    let Some(title) = self.resolve(title).await? else { return Ok(None) };
+    self.stored_last_version(&title).await
+  }
+
+  /// Returns the position of an article in the order the articles were created in, looking it up
+  /// by any form of its title or any title redirecting to it.
+  ///
+  /// Articles keep their position when they're moved.
+  pub async fn title_index(&self, title: &Title) -> Result<Option<u32>, WikiblocksError> {
+    let Some(title) = self.resolve(title).await? else { return Ok(None) };
+    self.0.storage(PALLET, "Titles", (sp_core::hashing::blake2_128(&title.encode()), title)).await
+  }
+
+  /// Returns the title an article is displayed with, looking it up by any form of its title or
+  /// any title redirecting to it.
+  pub async fn display_title(&self, title: &Title) -> Result<Option<Title>, WikiblocksError> {
+    let Some(title) = self.resolve(title).await? else { return Ok(None) };
     self
       .0
-      .storage(PALLET, "Authors", (sp_core::hashing::blake2_128(&article.encode()), article))
+      .storage(PALLET, "DisplayTitles", (sp_core::hashing::blake2_128(&title.encode()), title))
       .await
   }
+
+  /// Returns up to `limit` canonical titles, in the order the articles were created in, starting
+  /// with the title at position `start`.
+  ///
+  /// The node returns at most `MAX_PAGE_LEN` titles at once, so fewer titles than requested
+  /// doesn't mean there are no more titles.
+  pub async fn titles(&self, start: u32, limit: u32) -> Result<Vec<Title>, WikiblocksError> {
+    self.0.runtime_api("WikiblocksRuntimeApi_titles", (start, limit)).await
+  }
+
+  /// Returns up to `limit` canonical titles of the articles in a category, starting after the
+  /// title `after`.
+  ///
+  /// Titles are listed in storage order, so the last title of a page is passed as `after` to get
+  /// the next one. The node returns at most `MAX_PAGE_LEN` titles at once.
+  pub async fn category_members(
+    &self,
+    category: &Title,
+    after: Option<Title>,
+    limit: u32,
+  ) -> Result<Vec<Title>, WikiblocksError> {
+    self
+      .0
+      .runtime_api(
+        "WikiblocksRuntimeApi_category_members",
+        (Self::canonical(category)?, after, limit),
+      )
+      .await
+  }
+
+  /// Returns the amount of articles in a category.
+  pub async fn category_size(&self, category: &Title) -> Result<u32, WikiblocksError> {
+    let category = Self::canonical(category)?;
+    Ok(
+      self
+        .0
+        .storage(
+          PALLET,
+          "CategorySizes",
+          (sp_core::hashing::blake2_128(&category.encode()), category),
+        )
+        .await?
+        .unwrap_or(0),
+    )
+  }
+
+  /// Returns the canonical names of the categories of an article, following redirects.
+  pub async fn categories(&self, title: &Title) -> Result<Vec<Title>, WikiblocksError> {
+    self.0.runtime_api("WikiblocksRuntimeApi_categories", Self::canonical(title)?).await
+  }
+
+  /// Returns the amount of articles.
+  pub async fn title_count(&self) -> Result<u32, WikiblocksError> {
+    Ok(self.0.storage(PALLET, "TitleCount", ()).await?.unwrap_or(0))
+  }
+
+  /// Returns the versions an account authored, in the order they were added, starting with the
+  /// version at position `cursor` among them.
+  ///
+  /// Versions are listed under the current title of their article. The node returns at most
+  /// `MAX_PAGE_LEN` versions at once, so the cursor of the next page is `cursor` plus the amount
+  /// of versions returned.
+  pub async fn contributions(
+    &self,
+    account: Public,
+    cursor: u32,
+  ) -> Result<Vec<Article>, WikiblocksError> {
+    self.0.runtime_api("WikiblocksRuntimeApi_contributions", (account, cursor, MAX_PAGE_LEN)).await
+  }
+
+  /// Returns the limits on how often articles may be edited.
+  ///
+  /// Transactions adding versions over these limits are rejected by the node.
+  pub async fn rate_limits(&self) -> Result<RateLimits, WikiblocksError> {
+    Ok(self.0.storage(PALLET, "EditRateLimits", ()).await?.unwrap_or_default())
+  }
+
+  /// Returns the session the versions an account added were last counted in, and how many it
+  /// added within it.
+  pub async fn session_edits(
+    &self,
+    account: Public,
+  ) -> Result<Option<(u32, u32)>, WikiblocksError> {
+    self
+      .0
+      .storage(PALLET, "AccountEdits", (sp_core::hashing::blake2_128(&account.encode()), account))
+      .await
+  }
+
+  /// Returns the amount of versions an account authored.
+  pub async fn contribution_count(&self, account: Public) -> Result<u32, WikiblocksError> {
+    Ok(
+      self
+        .0
+        .storage(
+          PALLET,
+          "ContributionCounts",
+          (sp_core::hashing::blake2_128(&account.encode()), account),
+        )
+        .await?
+        .unwrap_or(0),
+    )
+  }
+
+  /// Returns if the versions of an article wait for review before they're added.
+  pub async fn is_protected(&self, title: &Title) -> Result<bool, WikiblocksError> {
+    let title = Self::canonical(title)?;
+    let protected: Option<()> = self
+      .0
+      .storage(PALLET, "ProtectedTitles", (sp_core::hashing::blake2_128(&title.encode()), title))
+      .await?;
+    Ok(protected.is_some())
+  }
+
+  /// Returns the ids of the versions of an article waiting for review, in the order they were
+  /// submitted.
+  pub async fn pending_versions(&self, title: &Title) -> Result<Vec<u32>, WikiblocksError> {
+    self.0.runtime_api("WikiblocksRuntimeApi_pending_versions", Self::canonical(title)?).await
+  }
+
+  /// Returns the version of an article its votes endorse, following redirects.
+  ///
+  /// This is the last version of the article unless one of its latest versions reached a quorum
+  /// of votes, and `None` if it doesn't exist.
+  ///
+  /// It's named after the runtime API rather than `canonical`, which returns the canonical form
+  /// of a title.
+  pub async fn canonical_version(
+    &self,
+    title: &Title,
+  ) -> Result<Option<ArticleVersion>, WikiblocksError> {
+    self.0.runtime_api("WikiblocksRuntimeApi_canonical_version", Self::canonical(title)?).await
+  }
+
+  /// Returns a version of an article waiting for review.
+  pub async fn pending_version(
+    &self,
+    title: &Title,
+    id: u32,
+  ) -> Result<Option<PendingVersion>, WikiblocksError> {
+    let title = Self::canonical(title)?;
+    self
+      .0
+      .storage(
+        PALLET,
+        "PendingVersions",
+        (sp_core::hashing::blake2_128(&title.encode()), title, id),
+      )
+      .await
+  }
+
+  // Fetches a version's entry from a map keyed by resolved articles.
+  async fn version_entry<R: scale::Decode>(
+    &self,
+    name: &'static str,
+    article: &Article,
+  ) -> Result<Option<R>, WikiblocksError> {
+    self.0.storage(PALLET, name, (sp_core::hashing::blake2_128(&article.encode()), article)).await
+  }
+
+  /// Returns the blake2-256 hash and encoded length of the extrinsic which added an article
+  /// version, if its script was indexed along with it.
+  pub async fn script_hash(
+    &self,
+    article: Article,
+  ) -> Result<Option<([u8; 32], u32)>, WikiblocksError> {
+    let Some(article) = self.resolve_article(&article).await? else { return Ok(None) };
+    self.version_entry("ScriptHashes", &article).await
+  }
+
+  /// Fetches an indexed script by the hash of the extrinsic which added it.
+  ///
+  /// Only nodes which keep the indexed data of the block which added the script have it.
+  pub async fn indexed_script(&self, hash: [u8; 32]) -> Result<Option<Script>, WikiblocksError> {
+    let data: Option<String> =
+      self.0.wikiblocks.call("articles_indexedExtrinsic", [hex::encode(hash)]).await?;
+    let Some(data) = data else { return Ok(None) };
+    let data = Wikiblocks::hex_decode(data)?;
+    if sp_core::hashing::blake2_256(&data) != hash {
+      Err(WikiblocksError::InvalidNode("returned an extrinsic with a different hash".to_string()))?;
+    }
+    let invalid =
+      || WikiblocksError::InvalidNode("indexed extrinsic didn't add a script".to_string());
+    let transaction = Transaction::decode(&mut data.as_slice()).map_err(|_| invalid())?;
+    match transaction.call() {
+      wikiblocks_abi::Call::Articles(
+        wikiblocks_abi::articles::Call::add_article { script, .. } |
+        wikiblocks_abi::articles::Call::add_version { script, .. },
+      ) => Ok(Some(script.clone())),
+      _ => Err(invalid()),
+    }
+  }
+
+  // Fetches the script of a resolved article version, from state or by its hash.
+  async fn script(&self, article: &Article) -> Result<Option<Script>, WikiblocksError> {
+    if let Some(script) = self.version_entry("Articles", article).await? {
+      return Ok(Some(script));
+    }
+    match self.version_entry::<([u8; 32], u32)>("ScriptHashes", article).await? {
+      Some((hash, _)) => self.indexed_script(hash).await,
+      None => Ok(None),
+    }
+  }
+
+  /// Returns the script of an article version, fetching it by its hash if it's not in state.
+  pub async fn article(&self, article: Article) -> Result<Option<Script>, WikiblocksError> {
+    let Some(article) = self.resolve_article(&article).await? else { return Ok(None) };
+    self.script(&article).await
+  }
+
+  pub async fn author(&self, article: Article) -> Result<Option<Public>, WikiblocksError> {
+    let Some(article) = self.resolve_article(&article).await? else { return Ok(None) };
+    self.version_entry("Authors", &article).await
+  }
+
+  /// Returns the record of how an article version came to be, if it was added since versions
+  /// were recorded.
+  pub async fn version_info(
+    &self,
+    article: Article,
+  ) -> Result<Option<VersionInfo>, WikiblocksError> {
+    let Some(article) = self.resolve_article(&article).await? else { return Ok(None) };
+    self.version_entry("VersionInfos", &article).await
+  }
+
+  /// Returns the full-text checkpoint of an article version, if one was stored for it.
+  pub async fn checkpoint(&self, article: Article) -> Result<Option<Script>, WikiblocksError> {
+    let Some(article) = self.resolve_article(&article).await? else { return Ok(None) };
+    self.version_entry("Checkpoints", &article).await
+  }
+
+  /// Returns the amount of scripts replayed to render an article version, not counting its own.
+  ///
+  /// Versions referencing a version `MAX_RENDER_DEPTH` deep have to be added as full text.
+  pub async fn render_depth(&self, article: Article) -> Result<u32, WikiblocksError> {
+    let Some(article) = self.resolve_article(&article).await? else { return Ok(0) };
+    Ok(self.version_entry("RenderDepths", &article).await?.unwrap_or(0))
+  }
+
+  /// Renders the body of an article version, fetching every version it's built on top of back to
+  /// the closest checkpoints.
+  pub async fn body(&self, article: Article) -> Result<Option<Vec<u8>>, WikiblocksError> {
+    let Some(article) = self.resolve_article(&article).await? else { return Ok(None) };
+    let mut scripts = HashMap::new();
+    let mut pending = vec![article.version()];
+    while let Some(version) = pending.pop() {
+      if scripts.contains_key(&version.0) {
+        continue;
+      }
+
+      // missing references are reported by the interpreter itself
+      let current = Article::new(article.title().clone(), version);
+      let script = match self.version_entry("Checkpoints", &current).await? {
+        Some(checkpoint) => Some(checkpoint),
+        None => self.script(&current).await?,
+      };
+      let Some(script) = script else {
+        if version == article.version() {
+          return Ok(None);
+        }
+        continue;
+      };
+      pending.extend(script.references().filter(|reference| reference.0 < version.0));
+      scripts.insert(version.0, script);
+    }
+
+    render(article.version(), |version| scripts.get(&version.0).cloned())
+      .map(Some)
+      .map_err(WikiblocksError::InvalidScript)
+  }
 }
@@ -46,6 +46,26 @@ impl<'a> WikiblocksCoins<'a> {
     )
   }
 
+  /// Returns the coins of an account held for `reason`, which it can't spend until they're
+  /// released.
+  pub async fn held(
+    &self,
+    address: WikiblocksAddress,
+    reason: [u8; 8],
+  ) -> Result<SubstrateAmount, WikiblocksError> {
+    Ok(
+      self
+        .0
+        .storage(
+          PALLET,
+          "Holds",
+          (sp_core::hashing::blake2_128(&address.encode()), &address.0, reason),
+        )
+        .await?
+        .unwrap_or(0),
+    )
+  }
+
   pub fn transfer(to: WikiblocksAddress, amount: SubstrateAmount) -> wikiblocks_abi::Call {
     wikiblocks_abi::Call::Coins(wikiblocks_abi::coins::Call::transfer { to, amount })
   }
@@ -0,0 +1,82 @@
+use scale::Encode;
+
+use wikiblocks_abi::primitives::{ArticleVersion, Comment, CommentBody, CommentId, Title};
+
+use crate::{TemporalWikiblocks, WikiblocksError};
+
+const PALLET: &str = "Discussions";
+
+pub type DiscussionsEvent = wikiblocks_abi::discussions::Event;
+
+#[derive(Clone, Copy)]
+pub struct WikiblocksDiscussions<'a>(pub(crate) &'a TemporalWikiblocks<'a>);
+impl<'a> WikiblocksDiscussions<'a> {
+  /// Posts a comment on the talk page of an article, optionally about a specific version of it or
+  /// in reply to another comment.
+  pub fn post_comment(
+    title: Title,
+    version: Option<ArticleVersion>,
+    parent: Option<CommentId>,
+    body: CommentBody,
+  ) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Discussions(wikiblocks_abi::discussions::Call::post_comment {
+      title,
+      version,
+      parent,
+      body,
+    })
+  }
+
+  pub async fn comment_posted_events(&self) -> Result<Vec<DiscussionsEvent>, WikiblocksError> {
+    self
+      .0
+      .events(|event| {
+        if let wikiblocks_abi::Event::Discussions(event) = event {
+          if matches!(event, DiscussionsEvent::CommentPosted { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
+      .await
+  }
+
+  /// Returns the position of the article a title leads to, which its talk page is keyed by.
+  ///
+  /// Talk pages move along with their articles, so this follows redirects.
+  pub async fn talk_page(&self, title: &Title) -> Result<Option<u32>, WikiblocksError> {
+    self.0.articles().title_index(title).await
+  }
+
+  /// Returns the amount of comments on the talk page of an article.
+  pub async fn comment_count(&self, title: &Title) -> Result<CommentId, WikiblocksError> {
+    let Some(page) = self.talk_page(title).await? else { return Ok(0) };
+    Ok(self.0.storage(PALLET, "CommentCount", page).await?.unwrap_or(0))
+  }
+
+  pub async fn comment(
+    &self,
+    title: &Title,
+    id: CommentId,
+  ) -> Result<Option<Comment>, WikiblocksError> {
+    let Some(page) = self.talk_page(title).await? else { return Ok(None) };
+    self.0.storage(PALLET, "Comments", (page, sp_core::hashing::twox_64(&id.encode()), id)).await
+  }
+
+  /// Returns up to `limit` comments on the talk page of an article, in the order they were posted
+  /// in, starting with the comment `start`.
+  ///
+  /// Replies are always posted after the comment they reply to, so threads can be built in a
+  /// single pass over the talk page. The node returns at most `MAX_PAGE_LEN` comments at once.
+  pub async fn comments(
+    &self,
+    title: &Title,
+    start: CommentId,
+    limit: u32,
+  ) -> Result<Vec<(CommentId, Comment)>, WikiblocksError> {
+    self.0.runtime_api("WikiblocksRuntimeApi_comments", (title, start, limit)).await
+  }
+}
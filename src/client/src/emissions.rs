@@ -0,0 +1,66 @@
+use scale::Encode;
+
+use wikiblocks_abi::primitives::{SubstrateAmount, WikiblocksAddress};
+
+use crate::{TemporalWikiblocks, WikiblocksError};
+
+const PALLET: &str = "Emissions";
+
+pub type EmissionsEvent = wikiblocks_abi::emissions::Event;
+
+#[derive(Clone, Copy)]
+pub struct WikiblocksEmissions<'a>(pub(crate) &'a TemporalWikiblocks<'a>);
+impl<'a> WikiblocksEmissions<'a> {
+  /// Mints the rewards the signer earned as an author to it.
+  pub fn claim_rewards() -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Emissions(wikiblocks_abi::emissions::Call::claim_rewards)
+  }
+
+  pub async fn author_rewarded_events(&self) -> Result<Vec<EmissionsEvent>, WikiblocksError> {
+    self
+      .0
+      .events(|event| {
+        if let wikiblocks_abi::Event::Emissions(event) = event {
+          if matches!(event, EmissionsEvent::AuthorRewarded { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
+      .await
+  }
+
+  pub async fn rewards_claimed_events(&self) -> Result<Vec<EmissionsEvent>, WikiblocksError> {
+    self
+      .0
+      .events(|event| {
+        if let wikiblocks_abi::Event::Emissions(event) = event {
+          if matches!(event, EmissionsEvent::RewardsClaimed { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
+      .await
+  }
+
+  /// Returns the rewards an author earned and didn't claim yet.
+  pub async fn rewards(
+    &self,
+    address: WikiblocksAddress,
+  ) -> Result<SubstrateAmount, WikiblocksError> {
+    Ok(
+      self
+        .0
+        .storage(PALLET, "Rewards", (sp_core::hashing::blake2_128(&address.encode()), &address.0))
+        .await?
+        .unwrap_or(0),
+    )
+  }
+}
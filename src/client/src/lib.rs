@@ -16,16 +16,20 @@ pub use abi::{primitives, Transaction};
 use abi::*;
 
 pub use primitives::{WikiblocksAddress, Signature};
-use primitives::Header;
+use primitives::{ArticleVersion, Header, ScriptError, Title};
 
 pub mod coins;
 pub use coins::WikiblocksCoins;
 pub mod validator_sets;
 pub use validator_sets::WikiblocksValidatorSets;
+pub mod emissions;
+pub use emissions::WikiblocksEmissions;
 pub mod articles;
 pub use articles::WikiblocksArticles;
 pub mod votes;
 pub use votes::WikiblocksVotes;
+pub mod discussions;
+pub use discussions::WikiblocksDiscussions;
 
 #[derive(Clone, PartialEq, Eq, Debug, scale::Encode, scale::Decode)]
 pub struct Block {
@@ -61,6 +65,12 @@ pub enum WikiblocksError {
   ErrorInResponse(String),
   #[error("wikiblocks-client library was intended for a different runtime version: {0}")]
   InvalidRuntime(String),
+  #[error("article couldn't be rendered: {0:?}")]
+  InvalidScript(ScriptError),
+  #[error("invalid title: {0}")]
+  InvalidTitle(&'static str),
+  #[error("edit conflict: expected version {expected:?} to be the last, yet it's {last:?}")]
+  EditConflict { expected: ArticleVersion, last: ArticleVersion },
 }
 
 #[derive(Clone)]
@@ -316,6 +326,36 @@ impl Wikiblocks {
   pub async fn p2p_validators(&self) -> Result<Vec<multiaddr::Multiaddr>, WikiblocksError> {
     self.call("p2p_validators", ()).await
   }
+
+  fn titles(titles: Vec<String>) -> Result<Vec<Title>, WikiblocksError> {
+    titles
+      .into_iter()
+      .map(|title| {
+        Title::new(title.into_bytes())
+          .map_err(|_| WikiblocksError::InvalidNode("returned an invalid title".to_string()))
+      })
+      .collect()
+  }
+
+  /// Returns up to `limit` canonical titles of articles and redirects starting with `prefix`,
+  /// ignoring case, in lexicographic order.
+  ///
+  /// Searches are served from the node's own index of the latest finalized block.
+  pub async fn search_titles(
+    &self,
+    prefix: &str,
+    limit: u32,
+  ) -> Result<Vec<Title>, WikiblocksError> {
+    Self::titles(self.call("articles_searchTitles", (prefix, limit)).await?)
+  }
+
+  /// Returns up to `limit` canonical titles of the articles containing every word of `query`,
+  /// ranked by how many times they contain them.
+  ///
+  /// Searches are served from the node's own index of the latest finalized block.
+  pub async fn search(&self, query: &str, limit: u32) -> Result<Vec<Title>, WikiblocksError> {
+    Self::titles(self.call("articles_search", (query, limit)).await?)
+  }
 }
 
 impl<'a> TemporalWikiblocks<'a> {
@@ -398,6 +438,10 @@ impl<'a> TemporalWikiblocks<'a> {
     WikiblocksValidatorSets(self)
   }
 
+  pub fn emissions(&'a self) -> WikiblocksEmissions<'a> {
+    WikiblocksEmissions(self)
+  }
+
   pub fn articles(&'a self) -> WikiblocksArticles<'a> {
     WikiblocksArticles(self)
   }
@@ -405,4 +449,8 @@ impl<'a> TemporalWikiblocks<'a> {
   pub fn votes(&'a self) -> WikiblocksVotes<'a> {
     WikiblocksVotes(self)
   }
+
+  pub fn discussions(&'a self) -> WikiblocksDiscussions<'a> {
+    WikiblocksDiscussions(self)
+  }
 }
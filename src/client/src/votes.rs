@@ -1,23 +1,136 @@
 use scale::Encode;
 
-use wikiblocks_abi::primitives::Article;
+use sp_core::sr25519::Public;
+
+use wikiblocks_abi::primitives::{Article, Conviction, SubstrateAmount, Vote, MAX_PAGE_LEN};
 pub use wikiblocks_abi::coins::primitives;
 
 use crate::{TemporalWikiblocks, WikiblocksError};
 
 const PALLET: &str = "Votes";
 
+pub type VotesEvent = wikiblocks_abi::votes::Event;
+
 #[derive(Clone, Copy)]
 pub struct WikiblocksVotes<'a>(pub(crate) &'a TemporalWikiblocks<'a>);
 impl<'a> WikiblocksVotes<'a> {
+  /// Upvotes an article version, replacing the downvote of the signer if it had one.
   pub fn upvote(article: Article) -> wikiblocks_abi::Call {
     wikiblocks_abi::Call::Votes(wikiblocks_abi::votes::Call::upvote { article })
   }
 
+  /// Downvotes an article version, replacing the upvote of the signer if it had one.
+  pub fn downvote(article: Article) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Votes(wikiblocks_abi::votes::Call::downvote { article })
+  }
+
+  /// Votes on an article version with the weight of `amount` coins, which are held by the chain
+  /// for the lock period of the conviction and released once it ends.
+  pub fn vote_with_stake(
+    article: Article,
+    vote: Vote,
+    amount: SubstrateAmount,
+    conviction: Conviction,
+  ) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Votes(wikiblocks_abi::votes::Call::vote_with_stake {
+      article,
+      vote,
+      amount,
+      conviction,
+    })
+  }
+
+  pub fn retract_vote(article: Article) -> wikiblocks_abi::Call {
+    wikiblocks_abi::Call::Votes(wikiblocks_abi::votes::Call::retract_vote { article })
+  }
+
+  pub async fn upvoted_events(&self) -> Result<Vec<VotesEvent>, WikiblocksError> {
+    self
+      .0
+      .events(|event| {
+        if let wikiblocks_abi::Event::Votes(event) = event {
+          if matches!(event, VotesEvent::Upvoted { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
+      .await
+  }
+
+  pub async fn downvoted_events(&self) -> Result<Vec<VotesEvent>, WikiblocksError> {
+    self
+      .0
+      .events(|event| {
+        if let wikiblocks_abi::Event::Votes(event) = event {
+          if matches!(event, VotesEvent::Downvoted { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
+      .await
+  }
+
+  pub async fn vote_retracted_events(&self) -> Result<Vec<VotesEvent>, WikiblocksError> {
+    self
+      .0
+      .events(|event| {
+        if let wikiblocks_abi::Event::Votes(event) = event {
+          if matches!(event, VotesEvent::VoteRetracted { .. }) {
+            Some(event.clone())
+          } else {
+            None
+          }
+        } else {
+          None
+        }
+      })
+      .await
+  }
+
+  /// Returns the article versions an account voted on, in storage order, starting after the
+  /// version `after`.
+  ///
+  /// Versions are listed under the current title of their article. The node returns at most
+  /// `MAX_PAGE_LEN` versions at once, so the next page starts after the last version returned.
+  pub async fn votes(
+    &self,
+    account: Public,
+    after: Option<Article>,
+  ) -> Result<Vec<Article>, WikiblocksError> {
+    self.0.runtime_api("WikiblocksRuntimeApi_votes", (account, after, MAX_PAGE_LEN)).await
+  }
+
   pub async fn upvotes(&self, article: &Article) -> Result<Option<u64>, WikiblocksError> {
     self
       .0
       .storage(PALLET, "Upvotes", (sp_core::hashing::blake2_128(&article.encode()), article))
       .await
   }
+
+  pub async fn downvotes(&self, article: &Article) -> Result<Option<u64>, WikiblocksError> {
+    self
+      .0
+      .storage(PALLET, "Downvotes", (sp_core::hashing::blake2_128(&article.encode()), article))
+      .await
+  }
+
+  /// Returns the weight of the upvotes of an article version minus the weight of its downvotes.
+  /// Votes without coins weigh 1.
+  pub async fn score(&self, article: &Article) -> Result<i64, WikiblocksError> {
+    Ok(
+      self
+        .0
+        .storage(PALLET, "Scores", (sp_core::hashing::blake2_128(&article.encode()), article))
+        .await?
+        .unwrap_or(0),
+    )
+  }
 }
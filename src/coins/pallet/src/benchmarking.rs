@@ -0,0 +1,38 @@
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+use sp_core::sr25519::Public;
+
+#[benchmarks]
+mod benchmarks {
+  use super::*;
+
+  #[benchmark]
+  fn transfer() {
+    let caller: Public = whitelisted_caller();
+    let to: Public = account("to", 0, 0);
+    Pallet::<T>::mint(caller, 100).unwrap();
+    // writing to an existing balance is as expensive as creating one
+    Pallet::<T>::mint(to, 1).unwrap();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), to, 50);
+
+    assert_eq!(Pallet::<T>::balances(to), Some(51));
+  }
+
+  #[benchmark]
+  fn burn() {
+    let caller: Public = whitelisted_caller();
+    Pallet::<T>::mint(caller, 100).unwrap();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), 50);
+
+    assert_eq!(Pallet::<T>::balances(caller), Some(50));
+  }
+
+  impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -1,11 +1,23 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
 use wikiblocks_primitives::SubstrateAmount;
 
 pub trait CallToFee<T: frame_system::Config> {
   fn call_to_fee(call: &T::RuntimeCall) -> SubstrateAmount;
 }
 
+/// What coins are held for, so every pallet holding coins only releases its own.
+pub type HoldReason = [u8; 8];
+
 // TODO: Investigate why Substrate generates this
 #[allow(unreachable_patterns, clippy::cast_possible_truncation)]
 #[frame_support::pallet]
@@ -30,6 +42,7 @@ pub mod pallet {
   pub trait Config: frame_system::Config<AccountId = Public> {
     type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
     type CallToFee: CallToFee<Self>;
+    type WeightInfo: WeightInfo;
   }
 
   #[pallet::genesis_config]
@@ -50,6 +63,7 @@ pub mod pallet {
     AmountOverflowed,
     NotEnoughCoins,
     MintNotAllowed,
+    NotEnoughHeld,
   }
 
   #[pallet::event]
@@ -58,6 +72,8 @@ pub mod pallet {
     Mint { to: Public, amount: SubstrateAmount },
     Burn { from: Public, amount: SubstrateAmount },
     Transfer { from: Public, to: Public, amount: SubstrateAmount },
+    Held { account: Public, reason: HoldReason, amount: SubstrateAmount },
+    Released { account: Public, reason: HoldReason, amount: SubstrateAmount },
   }
 
   #[pallet::pallet]
@@ -77,6 +93,22 @@ pub mod pallet {
   #[pallet::getter(fn supply)]
   pub type Supply<T: Config> = StorageValue<_, SubstrateAmount, ValueQuery>;
 
+  /// The coins of every account which are held, and can't be spent until they're released, keyed
+  /// by the account and what they're held for.
+  ///
+  /// Held coins are no longer part of the balance, yet still part of the supply.
+  #[pallet::storage]
+  #[pallet::getter(fn held)]
+  pub type Holds<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    Public,
+    Identity,
+    HoldReason,
+    SubstrateAmount,
+    OptionQuery,
+  >;
+
   #[pallet::genesis_build]
   impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
     fn build(&self) {
@@ -159,12 +191,50 @@ pub mod pallet {
       Self::deposit_event(Event::Transfer { from, to, amount });
       Ok(())
     }
+
+    /// Holds `amount` of the balance of an account for `reason`, until it's released.
+    pub fn hold(
+      account: Public,
+      reason: HoldReason,
+      amount: SubstrateAmount,
+    ) -> Result<(), Error<T>> {
+      let held = Self::held(account, reason)
+        .unwrap_or(0)
+        .checked_add(amount)
+        .ok_or(Error::<T>::AmountOverflowed)?;
+      Self::decrease_balance_internal(account, amount)?;
+      Holds::<T>::set(account, reason, Some(held));
+
+      Self::deposit_event(Event::Held { account, reason, amount });
+      Ok(())
+    }
+
+    /// Releases `amount` of the coins of an account held for `reason` back to its balance.
+    pub fn release(
+      account: Public,
+      reason: HoldReason,
+      amount: SubstrateAmount,
+    ) -> Result<(), Error<T>> {
+      let held = Self::held(account, reason)
+        .unwrap_or(0)
+        .checked_sub(amount)
+        .ok_or(Error::<T>::NotEnoughHeld)?;
+      Self::increase_balance_internal(account, amount)?;
+      if held == 0 {
+        Holds::<T>::remove(account, reason);
+      } else {
+        Holds::<T>::set(account, reason, Some(held));
+      }
+
+      Self::deposit_event(Event::Released { account, reason, amount });
+      Ok(())
+    }
   }
 
   #[pallet::call]
   impl<T: Config> Pallet<T> {
     #[pallet::call_index(0)]
-    #[pallet::weight((0, DispatchClass::Normal))] // TODO
+    #[pallet::weight(T::WeightInfo::transfer())]
     pub fn transfer(origin: OriginFor<T>, to: Public, amount: SubstrateAmount) -> DispatchResult {
       let from = ensure_signed(origin)?;
       Self::transfer_internal(from, to, amount)?;
@@ -173,7 +243,7 @@ pub mod pallet {
 
     /// Burn `balance` from the caller.
     #[pallet::call_index(1)]
-    #[pallet::weight((0, DispatchClass::Normal))] // TODO
+    #[pallet::weight(T::WeightInfo::burn())]
     pub fn burn(origin: OriginFor<T>, amount: SubstrateAmount) -> DispatchResult {
       let from = ensure_signed(origin)?;
       Self::burn_internal(from, amount)?;
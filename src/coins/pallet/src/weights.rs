@@ -0,0 +1,56 @@
+//! Weights for the Coins pallet.
+//!
+//! Generated by running the benchmarks in `benchmarking.rs` natively in the mock runtime of the
+//! pallet, over 10 steps of every component repeated 5 times, with a state backend counting the
+//! storage accesses as the benchmarking state of the node does. The execution times and proof sizes
+//! are of native code over the in-memory trie of the mock, not of the wasm runtime on the reference
+//! hardware, so they are to be replaced by running `wikiblocks-node benchmark pallet --chain dev
+//! --pallet coins --extrinsic '*' --steps 50 --repeat 20 --output src/coins/pallet/src/weights.rs`
+//! on it.
+
+use core::marker::PhantomData;
+
+use frame_support::{
+  traits::Get,
+  weights::{constants::RocksDbWeight, Weight},
+};
+
+pub trait WeightInfo {
+  fn transfer() -> Weight;
+  fn burn() -> Weight;
+}
+
+/// Weights for the Coins pallet, using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+  // Reads: Balances (twice)
+  // Writes: Balances (twice)
+  fn transfer() -> Weight {
+    Weight::from_parts(23_565_000, 287)
+      .saturating_add(T::DbWeight::get().reads(2))
+      .saturating_add(T::DbWeight::get().writes(2))
+  }
+
+  // Reads: Balances, Supply
+  // Writes: Balances, Supply
+  fn burn() -> Weight {
+    Weight::from_parts(17_852_000, 157)
+      .saturating_add(T::DbWeight::get().reads(2))
+      .saturating_add(T::DbWeight::get().writes(2))
+  }
+}
+
+// For tests, and backwards compatibility
+impl WeightInfo for () {
+  fn transfer() -> Weight {
+    Weight::from_parts(23_565_000, 287)
+      .saturating_add(RocksDbWeight::get().reads(2))
+      .saturating_add(RocksDbWeight::get().writes(2))
+  }
+
+  fn burn() -> Weight {
+    Weight::from_parts(17_852_000, 157)
+      .saturating_add(RocksDbWeight::get().reads(2))
+      .saturating_add(RocksDbWeight::get().writes(2))
+  }
+}
@@ -0,0 +1,54 @@
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+use sp_core::sr25519::Public;
+use sp_std::vec;
+
+use articles_pallet::{Pallet as Articles, Redirects};
+use wikiblocks_primitives::{
+  ArticleVersion, Body, CommentBody, OpCode, Script, Title, MAX_COMMENT_LEN, MAX_REDIRECT_DEPTH,
+  MAX_TITLE_LEN,
+};
+
+#[benchmarks]
+mod benchmarks {
+  use super::*;
+
+  // The comment is a reply about a specific version, which are checked for, posted under a title
+  // the longest chain of redirects away from its article.
+  #[benchmark]
+  fn post_comment(l: Linear<1, { MAX_COMMENT_LEN }>) {
+    let caller: Public = whitelisted_caller();
+
+    // the title is canonicalized to be looked up, which takes time linear to its length
+    let title = |c: u8| Title::new(vec![c; usize::try_from(MAX_TITLE_LEN).unwrap()]).unwrap();
+    let script = Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap();
+    Articles::<T>::add_article(RawOrigin::Signed(caller).into(), title(b'a'), None, script)
+      .unwrap();
+    for i in 1 ..= u8::try_from(MAX_REDIRECT_DEPTH).unwrap() {
+      Redirects::<T>::set(title(b'a' + i), Some(title(b'a' + i - 1)));
+    }
+    let title = title(b'a' + u8::try_from(MAX_REDIRECT_DEPTH).unwrap());
+
+    let comment = || CommentBody::new(b"comment".to_vec()).unwrap();
+    Pallet::<T>::post_comment(
+      RawOrigin::Signed(caller).into(),
+      title.clone(),
+      None,
+      None,
+      comment(),
+    )
+    .unwrap();
+
+    let body = CommentBody::new(vec![b'a'; usize::try_from(l).unwrap()]).unwrap();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), title.clone(), Some(ArticleVersion(0)), Some(0), body);
+
+    assert_eq!(Pallet::<T>::comment_count(0), 2);
+  }
+
+  impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,135 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[allow(clippy::cast_possible_truncation)]
+#[frame_support::pallet]
+pub mod pallet {
+  use frame_system::pallet_prelude::*;
+  use frame_support::pallet_prelude::*;
+
+  use sp_core::sr25519::Public;
+  use sp_std::vec::Vec;
+
+  use articles_pallet::{Config as ArticlesConfig, Pallet as Articles};
+  use wikiblocks_primitives::{ArticleVersion, Comment, CommentBody, CommentId, Title, MAX_PAGE_LEN};
+
+  use super::WeightInfo;
+
+  #[pallet::config]
+  pub trait Config: frame_system::Config<AccountId = Public> + ArticlesConfig {
+    type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+    type WeightInfo: WeightInfo;
+  }
+
+  #[pallet::error]
+  pub enum Error<T> {
+    InvalidTitle,
+    InvalidVersion,
+    InvalidParent,
+    EmptyComment,
+    InvalidUtf8,
+    TooManyComments,
+  }
+
+  #[pallet::event]
+  #[pallet::generate_deposit(fn deposit_event)]
+  pub enum Event<T: Config> {
+    CommentPosted { title: Title, id: CommentId, author: Public },
+  }
+
+  #[pallet::pallet]
+  pub struct Pallet<T>(_);
+
+  /// The amount of comments on the talk page of every article, keyed by the position of the
+  /// article in `Articles::Titles`.
+  #[pallet::storage]
+  #[pallet::getter(fn comment_count)]
+  pub type CommentCount<T: Config> = StorageMap<_, Identity, u32, CommentId, ValueQuery>;
+
+  /// The comments on the talk page of every article, keyed by the position of the article in
+  /// `Articles::Titles` and their position on the talk page.
+  ///
+  /// Articles keep their position when they're moved, so their talk pages move along with them.
+  #[pallet::storage]
+  #[pallet::getter(fn comment)]
+  pub type Comments<T: Config> =
+    StorageDoubleMap<_, Identity, u32, Twox64Concat, CommentId, Comment, OptionQuery>;
+
+  impl<T: Config> Pallet<T> {
+    /// Returns the position of the article a title leads to, which its talk page is keyed by.
+    pub fn talk_page(title: &Title) -> Option<u32> {
+      Articles::<T>::title_index(Articles::<T>::resolve(title)?)
+    }
+
+    /// Returns up to `limit` comments on the talk page of an article, in the order they were
+    /// posted in, starting with the comment `start`.
+    ///
+    /// At most `MAX_PAGE_LEN` comments are returned.
+    pub fn comments(title: &Title, start: CommentId, limit: u32) -> Vec<(CommentId, Comment)> {
+      let Some(page) = Self::talk_page(title) else { return Vec::new() };
+      let end = start.saturating_add(limit.min(MAX_PAGE_LEN).into()).min(Self::comment_count(page));
+      (start .. end).filter_map(|id| Some((id, Self::comment(page, id)?))).collect()
+    }
+  }
+
+  #[pallet::call]
+  impl<T: Config> Pallet<T> {
+    /// Posts a comment on the talk page of an article, optionally about a specific version of it
+    /// or in reply to another comment.
+    ///
+    /// The title may be a redirect, such as the title the article had before it was moved.
+    #[pallet::call_index(0)]
+    #[pallet::weight(
+      <T as Config>::WeightInfo::post_comment(u32::try_from(body.data().len()).unwrap())
+    )]
+    pub fn post_comment(
+      origin: OriginFor<T>,
+      title: Title,
+      version: Option<ArticleVersion>,
+      parent: Option<CommentId>,
+      body: CommentBody,
+    ) -> DispatchResult {
+      let author = ensure_signed(origin)?;
+
+      // make sure the article exists
+      let title = Articles::<T>::resolve(&title).ok_or(Error::<T>::InvalidTitle)?;
+      let page = Articles::<T>::title_index(&title).ok_or(Error::<T>::InvalidTitle)?;
+      let last_version = Articles::<T>::last_version(&title).ok_or(Error::<T>::InvalidTitle)?;
+      if version.is_some_and(|version| version.0 > last_version.0) {
+        Err(Error::<T>::InvalidVersion)?;
+      }
+
+      // replies are to comments already on the talk page
+      let id = Self::comment_count(page);
+      if parent.is_some_and(|parent| parent >= id) {
+        Err(Error::<T>::InvalidParent)?;
+      }
+
+      if body.data().is_empty() {
+        Err(Error::<T>::EmptyComment)?;
+      }
+      if core::str::from_utf8(body.data()).is_err() {
+        Err(Error::<T>::InvalidUtf8)?;
+      }
+
+      CommentCount::<T>::set(page, id.checked_add(1).ok_or(Error::<T>::TooManyComments)?);
+      Comments::<T>::set(page, id, Some(Comment { author: author.into(), version, parent, body }));
+
+      Self::deposit_event(Event::CommentPosted { title, id, author });
+      Ok(())
+    }
+  }
+}
+
+pub use pallet::*;
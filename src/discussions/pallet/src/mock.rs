@@ -0,0 +1,86 @@
+//! Test environment for Discussions pallet.
+
+use super::*;
+
+use frame_support::{
+  construct_runtime,
+  traits::{ConstU32, ConstU64, UnixTime},
+};
+
+use sp_core::{H256, sr25519::Public};
+use sp_runtime::{
+  traits::{BlakeTwo256, IdentityLookup},
+  BuildStorage,
+};
+
+use crate as discussions;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+  pub enum Test
+  {
+    System: frame_system,
+    Articles: articles_pallet,
+    Discussions: discussions,
+  }
+);
+
+// Blocks are six seconds apart, starting from the Unix epoch.
+pub struct MockTime;
+impl UnixTime for MockTime {
+  fn now() -> core::time::Duration {
+    core::time::Duration::from_secs(System::block_number() * 6)
+  }
+}
+
+impl frame_system::Config for Test {
+  type BaseCallFilter = frame_support::traits::Everything;
+  type BlockWeights = ();
+  type BlockLength = ();
+  type RuntimeOrigin = RuntimeOrigin;
+  type RuntimeCall = RuntimeCall;
+  type Nonce = u64;
+  type Hash = H256;
+  type Hashing = BlakeTwo256;
+  type AccountId = Public;
+  type Lookup = IdentityLookup<Self::AccountId>;
+  type Block = Block;
+  type RuntimeEvent = RuntimeEvent;
+  type BlockHashCount = ConstU64<250>;
+  type DbWeight = ();
+  type Version = ();
+  type PalletInfo = PalletInfo;
+  type AccountData = ();
+  type OnNewAccount = ();
+  type OnKilledAccount = ();
+  type SystemWeightInfo = ();
+  type SS58Prefix = ();
+  type OnSetCode = ();
+  type MaxConsumers = ConstU32<16>;
+}
+
+impl articles_pallet::Config for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = ();
+  type OnArticleMoved = ();
+  type OnVersionAdded = ();
+  type Time = MockTime;
+  type CurrentSession = ConstU32<0>;
+  type Reviewers = frame_support::traits::Everything;
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = frame_support::traits::Everything;
+}
+
+impl Config for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+  let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+
+  let mut ext = sp_io::TestExternalities::new(t);
+  ext.execute_with(|| System::set_block_number(1));
+  ext
+}
@@ -0,0 +1,138 @@
+use crate::{mock::*, pallet};
+
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+
+use sp_core::Pair;
+use wikiblocks_primitives::{
+  insecure_pair_from_name, ArticleVersion, Body, Comment, CommentBody, OpCode, Script, Title,
+};
+
+#[test]
+fn post_comments() {
+  new_test_ext().execute_with(|| {
+    let user = insecure_pair_from_name("user").public();
+    let title = |title: &str| Title::new(title.as_bytes().to_vec()).unwrap();
+    let body = |body: &str| CommentBody::new(body.as_bytes().to_vec()).unwrap();
+
+    let script = Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap();
+    assert_ok!(Articles::add_article(RawOrigin::Signed(user).into(), title("Paris"), None, script));
+
+    // comments are only posted about articles, and versions of them, which exist
+    assert_noop!(
+      Discussions::post_comment(
+        RawOrigin::Signed(user).into(),
+        title("Rome"),
+        None,
+        None,
+        body("hello")
+      ),
+      pallet::Error::<Test>::InvalidTitle
+    );
+    assert_noop!(
+      Discussions::post_comment(
+        RawOrigin::Signed(user).into(),
+        title("Paris"),
+        Some(ArticleVersion(1)),
+        None,
+        body("hello")
+      ),
+      pallet::Error::<Test>::InvalidVersion
+    );
+    assert_noop!(
+      Discussions::post_comment(
+        RawOrigin::Signed(user).into(),
+        title("Paris"),
+        None,
+        Some(0),
+        body("hello")
+      ),
+      pallet::Error::<Test>::InvalidParent
+    );
+    assert_noop!(
+      Discussions::post_comment(
+        RawOrigin::Signed(user).into(),
+        title("Paris"),
+        None,
+        None,
+        body("")
+      ),
+      pallet::Error::<Test>::EmptyComment
+    );
+    assert_noop!(
+      Discussions::post_comment(
+        RawOrigin::Signed(user).into(),
+        title("Paris"),
+        None,
+        None,
+        CommentBody::new(vec![0xff]).unwrap()
+      ),
+      pallet::Error::<Test>::InvalidUtf8
+    );
+
+    // start a thread and reply to it, under any form of the title
+    assert_ok!(Discussions::post_comment(
+      RawOrigin::Signed(user).into(),
+      title("Paris"),
+      Some(ArticleVersion(0)),
+      None,
+      body("is this accurate?")
+    ));
+    let replier = insecure_pair_from_name("replier").public();
+    assert_ok!(Discussions::post_comment(
+      RawOrigin::Signed(replier).into(),
+      title(" paris"),
+      None,
+      Some(0),
+      body("yes")
+    ));
+    System::assert_last_event(RuntimeEvent::Discussions(pallet::Event::CommentPosted {
+      title: title("paris"),
+      id: 1,
+      author: replier,
+    }));
+
+    assert_eq!(Discussions::talk_page(&title("Paris")), Some(0));
+    assert_eq!(Discussions::comment_count(0), 2);
+    let comments = Discussions::comments(&title("Paris"), 0, 10);
+    assert_eq!(
+      comments,
+      vec![
+        (
+          0,
+          Comment {
+            author: user.into(),
+            version: Some(ArticleVersion(0)),
+            parent: None,
+            body: body("is this accurate?"),
+          }
+        ),
+        (1, Comment { author: replier.into(), version: None, parent: Some(0), body: body("yes") }),
+      ]
+    );
+    assert_eq!(Discussions::comments(&title("Paris"), 1, 10), comments[1 ..]);
+    assert_eq!(Discussions::comments(&title("Paris"), 2, 10), vec![]);
+
+    // the talk page moves with its article, and can still be posted to under the old title
+    assert_ok!(Articles::move_article(
+      RawOrigin::Signed(user).into(),
+      title("Paris"),
+      title("Paris, France"),
+      1
+    ));
+    assert_eq!(Discussions::comments(&title("Paris, France"), 0, 10), comments);
+    assert_ok!(Discussions::post_comment(
+      RawOrigin::Signed(user).into(),
+      title("Paris"),
+      None,
+      Some(1),
+      body("thanks")
+    ));
+    System::assert_last_event(RuntimeEvent::Discussions(pallet::Event::CommentPosted {
+      title: title("paris, France"),
+      id: 2,
+      author: user,
+    }));
+    assert_eq!(Discussions::comment_count(0), 3);
+  })
+}
@@ -0,0 +1,43 @@
+//! Weights for the Discussions pallet.
+//!
+//! Generated by running the benchmarks in `benchmarking.rs` natively in the mock runtime of the
+//! pallet, over 10 steps of every component repeated 5 times, with a state backend counting the
+//! storage accesses as the benchmarking state of the node does. The execution times and proof sizes
+//! are of native code over the in-memory trie of the mock, not of the wasm runtime on the reference
+//! hardware, so they are to be replaced by running `wikiblocks-node benchmark pallet --chain dev
+//! --pallet discussions --extrinsic '*' --steps 50 --repeat 20 --output
+//! src/discussions/pallet/src/weights.rs` on it.
+
+use core::marker::PhantomData;
+
+use frame_support::{
+  traits::Get,
+  weights::{constants::RocksDbWeight, Weight},
+};
+
+pub trait WeightInfo {
+  fn post_comment(l: u32) -> Weight;
+}
+
+/// Weights for the Discussions pallet, using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+  // Reads: Articles::LastVersion (9 keys), Articles::Redirects (8 keys), Articles::Titles,
+  // CommentCount
+  // Writes: CommentCount, Comments
+  // The range of component `l` is `[1, 16384]`.
+  fn post_comment(_l: u32) -> Weight {
+    Weight::from_parts(215_093_284, 19_584)
+      .saturating_add(T::DbWeight::get().reads(19))
+      .saturating_add(T::DbWeight::get().writes(2))
+  }
+}
+
+// For tests, and backwards compatibility
+impl WeightInfo for () {
+  fn post_comment(_l: u32) -> Weight {
+    Weight::from_parts(215_093_284, 19_584)
+      .saturating_add(RocksDbWeight::get().reads(19))
+      .saturating_add(RocksDbWeight::get().writes(2))
+  }
+}
@@ -0,0 +1,71 @@
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+use coins_pallet::Pallet as Coins;
+use validator_sets_pallet::{Pallet as ValidatorSets, SessionBeginBlock};
+
+use validator_sets_primitives::{MAX_KEY_SHARES_PER_SET, Session};
+use wikiblocks_primitives::*;
+
+#[benchmarks]
+mod benchmarks {
+  use super::*;
+
+  #[benchmark]
+  fn claim_rewards() {
+    let caller: Public = whitelisted_caller();
+    Rewards::<T>::set(caller, 100);
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller));
+
+    assert_eq!(Pallet::<T>::rewards(caller), 0);
+  }
+
+  // The rewards of the validators are staked, so the worst case is a full set of validators
+  #[benchmark]
+  fn end_session() {
+    let amount = ValidatorSets::<T>::allocation_per_key_share();
+    let participants = (0 .. MAX_KEY_SHARES_PER_SET)
+      .map(|i| {
+        let validator: Public = account("validator", i, 0);
+        Coins::<T>::mint(validator, amount).unwrap();
+        ValidatorSets::<T>::allocate(RawOrigin::Signed(validator).into(), amount).unwrap();
+        (validator, amount)
+      })
+      .collect::<Vec<_>>();
+    Participants::<T>::set(Some(participants.try_into().unwrap()));
+
+    let ended = ValidatorSets::<T>::session().unwrap_or(Session(0));
+    let session = Session(ended.0 + 1);
+    SessionBeginBlock::<T>::set(session, SessionBeginBlock::<T>::get(ended) + FAST_EPOCH_DURATION);
+
+    #[block]
+    {
+      Pallet::<T>::end_session(session);
+    }
+
+    assert!(PendingAuthorRewards::<T>::contains_key(ended.0));
+  }
+
+  #[benchmark]
+  fn reward_authors(v: Linear<0, MAX_REWARDED_VERSIONS>) {
+    let title = Title::new(b"benchmark".to_vec()).unwrap();
+    let versions = (0 .. v)
+      .map(|i| (Article::new(title.clone(), ArticleVersion(i)), account("author", i, 0), 1))
+      .collect::<Vec<_>>();
+    let reward = u64::from(MAX_REWARDED_VERSIONS) * REWARD_PER_BLOCK;
+    PendingAuthorRewards::<T>::set(0, Some((reward, u64::from(v))));
+
+    #[block]
+    {
+      Pallet::<T>::reward_authors(0, reward, u64::from(v), versions);
+    }
+
+    assert_eq!(PendingAuthorRewards::<T>::contains_key(0), v != 0);
+  }
+
+  impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
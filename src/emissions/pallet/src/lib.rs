@@ -6,6 +6,44 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+use sp_std::vec::Vec;
+use sp_core::sr25519::Public;
+
+use frame_support::weights::Weight;
+
+use wikiblocks_primitives::Article;
+
+/// The versions voted on during every session, whose authors are rewarded from its emissions.
+pub trait VotedVersions {
+  /// Takes how many accounts upvoting them the versions voted on during a session gained within
+  /// it, summed. Accounts count the same whatever the weight of their votes.
+  fn take_total_gain(session: u32) -> u64;
+  /// Takes up to `limit` of the versions voted on during a session, with their authors and how
+  /// many accounts they gained within it.
+  fn take(session: u32, limit: u32) -> Vec<(Article, Public, u64)>;
+  /// The weight of taking the total gain of a session, or `versions` of the versions voted on
+  /// during it.
+  fn weight(versions: u32) -> Weight;
+}
+
+impl VotedVersions for () {
+  fn take_total_gain(_: u32) -> u64 {
+    0
+  }
+  fn take(_: u32, _: u32) -> Vec<(Article, Public, u64)> {
+    Vec::new()
+  }
+  fn weight(_: u32) -> Weight {
+    Weight::zero()
+  }
+}
+
 #[allow(
   unreachable_patterns,
   clippy::cast_possible_truncation,
@@ -20,6 +58,7 @@ pub mod pallet {
 
   use sp_std::{vec, vec::Vec};
   use sp_core::sr25519::Public;
+  use sp_runtime::Perbill;
 
   use coins_pallet::{Config as CoinsConfig, Pallet as Coins};
   use validator_sets_pallet::{Pallet as ValidatorSets, Config as ValidatorSetsConfig};
@@ -32,6 +71,12 @@ pub mod pallet {
     frame_system::Config<AccountId = PublicKey> + ValidatorSetsConfig + CoinsConfig
   {
     type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+    type WeightInfo: WeightInfo;
+    /// The share of the emissions of every session paid to the authors of the versions voted on
+    /// during it.
+    #[pallet::constant]
+    type AuthorRewardShare: Get<Perbill>;
+    type VotedVersions: VotedVersions;
   }
 
   #[pallet::genesis_config]
@@ -51,10 +96,15 @@ pub mod pallet {
     NetworkHasEconomicSecurity,
     NoValueForCoin,
     InsufficientAllocation,
+    NoRewards,
   }
 
   #[pallet::event]
-  pub enum Event<T: Config> {}
+  #[pallet::generate_deposit(fn deposit_event)]
+  pub enum Event<T: Config> {
+    AuthorRewarded { author: Public, article: Article, amount: SubstrateAmount },
+    RewardsClaimed { author: Public, amount: SubstrateAmount },
+  }
 
   #[pallet::pallet]
   pub struct Pallet<T>(PhantomData<T>);
@@ -70,6 +120,18 @@ pub mod pallet {
   #[pallet::getter(fn session)]
   pub type CurrentSession<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+  /// The rewards every author earned and didn't claim yet.
+  #[pallet::storage]
+  #[pallet::getter(fn rewards)]
+  pub type Rewards<T: Config> =
+    StorageMap<_, Blake2_128Concat, Public, SubstrateAmount, ValueQuery>;
+
+  /// The author rewards of the sessions which ended, with the accounts gained during them, paid out
+  /// a bounded amount of versions at a time.
+  #[pallet::storage]
+  pub(crate) type PendingAuthorRewards<T: Config> =
+    StorageMap<_, Identity, u32, (SubstrateAmount, u64), OptionQuery>;
+
   #[pallet::genesis_build]
   impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
     fn build(&self) {
@@ -81,39 +143,51 @@ pub mod pallet {
   #[pallet::hooks]
   impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
     fn on_initialize(_: BlockNumberFor<T>) -> Weight {
-      // check if we got a new session
-      let mut session_changed = false;
+      // Reads: ValidatorSets::CurrentSession, CurrentSession
+      let mut weight = T::DbWeight::get().reads(2);
+
+      // We only want to distribute emissions if session has ended
       let session = ValidatorSets::<T>::session().unwrap_or(Session(0));
       if session.0 > Self::session() {
-        session_changed = true;
         CurrentSession::<T>::set(session.0);
+        Self::end_session(session);
+        weight = weight
+          .saturating_add(<T as Config>::WeightInfo::end_session())
+          .saturating_add(T::VotedVersions::weight(0));
       }
 
-      // We only want to distribute emissions if session has ended
-      if !session_changed {
-        return Weight::zero(); // TODO
-      }
+      // the authors are paid over the blocks following the session
+      weight.saturating_add(Self::pay_authors())
+    }
+  }
 
+  impl<T: Config> Pallet<T> {
+    // Distribute the emissions of the session which ended before `session`.
+    pub(crate) fn end_session(session: Session) {
       // figure out the amount of blocks in the last session
       // Since the session has changed, we're now at least at session 1
+      let ended = Session(session.0 - 1);
       let block_count = ValidatorSets::<T>::session_begin_block(session) -
-        ValidatorSets::<T>::session_begin_block(Session(session.0 - 1));
+        ValidatorSets::<T>::session_begin_block(ended);
 
       // get total reward for this epoch
       let reward_this_epoch = block_count * REWARD_PER_BLOCK;
 
-      // distribute validators rewards
-      Self::distribute_to_validators(reward_this_epoch);
+      // the authors of the versions which gained accounts get their share, and the validators the
+      // rest
+      let total_gain = T::VotedVersions::take_total_gain(ended.0);
+      let author_reward =
+        if total_gain == 0 { 0 } else { T::AuthorRewardShare::get() * reward_this_epoch };
+      // the versions voted on are taken even if none gained accounts, so their records are cleared
+      PendingAuthorRewards::<T>::set(ended.0, Some((author_reward, total_gain)));
+      Self::distribute_to_validators(reward_this_epoch - author_reward);
 
       // TODO: we have the past session participants here in the emissions pallet so that we can
       // distribute rewards to them in the next session. Ideally we should be able to fetch this
       // information from validator sets pallet.
       Self::update_participants();
-      Weight::zero() // TODO
     }
-  }
 
-  impl<T: Config> Pallet<T> {
     // Distribute the reward among network's set based on
     // -> (key shares * stake per share) + ((stake % stake per share) / 2)
     fn distribute_to_validators(reward: u64) {
@@ -147,6 +221,52 @@ pub mod pallet {
       }
     }
 
+    // Pay up to `MAX_REWARDED_VERSIONS` of the authors of the versions voted on during a session
+    // whose rewards are pending, returning the weight consumed.
+    fn pay_authors() -> Weight {
+      // Reads: PendingAuthorRewards
+      let weight = T::DbWeight::get().reads(1);
+      let Some((session, (reward, total_gain))) = PendingAuthorRewards::<T>::iter().next() else {
+        return weight;
+      };
+
+      let versions = T::VotedVersions::take(session, MAX_REWARDED_VERSIONS);
+      let count = u32::try_from(versions.len()).unwrap();
+      Self::reward_authors(session, reward, total_gain, versions);
+      weight
+        .saturating_add(<T as Config>::WeightInfo::reward_authors(count))
+        .saturating_add(T::VotedVersions::weight(count))
+    }
+
+    // Credit the reward of a session to the authors of the versions voted on during it, pro rata
+    // by the accounts their versions gained, until none are left.
+    //
+    // The rewards are only minted once claimed.
+    pub(crate) fn reward_authors(
+      session: u32,
+      reward: SubstrateAmount,
+      total_gain: u64,
+      versions: Vec<(Article, Public, u64)>,
+    ) {
+      if versions.is_empty() {
+        PendingAuthorRewards::<T>::remove(session);
+        return;
+      }
+
+      for (article, author, gain) in versions {
+        let amount = u64::try_from(
+          u128::from(reward).saturating_mul(u128::from(gain)) / u128::from(total_gain.max(1)),
+        )
+        .unwrap();
+        if amount == 0 {
+          continue;
+        }
+
+        Rewards::<T>::mutate(author, |rewards| *rewards = rewards.saturating_add(amount));
+        Self::deposit_event(Event::AuthorRewarded { author, article, amount });
+      }
+    }
+
     fn update_participants() {
       let participants = ValidatorSets::<T>::current_participants()
         .into_iter()
@@ -156,6 +276,24 @@ pub mod pallet {
       Participants::<T>::set(Some(participants.try_into().unwrap()));
     }
   }
+
+  #[pallet::call]
+  impl<T: Config> Pallet<T> {
+    /// Mints the rewards the signer earned as an author to it.
+    #[pallet::call_index(0)]
+    #[pallet::weight(<T as Config>::WeightInfo::claim_rewards())]
+    pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+      let author = ensure_signed(origin)?;
+      let amount = Rewards::<T>::take(author);
+      if amount == 0 {
+        Err(Error::<T>::NoRewards)?;
+      }
+
+      Coins::<T>::mint(author, amount)?;
+      Self::deposit_event(Event::RewardsClaimed { author, amount });
+      Ok(())
+    }
+  }
 }
 
 pub use pallet::*;
@@ -3,14 +3,15 @@
 use super::*;
 
 use frame_support::{
-  construct_runtime,
+  construct_runtime, parameter_types,
   traits::{ConstU32, ConstU64},
+  weights::Weight,
 };
 
 use sp_core::{H256, Pair, sr25519::Public};
 use sp_runtime::{
   traits::{BlakeTwo256, IdentityLookup},
-  BuildStorage,
+  BuildStorage, Perbill,
 };
 
 use wikiblocks_primitives::*;
@@ -108,15 +109,42 @@ impl coins::CallToFee<Test> for FeeCollector {
 impl coins::Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type CallToFee = FeeCollector;
+  type WeightInfo = ();
 }
 
 impl validator_sets::Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type ShouldEndSession = Babe;
+  type WeightInfo = ();
+}
+
+parameter_types! {
+  pub const AuthorRewardShare: Perbill = Perbill::from_percent(20);
+  pub static Voted: Vec<(Article, Public, u64)> = vec![];
+}
+
+// The versions voted on during the session which ends next
+pub struct MockVotedVersions;
+impl VotedVersions for MockVotedVersions {
+  fn take_total_gain(_: u32) -> u64 {
+    Voted::get().iter().map(|(_, _, gain)| gain).sum()
+  }
+  fn take(_: u32, limit: u32) -> Vec<(Article, Public, u64)> {
+    let mut voted = Voted::get();
+    let taken = voted.drain(.. voted.len().min(usize::try_from(limit).unwrap())).collect();
+    Voted::set(voted);
+    taken
+  }
+  fn weight(_: u32) -> Weight {
+    Weight::zero()
+  }
 }
 
 impl Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = ();
+  type AuthorRewardShare = AuthorRewardShare;
+  type VotedVersions = MockVotedVersions;
 }
 
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
@@ -1,6 +1,12 @@
-use crate::mock::*;
+use crate::{mock::*, Error, Event, PendingAuthorRewards};
 
-use frame_support::traits::{Hooks, Get};
+use frame_support::{
+  assert_noop, assert_ok,
+  traits::{Hooks, Get},
+};
+use frame_system::RawOrigin;
+
+use sp_core::Pair;
 
 use validator_sets_pallet::{Pallet as ValidatorSets, primitives::Session};
 
@@ -46,3 +52,78 @@ fn check_post_ec_security_emissions() {
     }
   });
 }
+
+#[test]
+fn author_rewards() {
+  new_test_ext().execute_with(|| {
+    let mut block_number = System::block_number();
+    ValidatorSets::<Test>::new_session();
+    ValidatorSets::<Test>::retire_set(Session(0));
+    block_number += <<Test as pallet_babe::Config>::EpochDuration as Get<u64>>::get();
+    System::set_block_number(block_number);
+
+    let author = insecure_pair_from_name("author").public();
+    let other_author = insecure_pair_from_name("other author").public();
+    let title = Title::new("example title".as_bytes().to_vec()).unwrap();
+    let article = Article::new(title.clone(), ArticleVersion(0));
+    let other_article = Article::new(title.clone(), ArticleVersion(1));
+    // the versions which didn't gain accounts come first, filling the versions paid in a block
+    let mut voted = (0 .. MAX_REWARDED_VERSIONS)
+      .map(|i| (Article::new(title.clone(), ArticleVersion(i + 2)), other_author, 0))
+      .collect::<Vec<_>>();
+    voted.extend([(article.clone(), author, 3), (other_article.clone(), other_author, 1)]);
+    Voted::set(voted);
+
+    let current_stake = ValidatorSets::<Test>::total_allocated_stake().unwrap();
+    ValidatorSets::<Test>::new_session();
+    <Emissions as Hooks<BlockNumber>>::on_initialize(block_number + 1);
+
+    let session = ValidatorSets::<Test>::session().unwrap();
+    let block_count = ValidatorSets::<Test>::session_begin_block(session) -
+      ValidatorSets::<Test>::session_begin_block(Session(session.0 - 1));
+    let reward_this_epoch = block_count * REWARD_PER_BLOCK;
+
+    // the validators get their share once the session ends
+    let author_reward = AuthorRewardShare::get() * reward_this_epoch;
+    assert_eq!(
+      ValidatorSets::<Test>::total_allocated_stake().unwrap(),
+      current_stake + reward_this_epoch - author_reward
+    );
+    assert_eq!(Emissions::rewards(author), 0);
+    assert_eq!(Emissions::rewards(other_author), 0);
+
+    // and the authors theirs over the following blocks, pro rata by the accounts gained
+    <Emissions as Hooks<BlockNumber>>::on_initialize(block_number + 2);
+    let reward = (author_reward * 3) / 4;
+    let other_reward = author_reward / 4;
+    assert_eq!(Emissions::rewards(author), reward);
+    assert_eq!(Emissions::rewards(other_author), other_reward);
+    System::assert_has_event(RuntimeEvent::Emissions(Event::AuthorRewarded {
+      author,
+      article,
+      amount: reward,
+    }));
+    System::assert_has_event(RuntimeEvent::Emissions(Event::AuthorRewarded {
+      author: other_author,
+      article: other_article,
+      amount: other_reward,
+    }));
+    assert!(PendingAuthorRewards::<Test>::contains_key(session.0 - 1));
+    <Emissions as Hooks<BlockNumber>>::on_initialize(block_number + 3);
+    assert!(!PendingAuthorRewards::<Test>::contains_key(session.0 - 1));
+
+    // the rewards are minted once claimed
+    let balance = Coins::balances(author).unwrap_or(0);
+    assert_ok!(Emissions::claim_rewards(RawOrigin::Signed(author).into()));
+    assert_eq!(Coins::balances(author), Some(balance + reward));
+    assert_eq!(Emissions::rewards(author), 0);
+    System::assert_last_event(RuntimeEvent::Emissions(Event::RewardsClaimed {
+      author,
+      amount: reward,
+    }));
+    assert_noop!(
+      Emissions::claim_rewards(RawOrigin::Signed(author).into()),
+      Error::<Test>::NoRewards
+    );
+  });
+}
@@ -0,0 +1,77 @@
+//! Weights for the Emissions pallet.
+//!
+//! Generated by running the benchmarks in `benchmarking.rs` natively in the mock runtime of the
+//! pallet, over 10 steps of every component repeated 5 times, with a state backend counting the
+//! storage accesses as the benchmarking state of the node does. The execution times and proof sizes
+//! are of native code over the in-memory trie of the mock, not of the wasm runtime on the reference
+//! hardware, so they are to be replaced by running `wikiblocks-node benchmark pallet --chain dev
+//! --pallet emissions --extrinsic '*' --steps 50 --repeat 20 --output
+//! src/emissions/pallet/src/weights.rs` on it.
+
+use core::marker::PhantomData;
+
+use frame_support::{
+  traits::Get,
+  weights::{constants::RocksDbWeight, Weight},
+};
+
+pub trait WeightInfo {
+  fn claim_rewards() -> Weight;
+  fn end_session() -> Weight;
+  fn reward_authors(v: u32) -> Weight;
+}
+
+/// Weights for the Emissions pallet, using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+  // Reads: Coins::Balances, Coins::Supply, Rewards
+  // Writes: Coins::Balances, Coins::Supply, Rewards
+  fn claim_rewards() -> Weight {
+    Weight::from_parts(35_606_000, 513)
+      .saturating_add(T::DbWeight::get().reads(3))
+      .saturating_add(T::DbWeight::get().writes(3))
+  }
+
+  // Reads: Babe::Authorities, Coins::Balances (601 keys), Coins::Supply, Participants,
+  // ValidatorSets::AllocationPerKeyShare, ValidatorSets::Allocations (600 keys),
+  // ValidatorSets::InSet (600 keys), ValidatorSets::SessionBeginBlock (twice)
+  // Writes: Coins::Balances (601 keys), Coins::Supply, Participants, PendingAuthorRewards,
+  // ValidatorSets::Allocations (600 keys), ValidatorSets::SortedAllocations (1200 keys)
+  fn end_session() -> Weight {
+    Weight::from_parts(26_382_571_000, 89_940)
+      .saturating_add(T::DbWeight::get().reads(1_807))
+      .saturating_add(T::DbWeight::get().writes(2_404))
+  }
+
+  // Reads: Rewards (1024 keys)
+  // Writes: Rewards (1024 keys)
+  // The range of component `v` is `[0, 1024]`.
+  fn reward_authors(v: u32) -> Weight {
+    Weight::from_parts(3_119_000, 57)
+      .saturating_add(Weight::from_parts(8_840_460, 0).saturating_mul(v.into()))
+      .saturating_add(T::DbWeight::get().reads(1).saturating_mul(v.into()))
+      .saturating_add(T::DbWeight::get().writes(1).saturating_mul(v.into()))
+  }
+}
+
+// For tests, and backwards compatibility
+impl WeightInfo for () {
+  fn claim_rewards() -> Weight {
+    Weight::from_parts(35_606_000, 513)
+      .saturating_add(RocksDbWeight::get().reads(3))
+      .saturating_add(RocksDbWeight::get().writes(3))
+  }
+
+  fn end_session() -> Weight {
+    Weight::from_parts(26_382_571_000, 89_940)
+      .saturating_add(RocksDbWeight::get().reads(1_807))
+      .saturating_add(RocksDbWeight::get().writes(2_404))
+  }
+
+  fn reward_authors(v: u32) -> Weight {
+    Weight::from_parts(3_119_000, 57)
+      .saturating_add(Weight::from_parts(8_840_460, 0).saturating_mul(v.into()))
+      .saturating_add(RocksDbWeight::get().reads(1).saturating_mul(v.into()))
+      .saturating_add(RocksDbWeight::get().writes(1).saturating_mul(v.into()))
+  }
+}
@@ -6,8 +6,8 @@ use sp_core::{Decode, Pair as PairTrait, sr25519::Public};
 use sc_service::ChainType;
 
 use wikiblocks_runtime::{
-  primitives::*, BabeConfig, CoinsConfig, EmissionsConfig, GrandpaConfig, RuntimeGenesisConfig,
-  SystemConfig, ValidatorSetsConfig, BABE_GENESIS_EPOCH_CONFIG, WASM_BINARY,
+  primitives::*, ArticlesConfig, BabeConfig, CoinsConfig, EmissionsConfig, GrandpaConfig,
+  RuntimeGenesisConfig, SystemConfig, ValidatorSetsConfig, BABE_GENESIS_EPOCH_CONFIG, WASM_BINARY,
 };
 
 pub type ChainSpec = sc_service::GenericChainSpec<RuntimeGenesisConfig>;
@@ -54,6 +54,12 @@ fn devnet_genesis(
       participants: validators.iter().map(|validator| (*validator, key_share_amount)).collect(),
     },
 
+    articles: ArticlesConfig {
+      rate_limits: RateLimits::default(),
+      protected_titles: vec![],
+      _ignore: Default::default(),
+    },
+
     babe: BabeConfig {
       authorities: validators.iter().map(|validator| ((*validator).into(), 1)).collect(),
       epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG),
@@ -93,6 +99,12 @@ fn testnet_genesis(wasm_binary: &[u8], validators: Vec<&'static str>) -> Runtime
       participants: validators.iter().map(|validator| (*validator, key_share_amount)).collect(),
     },
 
+    articles: ArticlesConfig {
+      rate_limits: RateLimits::default(),
+      protected_titles: vec![],
+      _ignore: Default::default(),
+    },
+
     babe: BabeConfig {
       authorities: validators.iter().map(|validator| ((*validator).into(), 1)).collect(),
       epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG),
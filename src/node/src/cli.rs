@@ -39,4 +39,9 @@ pub enum Subcommand {
 
   // DB meta columns information
   ChainInfo(sc_cli::ChainInfoCmd),
+
+  // Benchmark the runtime's pallets
+  #[cfg(feature = "runtime-benchmarks")]
+  #[clap(subcommand)]
+  Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 }
@@ -98,6 +98,12 @@ pub fn run() -> sc_cli::Result<()> {
       cli.create_runner(cmd)?.sync_run(|config| cmd.run::<Block>(&config))
     }
 
+    #[cfg(feature = "runtime-benchmarks")]
+    Some(Subcommand::Benchmark(cmd)) => cli.create_runner(cmd)?.sync_run(|config| match cmd {
+      frame_benchmarking_cli::BenchmarkCmd::Pallet(cmd) => cmd.run::<Block, ()>(config),
+      _ => Err("only pallets can be benchmarked".into()),
+    }),
+
     None => cli.create_runner(&cli.run)?.run_node_until_exit(|mut config| async {
       if config.role.is_authority() {
         config.state_pruning = Some(PruningMode::ArchiveAll);
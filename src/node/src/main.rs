@@ -5,6 +5,7 @@ mod service;
 
 mod command;
 
+mod search;
 mod rpc;
 mod cli;
 
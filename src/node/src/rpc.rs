@@ -2,12 +2,15 @@ use std::{sync::Arc, collections::HashSet};
 
 use rand_core::{RngCore, OsRng};
 
+use sp_core::H256;
 use sp_blockchain::{Error as BlockchainError, HeaderBackend, HeaderMetadata};
 use sp_block_builder::BlockBuilder;
 use sp_api::ProvideRuntimeApi;
 
+use sc_client_api::BlockBackend;
+
 use wikiblocks_runtime::{
-  primitives::{SubstrateAmount, PublicKey},
+  primitives::{MAX_PAGE_LEN, SubstrateAmount, PublicKey},
   Nonce, Block, WikiblocksRuntimeApi,
 };
 
@@ -18,18 +21,22 @@ use jsonrpsee::RpcModule;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 
+use crate::search::SearchIndex;
+
 pub struct FullDeps<C, P> {
   pub id: String,
   pub client: Arc<C>,
   pub pool: Arc<P>,
   pub deny_unsafe: DenyUnsafe,
   pub authority_discovery: Option<sc_authority_discovery::Service>,
+  pub search: Arc<SearchIndex>,
 }
 
 pub fn create_full<
   C: ProvideRuntimeApi<Block>
     + HeaderBackend<Block>
     + HeaderMetadata<Block, Error = BlockchainError>
+    + BlockBackend<Block>
     + Send
     + Sync
     + 'static,
@@ -47,11 +54,47 @@ where
   use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 
   let mut module = RpcModule::new(());
-  let FullDeps { id, client, pool, deny_unsafe, authority_discovery } = deps;
+  let FullDeps { id, client, pool, deny_unsafe, authority_discovery, search } = deps;
 
   module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
   module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 
+  // The scripts of article versions are indexed along with the extrinsics which added them, not
+  // kept in state, so those extrinsics are fetched by their hash from the blocks which added them
+  let mut articles_module = RpcModule::new(client.clone());
+  articles_module.register_method("articles_indexedExtrinsic", |params, client| {
+    let hash: String = params.one()?;
+    let invalid_hash = || {
+      jsonrpsee::core::Error::to_call_error(std::io::Error::other(
+        "expected a hex-encoded 32-byte hash",
+      ))
+    };
+    let hash = hex::decode(hash.strip_prefix("0x").unwrap_or(&hash)).map_err(|_| invalid_hash())?;
+    let hash = <[u8; 32]>::try_from(hash).map_err(|_| invalid_hash())?;
+
+    let extrinsic = client.indexed_transaction(H256::from(hash)).map_err(|_| {
+      jsonrpsee::core::Error::to_call_error(std::io::Error::other(
+        "couldn't read indexed transactions, which may be pruned from this node",
+      ))
+    })?;
+    Ok(extrinsic.map(hex::encode))
+  })?;
+  module.merge(articles_module)?;
+
+  // Searches are served from the index the node keeps of the finalized articles
+  let mut search_module = RpcModule::new(search);
+  search_module.register_method("articles_searchTitles", |params, search| {
+    let (prefix, limit): (String, u32) = params.parse()?;
+    let limit = usize::try_from(limit.min(MAX_PAGE_LEN)).unwrap();
+    Ok(search.search_titles(&prefix, limit))
+  })?;
+  search_module.register_method("articles_search", |params, search| {
+    let (query, limit): (String, u32) = params.parse()?;
+    let limit = usize::try_from(limit.min(MAX_PAGE_LEN)).unwrap();
+    Ok(search.search(&query, limit))
+  })?;
+  module.merge(search_module)?;
+
   if let Some(authority_discovery) = authority_discovery {
     let mut authority_discovery_module =
       RpcModule::new((id, client, RwLock::new(authority_discovery)));
@@ -0,0 +1,389 @@
+use std::{
+  sync::{Arc, RwLock},
+  collections::{HashMap, BTreeMap, BTreeSet},
+};
+
+use scale::Decode;
+
+use futures_util::stream::StreamExt;
+
+use sp_core::storage::StorageKey;
+use sp_blockchain::HeaderBackend;
+
+use sc_client_api::{BlockBackend, BlockchainEvents, StorageProvider};
+
+use wikiblocks_runtime::{
+  primitives::{render, Article, ArticleVersion, Script, Title},
+  support::{storage::StoragePrefixedMap, traits::ExtrinsicCall},
+  system, articles, Hash, Runtime, RuntimeCall, RuntimeEvent, Transaction,
+};
+
+use crate::service::FullClient;
+
+// The lowercased words of some text, splitting it on everything which isn't alphanumeric
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+  text.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty()).map(str::to_lowercase)
+}
+
+#[derive(Default)]
+struct Index {
+  // the canonical titles, keyed by their lowercased form
+  titles: BTreeMap<String, BTreeSet<String>>,
+  // how many times every word occurs in every article
+  words: HashMap<String, HashMap<String, u32>>,
+  // the words indexed for every article, so they can be removed once it changes
+  article_words: HashMap<String, Vec<String>>,
+}
+
+impl Index {
+  fn remove(&mut self, title: &str) {
+    let lowercase = title.to_lowercase();
+    if let Some(titles) = self.titles.get_mut(&lowercase) {
+      titles.remove(title);
+      if titles.is_empty() {
+        self.titles.remove(&lowercase);
+      }
+    }
+
+    for word in self.article_words.remove(title).unwrap_or_default() {
+      if let Some(articles) = self.words.get_mut(&word) {
+        articles.remove(title);
+        if articles.is_empty() {
+          self.words.remove(&word);
+        }
+      }
+    }
+  }
+
+  fn insert(&mut self, title: String, body: Option<&str>) {
+    self.remove(&title);
+    self.titles.entry(title.to_lowercase()).or_default().insert(title.clone());
+
+    let mut counts = HashMap::<_, u32>::new();
+    for word in words(&title).chain(body.into_iter().flat_map(words)) {
+      *counts.entry(word).or_default() += 1;
+    }
+    let mut article_words = Vec::with_capacity(counts.len());
+    for (word, count) in counts {
+      self.words.entry(word.clone()).or_default().insert(title.clone(), count);
+      article_words.push(word);
+    }
+    self.article_words.insert(title, article_words);
+  }
+}
+
+/// An index of the titles and rendered bodies of the articles as of the latest finalized block,
+/// kept by the node to serve searches over RPC.
+///
+/// Redirects are indexed by their title alone.
+#[derive(Default)]
+pub struct SearchIndex(RwLock<Index>);
+
+impl SearchIndex {
+  /// Returns up to `limit` canonical titles starting with `prefix`, ignoring case, in
+  /// lexicographic order.
+  pub fn search_titles(&self, prefix: &str, limit: usize) -> Vec<String> {
+    let Ok(prefix) = Title::new(prefix.as_bytes().to_vec()).and_then(|prefix| prefix.canonical())
+    else {
+      return vec![];
+    };
+    let prefix = String::from_utf8_lossy(prefix.data()).to_lowercase();
+
+    let index = self.0.read().unwrap();
+    index
+      .titles
+      .range(prefix.clone() ..)
+      .take_while(|(lowercase, _)| lowercase.starts_with(&prefix))
+      .flat_map(|(_, titles)| titles)
+      .take(limit)
+      .cloned()
+      .collect()
+  }
+
+  /// Returns up to `limit` canonical titles of the articles containing every word of `query`,
+  /// ranked by how many times they contain them.
+  pub fn search(&self, query: &str, limit: usize) -> Vec<String> {
+    let mut query = words(query).collect::<Vec<_>>();
+    query.sort();
+    query.dedup();
+    if query.is_empty() {
+      return vec![];
+    }
+
+    let index = self.0.read().unwrap();
+    let Some(occurrences) =
+      query.iter().map(|word| index.words.get(word)).collect::<Option<Vec<_>>>()
+    else {
+      return vec![];
+    };
+    // only the articles with the rarest word can contain all of them
+    let rarest = occurrences.iter().min_by_key(|articles| articles.len()).unwrap();
+    let mut results = rarest
+      .keys()
+      .filter_map(|title| {
+        let mut score = 0u64;
+        for articles in &occurrences {
+          score += u64::from(*articles.get(title)?);
+        }
+        Some((score, title))
+      })
+      .collect::<Vec<_>>();
+    results.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then(a.cmp(b)));
+    results.into_iter().take(limit).map(|(_, title)| title.clone()).collect()
+  }
+
+  // Reindexes a title as of the block `hash`, removing it if it's no longer an article or redirect
+  fn refresh(&self, client: &FullClient, hash: Hash, title: &Title) {
+    let Ok(name) = String::from_utf8(title.data().to_vec()) else { return };
+
+    if let Some(version) =
+      read::<ArticleVersion>(client, hash, articles::LastVersion::<Runtime>::hashed_key_for(title))
+    {
+      // articles whose scripts were pruned from this node are only indexed by their title
+      let body = body(client, hash, &Article::new(title.clone(), version));
+      let body = body.map(|body| String::from_utf8_lossy(&body).into_owned());
+      self.0.write().unwrap().insert(name, body.as_deref());
+    } else if client
+      .storage(hash, &StorageKey(articles::Redirects::<Runtime>::hashed_key_for(title)))
+      .ok()
+      .flatten()
+      .is_some()
+    {
+      self.0.write().unwrap().insert(name, None);
+    } else {
+      self.0.write().unwrap().remove(&name);
+    }
+  }
+}
+
+fn read<T: Decode>(client: &FullClient, hash: Hash, key: Vec<u8>) -> Option<T> {
+  let data = client.storage(hash, &StorageKey(key)).ok()??;
+  T::decode(&mut data.0.as_slice()).ok()
+}
+
+// Reads a script indexed along with the extrinsic which added it, by the hash of the extrinsic
+fn indexed_script(client: &FullClient, hash: [u8; 32]) -> Option<Script> {
+  let extrinsic = client.indexed_transaction(Hash::from(hash)).ok()??;
+  let transaction = Transaction::decode(&mut extrinsic.as_slice()).ok()?;
+  match ExtrinsicCall::call(&transaction) {
+    RuntimeCall::Articles(
+      articles::Call::add_article { script, .. } | articles::Call::add_version { script, .. },
+    ) => Some(script.clone()),
+    _ => None,
+  }
+}
+
+// Renders an article version starting from the nearest checkpoints of the versions it's built on,
+// so the scripts of the versions before a checkpoint aren't fetched
+fn render_from_checkpoints(
+  version: ArticleVersion,
+  checkpoint: impl Fn(ArticleVersion) -> Option<Script>,
+  script: impl Fn(ArticleVersion) -> Option<Script>,
+) -> Option<Vec<u8>> {
+  render(version, |version| checkpoint(version).or_else(|| script(version))).ok()
+}
+
+// Renders the body of an article version, reading the scripts which aren't in state anymore from
+// the extrinsics which added them
+fn body(client: &FullClient, hash: Hash, article: &Article) -> Option<Vec<u8>> {
+  let current = |version| Article::new(article.title().clone(), version);
+  render_from_checkpoints(
+    article.version(),
+    |version| {
+      read(client, hash, articles::Checkpoints::<Runtime>::hashed_key_for(current(version)))
+    },
+    |version| {
+      let current = current(version);
+      read(client, hash, articles::Articles::<Runtime>::hashed_key_for(&current)).or_else(|| {
+        let (script_hash, _) = read::<([u8; 32], u32)>(
+          client,
+          hash,
+          articles::ScriptHashes::<Runtime>::hashed_key_for(&current),
+        )?;
+        indexed_script(client, script_hash)
+      })
+    },
+  )
+}
+
+// The titles of every entry of a map keyed by titles
+fn titles(client: &FullClient, hash: Hash, prefix: [u8; 32]) -> Vec<Title> {
+  let Ok(keys) = client.storage_keys(hash, Some(&StorageKey(prefix.to_vec())), None) else {
+    return vec![];
+  };
+  keys
+    .filter_map(|key| {
+      // the key is the prefix, followed by the blake2_128 hash of the title and the title itself
+      Title::decode(&mut key.0.get((32 + 16) ..)?).ok()
+    })
+    .collect()
+}
+
+// The titles of the articles and redirects changed by the events of a block
+fn changed_titles(client: &FullClient, hash: Hash) -> Vec<Title> {
+  let events = read::<Vec<system::EventRecord<RuntimeEvent, Hash>>>(
+    client,
+    hash,
+    system::Events::<Runtime>::hashed_key().to_vec(),
+  )
+  .unwrap_or_default();
+  titles_changed_by(events.into_iter().map(|record| record.event))
+}
+
+// The titles of the articles and redirects changed by some events
+fn titles_changed_by(events: impl IntoIterator<Item = RuntimeEvent>) -> Vec<Title> {
+  let mut titles = vec![];
+  for event in events {
+    let RuntimeEvent::Articles(event) = event else { continue };
+    match event {
+      articles::Event::ArticleCreated { article, .. } |
+      articles::Event::VersionAdded { article, .. } |
+      articles::Event::Reverted { article, .. } |
+      articles::Event::VersionAccepted { article, .. } => titles.push(article.title().clone()),
+      articles::Event::RedirectAdded { from, .. } => titles.push(from),
+      articles::Event::ArticleMoved { from, to, .. } => titles.extend([from, to]),
+      _ => {}
+    }
+  }
+  titles
+}
+
+/// Indexes every article as of the latest finalized block, then keeps the index up to date as
+/// blocks are finalized.
+pub async fn run(client: Arc<FullClient>, index: Arc<SearchIndex>) {
+  // subscribe before indexing the existing articles, so no article changed meanwhile is missed
+  let mut finalized = client.finality_notification_stream();
+
+  let hash = client.info().finalized_hash;
+  let mut existing = titles(&client, hash, articles::LastVersion::<Runtime>::final_prefix());
+  existing.extend(titles(&client, hash, articles::Redirects::<Runtime>::final_prefix()));
+  for title in existing {
+    index.refresh(&client, hash, &title);
+  }
+  log::info!("indexed the articles as of block {hash} for search");
+
+  while let Some(notification) = finalized.next().await {
+    let mut changed = Vec::<Title>::new();
+    for hash in notification.tree_route.iter().chain(core::iter::once(&notification.hash)) {
+      for title in changed_titles(&client, *hash) {
+        if !changed.contains(&title) {
+          changed.push(title);
+        }
+      }
+    }
+    for title in changed {
+      index.refresh(&client, notification.hash, &title);
+    }
+  }
+}
+
+#[cfg(test)]
+fn test_index(articles: &[(&str, Option<&str>)]) -> SearchIndex {
+  let index = SearchIndex::default();
+  for (title, body) in articles {
+    index.0.write().unwrap().insert(title.to_string(), *body);
+  }
+  index
+}
+
+#[test]
+fn test_search_titles() {
+  let index = test_index(&[
+    ("Paris", Some("The capital of France")),
+    ("Parish", None),
+    ("Paris, Texas", None),
+    ("PARIS", None),
+    ("Rome", Some("The capital of Italy")),
+  ]);
+
+  // titles are matched ignoring case, and listed in lexicographic order
+  assert_eq!(index.search_titles("par", 10), ["PARIS", "Paris", "Paris, Texas", "Parish"]);
+  assert_eq!(index.search_titles("Paris,", 10), ["Paris, Texas"]);
+  assert_eq!(index.search_titles("par", 2), ["PARIS", "Paris"]);
+  assert_eq!(index.search_titles(" rome", 10), ["Rome"]);
+  assert!(index.search_titles("italy", 10).is_empty());
+  assert!(index.search_titles("  ", 10).is_empty());
+}
+
+#[test]
+fn test_search() {
+  let index = test_index(&[
+    ("Paris", Some("Paris is the capital of France. Paris is its largest city.")),
+    ("Paris, Texas", Some("A city in Texas named after Paris")),
+    ("Lyon", Some("A city in France")),
+    ("Rome", Some("The capital of Italy")),
+  ]);
+
+  // articles are ranked by how many times they contain the words, titles included
+  assert_eq!(index.search("PARIS", 10), ["Paris", "Paris, Texas"]);
+  assert_eq!(index.search("city", 10), ["Lyon", "Paris", "Paris, Texas"]);
+  assert_eq!(index.search("city", 1), ["Lyon"]);
+  // every word has to be contained
+  assert_eq!(index.search("capital, france", 10), ["Paris"]);
+  assert!(index.search("capital berlin", 10).is_empty());
+  assert!(index.search("cap", 10).is_empty());
+  assert!(index.search(" ,.", 10).is_empty());
+}
+
+#[test]
+fn test_updates() {
+  use sp_core::sr25519::Public;
+
+  let title = |title: &str| Title::new(title.as_bytes().to_vec()).unwrap();
+  let author = Public::from_raw([0; 32]);
+  let events = [
+    articles::Event::VersionAdded {
+      article: Article::new(title("Rome"), ArticleVersion(1)),
+      author,
+    },
+    articles::Event::ArticleMoved {
+      from: title("Paris"),
+      to: title("Paname"),
+      author: Some(author),
+    },
+    articles::Event::ArticleTagged { title: title("Lyon"), category: title("Cities"), author },
+  ];
+  let changed = titles_changed_by(events.into_iter().map(RuntimeEvent::Articles));
+  assert_eq!(changed, [title("Rome"), title("Paris"), title("Paname")]);
+
+  // a finalized block changing the body of an article replaces the words it's indexed by
+  let index = test_index(&[("Rome", Some("The capital of the Papal States")), ("Paris", None)]);
+  assert_eq!(index.search("papal", 10), ["Rome"]);
+  index.0.write().unwrap().insert("Rome".to_string(), Some("The capital of Italy"));
+  assert!(index.search("papal", 10).is_empty());
+  assert_eq!(index.search("italy", 10), ["Rome"]);
+
+  // and one moving an article removes its old title, which is indexed again if it's a redirect
+  index.0.write().unwrap().remove("Paris");
+  index.0.write().unwrap().insert("Paname".to_string(), None);
+  assert!(index.search_titles("paris", 10).is_empty());
+  assert_eq!(index.search("paname", 10), ["Paname"]);
+}
+
+#[test]
+fn test_render_from_checkpoints() {
+  use wikiblocks_runtime::primitives::{Body, OpCode};
+
+  let add = |text: &str| OpCode::Add(Body::new(text.as_bytes().to_vec()).unwrap());
+  let scripts = [
+    Script::new(vec![add("a")]).unwrap(),
+    Script::new(vec![OpCode::Reference(ArticleVersion(0)), OpCode::Cp(1), add("b")]).unwrap(),
+    Script::new(vec![OpCode::Reference(ArticleVersion(1)), OpCode::Cp(2), add("c")]).unwrap(),
+    Script::new(vec![OpCode::Reference(ArticleVersion(2)), OpCode::Cp(3), add("d")]).unwrap(),
+  ];
+  let script = |version: ArticleVersion| scripts.get(usize::try_from(version.0).unwrap()).cloned();
+
+  assert_eq!(render_from_checkpoints(ArticleVersion(3), |_| None, script).unwrap(), b"abcd");
+
+  // the scripts of the versions before the checkpoint aren't fetched
+  let checkpoint =
+    |version: ArticleVersion| (version.0 == 2).then(|| Script::full_text(b"abc").unwrap());
+  let after_checkpoint = |version: ArticleVersion| {
+    assert!(version.0 > 2, "fetched the script of version {}", version.0);
+    script(version)
+  };
+  assert_eq!(
+    render_from_checkpoints(ArticleVersion(3), checkpoint, after_checkpoint).unwrap(),
+    b"abcd"
+  );
+}
@@ -315,6 +315,13 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
     None
   };
 
+  let search = Arc::new(crate::search::SearchIndex::default());
+  task_manager.spawn_handle().spawn_blocking(
+    "articles-search",
+    Some("rpc"),
+    crate::search::run(client.clone(), search.clone()),
+  );
+
   let rpc_builder = {
     let id = config.chain_spec.id().to_string();
     let client = client.clone();
@@ -327,6 +334,7 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
         pool: pool.clone(),
         deny_unsafe,
         authority_discovery: authority_discovery.clone(),
+        search: search.clone(),
       })
       .map_err(Into::into)
     })
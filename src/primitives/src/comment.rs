@@ -0,0 +1,77 @@
+#[cfg(feature = "std")]
+use zeroize::Zeroize;
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshSerialize, BorshDeserialize};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use scale::{Encode, Decode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+use sp_core::{ConstU32, bounded::BoundedVec};
+use sp_std::vec::Vec;
+
+#[cfg(feature = "borsh")]
+use crate::{borsh_serialize_bounded_vec, borsh_deserialize_bounded_vec};
+use crate::{ArticleVersion, WikiblocksAddress};
+
+/// The position of a comment on the talk page of an article, in the order comments were posted.
+pub type CommentId = u64;
+
+// Talk pages are for discussing articles, not writing them
+pub const MAX_COMMENT_LEN: u32 = 16 * 1024;
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CommentBody(
+  #[cfg_attr(
+    feature = "borsh",
+    borsh(
+      serialize_with = "borsh_serialize_bounded_vec",
+      deserialize_with = "borsh_deserialize_bounded_vec"
+    )
+  )]
+  BoundedVec<u8, ConstU32<{ MAX_COMMENT_LEN }>>,
+);
+
+#[cfg(feature = "std")]
+impl Zeroize for CommentBody {
+  fn zeroize(&mut self) {
+    self.0.as_mut().zeroize()
+  }
+}
+
+impl CommentBody {
+  pub fn new(data: Vec<u8>) -> Result<CommentBody, &'static str> {
+    Ok(CommentBody(data.try_into().map_err(|_| "comment length exceeds {MAX_COMMENT_LEN}")?))
+  }
+
+  pub fn data(&self) -> &[u8] {
+    self.0.as_ref()
+  }
+
+  #[cfg(feature = "std")]
+  pub fn consume(self) -> Vec<u8> {
+    self.0.into_inner()
+  }
+}
+
+impl AsRef<[u8]> for CommentBody {
+  fn as_ref(&self) -> &[u8] {
+    self.0.as_ref()
+  }
+}
+
+/// A comment on the talk page of an article.
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Comment {
+  pub author: WikiblocksAddress,
+  /// The version of the article the comment is about, if it's about a specific one.
+  pub version: Option<ArticleVersion>,
+  /// The comment this is a reply to, which is always posted before it.
+  pub parent: Option<CommentId>,
+  pub body: CommentBody,
+}
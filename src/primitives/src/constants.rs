@@ -15,6 +15,41 @@ pub const MONTHS: BlockNumber = 30 * DAYS;
 // Defines a year as 12 inaccurate months, which is 360 days literally (~1.5% off)
 pub const YEARS: BlockNumber = 12 * MONTHS;
 
+// The maximum amount of items a paginated listing returns at once.
+pub const MAX_PAGE_LEN: u32 = 1_000;
+
+// Every version which is a multiple of this gets a full-text checkpoint.
+pub const CHECKPOINT_INTERVAL: u32 = 64;
+// The most scripts rendering a version may replay before a full-text checkpoint of it is stored.
+pub const MAX_RENDER_DEPTH: u32 = 16;
+// The most versions an edit may be rebased over, when it was written on top of an older version.
+pub const MAX_REBASE_DEPTH: u32 = 8;
+
+// The most redirects followed when resolving a title to an article.
+pub const MAX_REDIRECT_DEPTH: u32 = 8;
+// The most categories an article may be tagged with.
+pub const MAX_ARTICLE_CATEGORIES: u32 = 32;
+
+// The most versions of a protected article which may wait for review at once.
+pub const MAX_PENDING_VERSIONS: u32 = 64;
+
+// The shortest period coins backing a vote are held for.
+pub const VOTE_LOCK_PERIOD: BlockNumber = WEEKS;
+// The coins backing a vote are counted in whole coins.
+pub const VOTE_WEIGHT_UNIT: u64 = 10u64.pow(8);
+// The most releases of coins backing votes, and votes whose coins are released, handled in a
+// block. The rest are carried over to the next blocks.
+pub const MAX_EXPIRATIONS: u32 = 256;
+
+// The amount of latest versions of an article which may be its canonical version.
+pub const CANONICAL_WINDOW: u32 = 16;
+// The score a version needs to become the canonical version of its article. Scores count the
+// weight of votes, so a vote backed by enough coins reaches it alone.
+pub const CANONICAL_QUORUM: i64 = 3;
+
+// The most voted versions whose authors are rewarded from the emissions of a session in a block.
+pub const MAX_REWARDED_VERSIONS: u32 = 1024;
+
 // 1000b/1usd rate for data insert into the chain.
 // this represent a usd. 1000 mill.
 pub const DATA_FEE_RATE: u64 = 1000;
@@ -0,0 +1,339 @@
+use super::*;
+
+// The maximum edit distance searched for before falling back to a coarser diff.
+const MAX_EDIT_DISTANCE: usize = 1024;
+
+// Encoded sizes of the opcodes, used to decide if copying from the reference is cheaper than
+// adding the same data again.
+const REFERENCE_COST: usize = 5;
+const BEGIN_END_COST: usize = 1;
+const DEL_COST: usize = 5;
+const MVCR_COST: usize = 6;
+const CP_COST: usize = 5;
+
+// A run of data which is the same in both the old and the new body.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Match {
+  old: usize,
+  new: usize,
+  len: usize,
+}
+
+// Finds the longest common subsequence of `a` and `b` using Myers' algorithm.
+//
+// Returns None if the edit distance between them exceeds MAX_EDIT_DISTANCE.
+fn myers<T: PartialEq>(a: &[T], b: &[T]) -> Option<Vec<Match>> {
+  let (n, m) = (isize::try_from(a.len()).unwrap(), isize::try_from(b.len()).unwrap());
+  let max = usize::try_from(n + m).unwrap().min(MAX_EDIT_DISTANCE);
+
+  // v[k] is the furthest x reached on the diagonal k, offset by `max + 1`
+  let offset = isize::try_from(max).unwrap() + 1;
+  let index = |k: isize| usize::try_from(k + offset).unwrap();
+  let mut v = vec![0isize; (2 * max) + 3];
+  // the state of v at the beginning of every round, only keeping the diagonals that round reads
+  let mut trace = vec![];
+
+  let mut found = false;
+  for d in 0 ..= isize::try_from(max).unwrap() {
+    trace.push(v[index(-d - 1) ..= index(d + 1)].to_vec());
+    for k in (-d ..= d).step_by(2) {
+      let mut x = if (k == -d) || ((k != d) && (v[index(k - 1)] < v[index(k + 1)])) {
+        v[index(k + 1)]
+      } else {
+        v[index(k - 1)] + 1
+      };
+      let mut y = x - k;
+      while (x < n) && (y < m) && (a[usize::try_from(x).unwrap()] == b[usize::try_from(y).unwrap()])
+      {
+        x += 1;
+        y += 1;
+      }
+      v[index(k)] = x;
+
+      if (x >= n) && (y >= m) {
+        found = true;
+        break;
+      }
+    }
+    if found {
+      break;
+    }
+  }
+  if !found {
+    return None;
+  }
+
+  // walk back through the trace, collecting the diagonals (matching elements)
+  let mut matches: Vec<Match> = vec![];
+  let (mut x, mut y) = (n, m);
+  for (d, v) in trace.iter().enumerate().rev() {
+    let d = isize::try_from(d).unwrap();
+    let at = |k: isize| v[usize::try_from(k + d + 1).unwrap()];
+
+    let k = x - y;
+    let prev_k = if (k == -d) || ((k != d) && (at(k - 1) < at(k + 1))) { k + 1 } else { k - 1 };
+    let prev_x = at(prev_k);
+    let prev_y = prev_x - prev_k;
+    while (x > prev_x) && (y > prev_y) {
+      x -= 1;
+      y -= 1;
+      let (old, new) = (usize::try_from(x).unwrap(), usize::try_from(y).unwrap());
+      match matches.last_mut() {
+        Some(last) if (last.old == old + 1) && (last.new == new + 1) => {
+          last.old = old;
+          last.new = new;
+          last.len += 1;
+        }
+        _ => matches.push(Match { old, new, len: 1 }),
+      }
+    }
+    x = prev_x;
+    y = prev_y;
+  }
+  matches.reverse();
+  Some(matches)
+}
+
+// Splits the text into lines, along with the position each line starts at.
+fn lines(text: &[char]) -> Vec<(usize, &[char])> {
+  let mut start = 0;
+  text
+    .split_inclusive(|c| *c == '\n')
+    .map(|line| {
+      start += line.len();
+      (start - line.len(), line)
+    })
+    .collect()
+}
+
+// Finds the runs of text which are the same in `old` and `new`.
+//
+// If the bodies are too different to diff character by character, they're diffed line by line
+// instead.
+fn matches(old: &[char], new: &[char]) -> Vec<Match> {
+  let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+  let suffix = old[prefix ..]
+    .iter()
+    .rev()
+    .zip(new[prefix ..].iter().rev())
+    .take_while(|(a, b)| a == b)
+    .count();
+  let old_middle = &old[prefix .. (old.len() - suffix)];
+  let new_middle = &new[prefix .. (new.len() - suffix)];
+
+  let mut res = vec![];
+  if prefix != 0 {
+    res.push(Match { old: 0, new: 0, len: prefix });
+  }
+
+  let middle = myers(old_middle, new_middle).unwrap_or_else(|| {
+    let (old_lines, new_lines) = (lines(old_middle), lines(new_middle));
+    let old_data = old_lines.iter().map(|(_, line)| *line).collect::<Vec<_>>();
+    let new_data = new_lines.iter().map(|(_, line)| *line).collect::<Vec<_>>();
+
+    // translate the matched lines back to character positions
+    myers(&old_data, &new_data)
+      .unwrap_or_default()
+      .into_iter()
+      .map(|m| {
+        let len = old_data[m.old .. (m.old + m.len)].iter().map(|line| line.len()).sum();
+        Match { old: old_lines[m.old].0, new: new_lines[m.new].0, len }
+      })
+      .collect()
+  });
+  res.extend(middle.into_iter().map(|m| Match { old: m.old + prefix, new: m.new + prefix, ..m }));
+
+  if suffix != 0 {
+    res.push(Match { old: old.len() - suffix, new: new.len() - suffix, len: suffix });
+  }
+  res
+}
+
+fn move_cost(cursor: usize, to: usize, len: usize) -> usize {
+  if to == cursor {
+    0
+  } else if (to == 0) || (to == len) {
+    BEGIN_END_COST
+  } else if to > cursor {
+    DEL_COST
+  } else {
+    MVCR_COST
+  }
+}
+
+fn move_cursor(opcodes: &mut Vec<OpCode>, cursor: usize, to: usize, len: usize) {
+  let to_u32 = |n: usize| u32::try_from(n).unwrap();
+  if to == cursor {
+    return;
+  }
+  opcodes.push(if to == 0 {
+    OpCode::Begin
+  } else if to == len {
+    OpCode::End
+  } else if to > cursor {
+    // deleting moves the cursor forward while being cheaper to encode than MvCr
+    OpCode::Del(to_u32(to - cursor))
+  } else {
+    OpCode::MvCr(Direction::Left, to_u32(cursor - to))
+  });
+}
+
+// The encoded length of the text.
+fn utf8_len(text: &[char]) -> usize {
+  text.iter().copied().map(char::len_utf8).sum()
+}
+
+fn add(opcodes: &mut Vec<OpCode>, text: &[char]) {
+  let mut data = Vec::new();
+  for c in text {
+    if (data.len() + c.len_utf8()) > usize::try_from(MAX_BODY_LEN).unwrap() {
+      opcodes.push(OpCode::Add(Body::new(core::mem::take(&mut data)).unwrap()));
+    }
+    data.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+  }
+  if !data.is_empty() {
+    opcodes.push(OpCode::Add(Body::new(data).unwrap()));
+  }
+}
+
+// Builds the opcodes, copying every match which is cheaper to copy than to add and is longer
+// than `min_copy`.
+fn compile(
+  reference: ArticleVersion,
+  old: &[char],
+  new: &[char],
+  matches: &[Match],
+  min_copy: usize,
+) -> Vec<OpCode> {
+  let mut opcodes = vec![];
+  let mut referenced = false;
+  // position of the cursor within the old body
+  let mut cursor = 0;
+  // how much of the new body was written so far
+  let mut written = 0;
+  for m in matches {
+    let mut cost = move_cost(cursor, m.old, old.len()) + CP_COST;
+    if !referenced {
+      cost += REFERENCE_COST;
+    }
+    if (m.len <= min_copy) || (utf8_len(&new[m.new .. (m.new + m.len)]) <= cost) {
+      continue;
+    }
+
+    add(&mut opcodes, &new[written .. m.new]);
+    if !referenced {
+      opcodes.push(OpCode::Reference(reference));
+      referenced = true;
+    }
+    move_cursor(&mut opcodes, cursor, m.old, old.len());
+    opcodes.push(OpCode::Cp(u32::try_from(m.len).unwrap()));
+    cursor = m.old;
+    written = m.new + m.len;
+  }
+  add(&mut opcodes, &new[written ..]);
+  opcodes
+}
+
+impl Script {
+  /// Compiles the edits which turn `old` into `new` into a script counting characters.
+  ///
+  /// `old` is expected to be the rendered body of the `reference` version. Runs of text which
+  /// are cheaper to copy from the reference than to add again are copied, keeping the encoded
+  /// script, which is what fees are charged for, compact.
+  pub fn diff(reference: ArticleVersion, old: &str, new: &str) -> Result<Script, &'static str> {
+    let old = old.chars().collect::<Vec<_>>();
+    let new = new.chars().collect::<Vec<_>>();
+    if new.is_empty() {
+      Err("body can't be empty")?;
+    }
+    if new.len() > usize::try_from(MAX_BODY_LEN).unwrap() {
+      Err("body length exceeds {MAX_BODY_LEN}")?;
+    }
+
+    let matches = matches(&old, &new);
+    let mut min_copy = 0;
+    loop {
+      let opcodes = compile(reference, &old, &new, &matches, min_copy);
+      // if there are too many opcodes, only copy longer matches
+      if opcodes.len() > usize::try_from(MAX_SCRIPT_LEN).unwrap() {
+        min_copy = (min_copy + 1) * 2;
+        continue;
+      }
+
+      let script = Script::new(opcodes)?;
+      if script.encode().len() > usize::try_from(MAX_DATA_LEN).unwrap() {
+        Err("script length exceeds {MAX_DATA_LEN}")?;
+      }
+      return Ok(script);
+    }
+  }
+}
+
+#[cfg(test)]
+fn test_diff(old: &str, new: &str) -> Script {
+  let script = Script::diff(ArticleVersion(0), old, new).unwrap();
+  let rendered =
+    script.apply(|version| (version == ArticleVersion(0)).then_some(old.as_bytes())).unwrap();
+  assert_eq!(rendered, new.as_bytes());
+  script
+}
+
+#[test]
+fn test_diff_script() {
+  let old = "the quick brown fox jumps over the lazy dog. ".repeat(20);
+
+  // a small edit should only add the new data
+  let new = old.replacen("lazy", "sleepy", 1);
+  let script = test_diff(&old, &new);
+  assert!(script.encode().len() < 50);
+  assert!(script.data().contains(&OpCode::Add(Body::new(b"sleep".to_vec()).unwrap())));
+
+  // moving data around
+  let new = format!("{}{}", &old[450 ..], &old[.. 450]);
+  test_diff(&old, &new);
+
+  // a short body shouldn't reference anything
+  let script = test_diff("ab", "abc");
+  assert_eq!(script.data(), &[OpCode::Add(Body::new(b"abc".to_vec()).unwrap())]);
+
+  // a completely different body
+  test_diff(&old, &"a new body".repeat(100));
+  test_diff("", &old);
+
+  // edits within non-ASCII text
+  let old = "Вікіпедія — вільна енциклопедія. 维基百科是一个自由的百科全书。".repeat(10);
+  test_diff(&old, &old.replacen("вільна", "відкрита", 3));
+  test_diff(&old, &old.replace("自由", "多语言"));
+
+  assert!(Script::diff(ArticleVersion(0), &old, "").is_err());
+}
+
+#[test]
+fn test_diff_random() {
+  use rand_core::{RngCore, SeedableRng};
+  use rand_chacha::ChaCha20Rng;
+
+  // keep a small alphabet with newlines so the line diff gets exercised too
+  const ALPHABET: [char; 6] = ['a', 'b', 'é', '世', '😀', '\n'];
+
+  // seeded, so a failing case can be reproduced
+  for seed in 0 .. 20 {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    let random =
+      |rng: &mut ChaCha20Rng| ALPHABET[usize::try_from(rng.next_u32()).unwrap() % ALPHABET.len()];
+
+    let old = (0 .. 2000).map(|_| random(&mut rng)).collect::<Vec<_>>();
+    let mut new = old.clone();
+    for _ in 0 .. (rng.next_u32() % 3000) {
+      let pos = usize::try_from(rng.next_u32()).unwrap() % (new.len() + 1);
+      if (rng.next_u32() < (u32::MAX / 2)) && (pos < new.len()) {
+        new.remove(pos);
+      } else {
+        new.insert(pos, random(&mut rng));
+      }
+    }
+    if !new.is_empty() {
+      test_diff(&old.iter().collect::<String>(), &new.iter().collect::<String>());
+    }
+  }
+}
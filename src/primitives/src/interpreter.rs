@@ -0,0 +1,521 @@
+use super::*;
+
+use core::ops::Range;
+
+use sp_std::{vec::Vec, collections::btree_map::BTreeMap};
+
+/// An error encountered while interpreting a script.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScriptError {
+  /// A referenced version couldn't be found.
+  UnknownReference(ArticleVersion),
+  /// A version referenced itself or a version which came after it.
+  InvalidReference(ArticleVersion),
+  /// An opcode which works on a reference was used before any `Reference` opcode.
+  MissingReference,
+  /// The script contains an opcode which can't be interpreted, such as `Title`.
+  UnexpectedOpCode,
+  /// The cursor was moved, or data was read, outside of the reference body.
+  CursorOutOfBounds,
+  /// The resulting body exceeds `MAX_BODY_LEN`, counted in the unit of the script.
+  BodyTooBig,
+  /// A script counting characters added, or referenced, data which isn't valid UTF-8.
+  InvalidUtf8,
+}
+
+fn to_usize(n: u32) -> usize {
+  usize::try_from(n).unwrap()
+}
+
+// Moves the cursor `n` positions to the right, making sure it stays within the reference.
+fn forward(cursor: usize, n: u32, len: usize) -> Result<usize, ScriptError> {
+  cursor.checked_add(to_usize(n)).filter(|end| *end <= len).ok_or(ScriptError::CursorOutOfBounds)
+}
+
+/// Returns the length of `data` in the given unit.
+pub fn unit_len(unit: CursorUnit, data: &[u8]) -> Result<usize, ScriptError> {
+  match unit {
+    CursorUnit::Byte => Ok(data.len()),
+    CursorUnit::Char => {
+      Ok(core::str::from_utf8(data).map_err(|_| ScriptError::InvalidUtf8)?.chars().count())
+    }
+  }
+}
+
+// A piece of the body produced by a script.
+#[derive(Clone)]
+enum Piece<'a> {
+  // Data added by the script itself.
+  Data(&'a [u8]),
+  // A range of the body of a reference version.
+  Copy(ArticleVersion, Range<usize>),
+}
+
+impl<'a> Piece<'a> {
+  fn len(&self) -> usize {
+    match self {
+      Piece::Data(data) => data.len(),
+      Piece::Copy(_, range) => range.len(),
+    }
+  }
+
+  // Splits the piece in two, `at` units into it.
+  fn split(&self, at: usize) -> (Piece<'a>, Piece<'a>) {
+    match self {
+      Piece::Data(data) => (Piece::Data(&data[.. at]), Piece::Data(&data[at ..])),
+      Piece::Copy(version, range) => (
+        Piece::Copy(*version, range.start .. (range.start + at)),
+        Piece::Copy(*version, (range.start + at) .. range.end),
+      ),
+    }
+  }
+}
+
+// Splits the pieces making up a body at a position within it, returning the index of the first
+// piece after the position.
+fn split(pieces: &mut Vec<Piece<'_>>, at: usize) -> usize {
+  let mut start = 0;
+  for i in 0 .. pieces.len() {
+    if at == start {
+      return i;
+    }
+    let len = pieces[i].len();
+    if at < (start + len) {
+      let (left, right) = pieces[i].split(at - start);
+      pieces[i] = left;
+      pieces.insert(i + 1, right);
+      return i + 1;
+    }
+    start += len;
+  }
+  pieces.len()
+}
+
+impl Script {
+  /// Returns the versions this script references, in the order they're referenced.
+  pub fn references(&self) -> impl Iterator<Item = ArticleVersion> + '_ {
+    self.data().iter().filter_map(|opcode| match opcode {
+      OpCode::Reference(version) => Some(*version),
+      _ => None,
+    })
+  }
+
+  // Interprets a script counting characters, which builds the new body from scratch (see
+  // `OpCode`), only needing the lengths of the referenced bodies. Every piece of the new body is
+  // passed to `write`, in order, and the length of the new body is returned.
+  //
+  // All lengths and ranges are in the unit of the script.
+  fn interpret<'a>(
+    &'a self,
+    reference_len: impl Fn(ArticleVersion) -> Result<usize, ScriptError>,
+    mut write: impl FnMut(Piece<'a>),
+  ) -> Result<usize, ScriptError> {
+    let mut len = 0;
+    // the current reference and the length of its body
+    let mut current = None;
+    let mut cursor = 0;
+    for opcode in self.data() {
+      match opcode {
+        OpCode::Title(_) => Err(ScriptError::UnexpectedOpCode)?,
+        OpCode::Reference(version) => {
+          current = Some((*version, reference_len(*version)?));
+          cursor = 0;
+        }
+        OpCode::Add(data) => {
+          len += unit_len(self.unit(), data.data())?;
+          write(Piece::Data(data.data()));
+        }
+        OpCode::Begin | OpCode::End | OpCode::MvCr(..) | OpCode::Del(_) | OpCode::Cp(_) => {
+          let (version, current_len) = current.ok_or(ScriptError::MissingReference)?;
+          match opcode {
+            OpCode::Begin => cursor = 0,
+            OpCode::End => cursor = current_len,
+            OpCode::MvCr(Direction::Right, n) | OpCode::Del(n) => {
+              cursor = forward(cursor, *n, current_len)?;
+            }
+            OpCode::MvCr(Direction::Left, n) => {
+              cursor = cursor.checked_sub(to_usize(*n)).ok_or(ScriptError::CursorOutOfBounds)?;
+            }
+            OpCode::Cp(n) => {
+              let end = forward(cursor, *n, current_len)?;
+              len += end - cursor;
+              write(Piece::Copy(version, cursor .. end));
+            }
+            OpCode::Title(_) | OpCode::Reference(_) | OpCode::Add(_) => unreachable!(),
+          }
+        }
+      }
+
+      if len > to_usize(MAX_BODY_LEN) {
+        Err(ScriptError::BodyTooBig)?;
+      }
+    }
+    Ok(len)
+  }
+
+  // Interprets a script counting bytes, which edits the bodies it references in place (see
+  // `OpCode`), only needing the lengths of the referenced bodies. Returns the pieces the new body
+  // is made of, in order.
+  fn edit_in_place(
+    &self,
+    reference_len: impl Fn(ArticleVersion) -> Result<usize, ScriptError>,
+  ) -> Result<Vec<Piece<'_>>, ScriptError> {
+    let mut pieces = Vec::new();
+    let mut len = 0;
+    // where the body of the current reference starts within the new body
+    let mut start = None;
+    let mut cursor = 0;
+    for opcode in self.data() {
+      match opcode {
+        OpCode::Title(_) => Err(ScriptError::UnexpectedOpCode)?,
+        OpCode::Reference(version) => {
+          let reference_len = reference_len(*version)?;
+          pieces.push(Piece::Copy(*version, 0 .. reference_len));
+          start = Some(len);
+          cursor = len;
+          len += reference_len;
+        }
+        OpCode::Add(data) => {
+          let at = split(&mut pieces, cursor);
+          pieces.insert(at, Piece::Data(data.data()));
+          cursor += data.data().len();
+          len += data.data().len();
+        }
+        OpCode::Begin | OpCode::End | OpCode::MvCr(..) | OpCode::Del(_) | OpCode::Cp(_) => {
+          let start = start.ok_or(ScriptError::MissingReference)?;
+          match opcode {
+            OpCode::Begin => cursor = start,
+            OpCode::End => cursor = len,
+            OpCode::MvCr(Direction::Right, n) => cursor = forward(cursor, *n, len)?,
+            OpCode::MvCr(Direction::Left, n) => {
+              cursor = cursor
+                .checked_sub(to_usize(*n))
+                .filter(|cursor| *cursor >= start)
+                .ok_or(ScriptError::CursorOutOfBounds)?;
+            }
+            OpCode::Del(n) => {
+              let end = forward(cursor, *n, len)?;
+              let (from, to) = (split(&mut pieces, cursor), split(&mut pieces, end));
+              pieces.drain(from .. to);
+              len -= end - cursor;
+            }
+            OpCode::Cp(n) => {
+              let end = forward(cursor, *n, len)?;
+              let (from, to) = (split(&mut pieces, cursor), split(&mut pieces, end));
+              let copy = pieces[from .. to].to_vec();
+              pieces.splice(from .. from, copy);
+              len += end - cursor;
+            }
+            OpCode::Title(_) | OpCode::Reference(_) | OpCode::Add(_) => unreachable!(),
+          }
+        }
+      }
+
+      if len > to_usize(MAX_BODY_LEN) {
+        Err(ScriptError::BodyTooBig)?;
+      }
+    }
+    Ok(pieces)
+  }
+
+  /// Applies the script, returning the body it describes.
+  ///
+  /// `reference` is called with every referenced version and should return the rendered body of
+  /// it.
+  pub fn apply<'a>(
+    &self,
+    reference: impl Fn(ArticleVersion) -> Option<&'a [u8]>,
+  ) -> Result<Vec<u8>, ScriptError> {
+    let reference_len = |version| {
+      unit_len(self.unit(), reference(version).ok_or(ScriptError::UnknownReference(version))?)
+    };
+    let mut body = Vec::new();
+    match self.unit() {
+      CursorUnit::Byte => {
+        for piece in self.edit_in_place(reference_len)? {
+          match piece {
+            Piece::Data(data) => body.extend_from_slice(data),
+            // the reference was already resolved while interpreting, so it's known to exist
+            Piece::Copy(version, range) => {
+              body.extend_from_slice(&reference(version).unwrap()[range])
+            }
+          }
+        }
+      }
+      CursorUnit::Char => {
+        // the positions of the characters of the last reference copied from
+        let mut chars: Option<(ArticleVersion, Vec<usize>)> = None;
+        self.interpret(reference_len, |piece| match piece {
+          Piece::Data(data) => body.extend_from_slice(data),
+          Piece::Copy(version, range) => {
+            // the reference was already resolved while interpreting, so it's known to exist, and
+            // to be valid UTF-8
+            let reference = core::str::from_utf8(reference(version).unwrap()).unwrap();
+            if chars.as_ref().map(|(chars_of, _)| *chars_of) != Some(version) {
+              let positions = reference.char_indices().map(|(i, _)| i);
+              chars = Some((version, positions.chain(core::iter::once(reference.len())).collect()));
+            }
+            let positions = &chars.as_ref().unwrap().1;
+            body.extend_from_slice(
+              &reference.as_bytes()[positions[range.start] .. positions[range.end]],
+            );
+          }
+        })?;
+      }
+    }
+    Ok(body)
+  }
+
+  /// Simulates the script without building the body, returning the length of the body it'd
+  /// produce.
+  ///
+  /// This performs the same checks as `apply` while only needing the lengths of the referenced
+  /// bodies, which `reference_len` should return in the unit of the script (see `unit_len`). The
+  /// returned length is in the unit of the script as well.
+  pub fn simulate(
+    &self,
+    reference_len: impl Fn(ArticleVersion) -> Option<usize>,
+  ) -> Result<usize, ScriptError> {
+    let reference_len =
+      |version| reference_len(version).ok_or(ScriptError::UnknownReference(version));
+    match self.unit() {
+      CursorUnit::Byte => Ok(self.edit_in_place(reference_len)?.iter().map(Piece::len).sum()),
+      CursorUnit::Char => self.interpret(reference_len, |_| {}),
+    }
+  }
+}
+
+/// Renders the body of `version`, resolving its chain of references through `scripts`.
+///
+/// `scripts` should return the script stored for the requested version of the article. Every
+/// version in the chain is rendered once, in ascending order, and dropped as soon as no later
+/// version needs it.
+pub fn render(
+  version: ArticleVersion,
+  scripts: impl Fn(ArticleVersion) -> Option<Script>,
+) -> Result<Vec<u8>, ScriptError> {
+  // collect the scripts of every version we need to render the requested one
+  let mut needed = BTreeMap::new();
+  // the last version which uses a given version as a reference
+  let mut last_use = BTreeMap::new();
+  let mut pending = Vec::new();
+  pending.push(version);
+  while let Some(current) = pending.pop() {
+    if needed.contains_key(&current.0) {
+      continue;
+    }
+
+    let script = scripts(current).ok_or(ScriptError::UnknownReference(current))?;
+    for reference in script.references() {
+      // versions can only be built on top of the prior versions
+      if reference.0 >= current.0 {
+        Err(ScriptError::InvalidReference(reference))?;
+      }
+
+      let last = last_use.entry(reference.0).or_insert(current.0);
+      *last = (*last).max(current.0);
+      pending.push(reference);
+    }
+    needed.insert(current.0, script);
+  }
+
+  // render them starting from the oldest, since that is the order they depend on each other
+  let mut bodies = BTreeMap::<u32, Vec<u8>>::new();
+  for (current, script) in needed {
+    let body = script.apply(|reference| bodies.get(&reference.0).map(Vec::as_slice))?;
+    bodies.retain(|reference, _| last_use.get(reference).is_some_and(|last| *last > current));
+    bodies.insert(current, body);
+  }
+  Ok(bodies.remove(&version.0).unwrap())
+}
+
+#[cfg(test)]
+fn test_scripts(scripts: Vec<Vec<OpCode>>) -> impl Fn(ArticleVersion) -> Option<Script> {
+  move |version| {
+    scripts.get(to_usize(version.0)).map(|opcodes| Script::new(opcodes.clone()).unwrap())
+  }
+}
+
+#[cfg(test)]
+fn test_body(data: &str) -> OpCode {
+  OpCode::Add(Body::new(data.as_bytes().to_vec()).unwrap())
+}
+
+#[test]
+fn test_render() {
+  let scripts = test_scripts(vec![
+    vec![test_body("hello world")],
+    // copy "hello " and replace "world"
+    vec![OpCode::Reference(ArticleVersion(0)), OpCode::Cp(6), test_body("there")],
+    // combine both prior versions
+    vec![
+      OpCode::Reference(ArticleVersion(1)),
+      OpCode::MvCr(Direction::Right, 6),
+      OpCode::Cp(5),
+      test_body(" and "),
+      OpCode::Reference(ArticleVersion(0)),
+      OpCode::End,
+      OpCode::MvCr(Direction::Left, 5),
+      OpCode::Cp(5),
+    ],
+    // deleting moves the cursor, copying doesn't
+    vec![
+      OpCode::Reference(ArticleVersion(2)),
+      OpCode::Cp(5),
+      OpCode::Del(10),
+      OpCode::Cp(5),
+      OpCode::Begin,
+      OpCode::Cp(1),
+    ],
+  ]);
+
+  assert_eq!(render(ArticleVersion(0), &scripts).unwrap(), b"hello world");
+  assert_eq!(render(ArticleVersion(1), &scripts).unwrap(), b"hello there");
+  assert_eq!(render(ArticleVersion(2), &scripts).unwrap(), b"there and world");
+  assert_eq!(render(ArticleVersion(3), &scripts).unwrap(), b"thereworldt");
+  assert_eq!(
+    render(ArticleVersion(4), &scripts),
+    Err(ScriptError::UnknownReference(ArticleVersion(4)))
+  );
+}
+
+#[test]
+fn test_render_errors() {
+  let render_single = |opcodes: Vec<OpCode>| {
+    render(ArticleVersion(1), test_scripts(vec![vec![test_body("hello")], opcodes]))
+  };
+
+  assert_eq!(render_single(vec![OpCode::Cp(1)]), Err(ScriptError::MissingReference));
+  assert_eq!(
+    render_single(vec![OpCode::Title(Title::new(b"title".to_vec()).unwrap())]),
+    Err(ScriptError::UnexpectedOpCode)
+  );
+  assert_eq!(
+    render_single(vec![OpCode::Reference(ArticleVersion(1))]),
+    Err(ScriptError::InvalidReference(ArticleVersion(1)))
+  );
+  assert_eq!(
+    render_single(vec![OpCode::Reference(ArticleVersion(0)), OpCode::Cp(6)]),
+    Err(ScriptError::CursorOutOfBounds)
+  );
+  assert_eq!(
+    render_single(vec![OpCode::Reference(ArticleVersion(0)), OpCode::MvCr(Direction::Left, 1)]),
+    Err(ScriptError::CursorOutOfBounds)
+  );
+  assert_eq!(
+    render_single(vec![OpCode::Reference(ArticleVersion(0)), OpCode::End, OpCode::Del(1)]),
+    Err(ScriptError::CursorOutOfBounds)
+  );
+}
+
+#[test]
+fn test_simulate() {
+  let body = b"hello world";
+  let reference = |version: ArticleVersion| (version == ArticleVersion(0)).then_some(&body[..]);
+  let reference_len =
+    |version| reference(version).map(|body| unit_len(CursorUnit::Char, body).unwrap());
+
+  let scripts = [
+    vec![test_body("hi")],
+    vec![OpCode::Reference(ArticleVersion(0)), OpCode::Del(6), OpCode::Cp(5), test_body("!")],
+    vec![OpCode::Reference(ArticleVersion(0)), OpCode::End, OpCode::MvCr(Direction::Right, 1)],
+    vec![OpCode::Reference(ArticleVersion(0)), OpCode::Cp(12)],
+    vec![OpCode::Reference(ArticleVersion(1))],
+    vec![OpCode::Begin],
+  ];
+  for opcodes in scripts {
+    for unit in [CursorUnit::Byte, CursorUnit::Char] {
+      let script = Script::with_unit(unit, opcodes.clone()).unwrap();
+      // simulating should agree with actually applying the script
+      assert_eq!(script.simulate(reference_len), script.apply(reference).map(|body| body.len()));
+    }
+  }
+}
+
+#[test]
+fn test_edit_in_place() {
+  let body = b"hello world";
+  let reference = |version: ArticleVersion| (version == ArticleVersion(0)).then_some(&body[..]);
+  let apply = |opcodes| Script::with_unit(CursorUnit::Byte, opcodes).unwrap().apply(reference);
+  let v0 = || OpCode::Reference(ArticleVersion(0));
+
+  // adding inserts the data at the cursor, moving the cursor to its end
+  assert_eq!(
+    apply(vec![v0(), OpCode::MvCr(Direction::Right, 5), test_body(","), test_body(" there")])
+      .unwrap(),
+    b"hello, there world"
+  );
+  // deleting leaves the cursor where the deleted data was
+  assert_eq!(apply(vec![v0(), OpCode::Del(6), test_body("goodbye ")]).unwrap(), b"goodbye world");
+  // copying inserts the copy at the cursor, leaving the cursor before it
+  assert_eq!(
+    apply(vec![v0(), OpCode::Cp(6), OpCode::End, test_body("!")]).unwrap(),
+    b"hello hello world!"
+  );
+  // every reference appends its body, which the cursor can't leave
+  assert_eq!(
+    apply(vec![test_body("> "), v0(), OpCode::End, v0(), OpCode::Del(6)]).unwrap(),
+    b"> hello worldworld"
+  );
+  assert_eq!(
+    apply(vec![v0(), v0(), OpCode::MvCr(Direction::Left, 1)]),
+    Err(ScriptError::CursorOutOfBounds)
+  );
+  assert_eq!(apply(vec![v0(), OpCode::End, OpCode::Del(1)]), Err(ScriptError::CursorOutOfBounds));
+}
+
+#[test]
+fn test_cursor_units() {
+  let body = "héllo 世界".as_bytes();
+  let reference = |version: ArticleVersion| (version == ArticleVersion(0)).then_some(body);
+  let apply = |unit, opcodes| Script::with_unit(unit, opcodes).unwrap().apply(reference);
+
+  // characters are never split
+  let opcodes =
+    vec![OpCode::Reference(ArticleVersion(0)), OpCode::Cp(2), OpCode::Del(6), OpCode::Cp(2)];
+  assert_eq!(apply(CursorUnit::Char, opcodes).unwrap(), "hé世界".as_bytes());
+  // while bytes may split them
+  let opcodes = vec![
+    OpCode::Reference(ArticleVersion(0)),
+    OpCode::MvCr(Direction::Right, 2),
+    OpCode::Del(6),
+    test_body("x"),
+  ];
+  assert_eq!(
+    apply(CursorUnit::Byte, opcodes).unwrap(),
+    [b"h\xC3x", &"世界".as_bytes()[1 ..]].concat()
+  );
+
+  let opcodes = vec![
+    OpCode::Reference(ArticleVersion(0)),
+    OpCode::End,
+    OpCode::MvCr(Direction::Left, 2),
+    OpCode::Cp(2),
+  ];
+  assert_eq!(apply(CursorUnit::Char, opcodes).unwrap(), "世界".as_bytes());
+  let opcodes = vec![OpCode::Reference(ArticleVersion(0)), OpCode::Del(8), OpCode::Cp(1)];
+  assert_eq!(apply(CursorUnit::Char, opcodes), Err(ScriptError::CursorOutOfBounds));
+
+  // added data has to be valid UTF-8 when counting characters
+  let invalid = vec![OpCode::Add(Body::new(vec![0xC3]).unwrap())];
+  assert_eq!(apply(CursorUnit::Char, invalid.clone()), Err(ScriptError::InvalidUtf8));
+  assert_eq!(apply(CursorUnit::Byte, invalid).unwrap(), [0xC3]);
+}
+
+#[test]
+fn test_full_text() {
+  let apply = |script: &Script| script.apply(|_| None).unwrap();
+
+  // a body too many bytes long for a single `Add`, which has to be split within "世界"
+  let text = format!("{}{}", "a".repeat(to_usize(MAX_BODY_LEN) - 100), "世界".repeat(40));
+  let script = Script::full_text(text.as_bytes()).unwrap();
+  assert_eq!(script.unit(), CursorUnit::Char);
+  assert_eq!(script.data().len(), 2);
+  assert_eq!(script.references().count(), 0);
+  assert_eq!(apply(&script), text.as_bytes());
+
+  // bodies which aren't UTF-8 are added byte by byte
+  let script = Script::full_text(&[b'a', 0xC3]).unwrap();
+  assert_eq!(script.unit(), CursorUnit::Byte);
+  assert_eq!(apply(&script), [b'a', 0xC3]);
+}
@@ -19,6 +19,7 @@ use sp_io::TestExternalities;
 #[cfg(test)]
 use frame_support::{pallet_prelude::*, Identity, traits::StorageInstance};
 
+use sp_std::vec::Vec;
 use sp_core::{ConstU32, bounded::BoundedVec};
 pub use sp_application_crypto as crypto;
 
@@ -34,9 +35,28 @@ pub use account::*;
 mod constants;
 pub use constants::*;
 
+mod title;
+
 mod script;
 pub use script::*;
 
+mod interpreter;
+pub use interpreter::*;
+
+mod rebase;
+
+mod version;
+pub use version::*;
+
+mod comment;
+pub use comment::*;
+
+mod vote;
+pub use vote::*;
+
+#[cfg(feature = "std")]
+mod diff;
+
 pub type BlockNumber = u64;
 pub type Header = sp_runtime::generic::Header<BlockNumber, sp_runtime::traits::BlakeTwo256>;
 
@@ -106,7 +126,6 @@ impl Zeroize for Title {
 }
 
 impl Title {
-  #[cfg(feature = "std")]
   pub fn new(data: Vec<u8>) -> Result<Title, &'static str> {
     Ok(Title(data.try_into().map_err(|_| "title length exceeds {MAX_TITLE_LEN}")?))
   }
@@ -154,7 +173,6 @@ impl Zeroize for Body {
 }
 
 impl Body {
-  #[cfg(feature = "std")]
   pub fn new(data: Vec<u8>) -> Result<Body, &'static str> {
     Ok(Body(data.try_into().map_err(|_| "body length exceeds {MAX_BODY_LEN}")?))
   }
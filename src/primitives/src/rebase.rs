@@ -0,0 +1,218 @@
+use super::*;
+
+use sp_std::{vec, vec::Vec};
+
+// A change a script makes to the body it references, replacing the range `start .. end` of it
+// (which may be empty) with the data it adds. Positions are counted in the unit of the script.
+struct Edit<'a> {
+  start: usize,
+  end: usize,
+  data: Vec<&'a Body>,
+}
+
+impl Script {
+  // Describes the script as the edits it makes to the body of `base`, which is `base_len` long.
+  //
+  // Returns None if the script references any other version, or doesn't copy the parts of the
+  // body it keeps in order, such as when it moves a paragraph around.
+  fn edits(&self, base: ArticleVersion, base_len: usize) -> Option<Vec<Edit<'_>>> {
+    let mut edits = Vec::new();
+    // the position right after the last part of the body kept
+    let mut kept = 0;
+    let mut data = Vec::new();
+    let mut cursor = None;
+    for opcode in self.data() {
+      match opcode {
+        OpCode::Title(_) => None?,
+        OpCode::Reference(version) => {
+          if *version != base {
+            None?;
+          }
+          cursor = Some(0);
+        }
+        OpCode::Add(body) => data.push(body),
+        OpCode::Begin => *cursor.as_mut()? = 0,
+        OpCode::End => *cursor.as_mut()? = base_len,
+        OpCode::MvCr(Direction::Right, n) | OpCode::Del(n) => {
+          let cursor = cursor.as_mut()?;
+          *cursor = cursor.checked_add(usize::try_from(*n).ok()?).filter(|c| *c <= base_len)?;
+        }
+        OpCode::MvCr(Direction::Left, n) => {
+          let cursor = cursor.as_mut()?;
+          *cursor = cursor.checked_sub(usize::try_from(*n).ok()?)?;
+        }
+        OpCode::Cp(n) => {
+          let cursor = cursor?;
+          let end = cursor.checked_add(usize::try_from(*n).ok()?).filter(|end| *end <= base_len)?;
+          if *n == 0 {
+            continue;
+          }
+          if cursor < kept {
+            None?;
+          }
+          if (cursor > kept) || !data.is_empty() {
+            edits.push(Edit { start: kept, end: cursor, data: core::mem::take(&mut data) });
+          }
+          kept = end;
+        }
+      }
+    }
+    if (kept < base_len) || !data.is_empty() {
+      edits.push(Edit { start: kept, end: base_len, data });
+    }
+    Some(edits)
+  }
+
+  /// Rebases this script, written on top of the `base` version, onto the `onto` version, which
+  /// was written on top of the same base by `onto_script`.
+  ///
+  /// Both scripts are read as the ranges of the base body they replace, and the rebased script
+  /// makes the same replacements to the body of `onto`. This requires every replacement of one
+  /// script to be apart from those of the other, with at least a unit of untouched body between.
+  /// Returns None when they overlap, when either script does more than edit the base body in
+  /// order, or when the lengths given don't match the scripts.
+  ///
+  /// `base_len` and `onto_len` are the lengths of the bodies, in characters, as only scripts
+  /// counting characters can be rebased.
+  pub fn rebase(
+    &self,
+    base: ArticleVersion,
+    base_len: usize,
+    onto_script: &Script,
+    onto: ArticleVersion,
+    onto_len: usize,
+  ) -> Option<Script> {
+    // scripts counting bytes edit their references in place, which isn't read as replacements
+    if (self.unit() != CursorUnit::Char) || (onto_script.unit() != CursorUnit::Char) {
+      None?;
+    }
+    let ours = self.edits(base, base_len)?;
+    let theirs = onto_script.edits(base, base_len)?;
+
+    let mut opcodes = vec![OpCode::Reference(onto)];
+    // the position in the body of `onto`
+    let mut cursor = 0;
+    // what the edits of `onto` before the current position removed and inserted
+    let (mut removed, mut inserted) = (0, 0);
+    let mut theirs = theirs.iter().peekable();
+    for edit in ours {
+      while let Some(their) = theirs.next_if(|their| their.end < edit.start) {
+        removed += their.end - their.start;
+        for body in &their.data {
+          inserted += unit_len(self.unit(), body.data()).ok()?;
+        }
+      }
+      if theirs.peek().is_some_and(|their| their.start <= edit.end) {
+        None?;
+      }
+
+      let start = (edit.start - removed).checked_add(inserted)?;
+      let end = (edit.end - removed).checked_add(inserted)?;
+      if start > cursor {
+        let n = u32::try_from(start - cursor).ok()?;
+        opcodes.extend([OpCode::Cp(n), OpCode::MvCr(Direction::Right, n)]);
+      }
+      opcodes.extend(edit.data.into_iter().cloned().map(OpCode::Add));
+      if end > start {
+        opcodes.push(OpCode::Del(u32::try_from(end - start).ok()?));
+      }
+      cursor = end;
+    }
+    if cursor > onto_len {
+      None?;
+    }
+    if onto_len > cursor {
+      opcodes.push(OpCode::Cp(u32::try_from(onto_len - cursor).ok()?));
+    }
+
+    Script::with_unit(self.unit(), opcodes).ok()
+  }
+}
+
+#[cfg(test)]
+fn test_edit(opcodes: Vec<OpCode>) -> Script {
+  Script::new(opcodes).unwrap()
+}
+
+#[cfg(test)]
+fn test_add(data: &str) -> OpCode {
+  OpCode::Add(Body::new(data.as_bytes().to_vec()).unwrap())
+}
+
+#[test]
+fn test_rebase() {
+  let base = "the quick brown fox";
+  let v0 = test_edit(vec![test_add(base)]);
+  // replaces "quick" with "slow"
+  let v1 = test_edit(vec![
+    OpCode::Reference(ArticleVersion(0)),
+    OpCode::Cp(4),
+    OpCode::MvCr(Direction::Right, 4),
+    test_add("slow"),
+    OpCode::Del(5),
+    OpCode::Cp(10),
+  ]);
+  // replaces "fox" with "dog", written on top of the base as well
+  let ours = test_edit(vec![
+    OpCode::Reference(ArticleVersion(0)),
+    OpCode::Cp(16),
+    OpCode::End,
+    test_add("dog"),
+  ]);
+
+  let rebased = ours.rebase(ArticleVersion(0), 19, &v1, ArticleVersion(1), 18).unwrap();
+  let scripts = [v0, v1, rebased];
+  let body =
+    render(ArticleVersion(2), |version| scripts.get(usize::try_from(version.0).unwrap()).cloned())
+      .unwrap();
+  assert_eq!(body, b"the slow brown dog");
+}
+
+#[test]
+fn test_rebase_conflicts() {
+  let base = ArticleVersion(0);
+  // replaces "brown" in "the quick brown fox"
+  let theirs = test_edit(vec![
+    OpCode::Reference(base),
+    OpCode::Cp(10),
+    OpCode::MvCr(Direction::Right, 10),
+    test_add("red"),
+    OpCode::Del(5),
+    OpCode::Cp(4),
+  ]);
+  let onto = ArticleVersion(1);
+
+  // replacing "brown" too
+  let ours =
+    test_edit(vec![OpCode::Reference(base), OpCode::Cp(10), test_add("grey"), OpCode::End]);
+  assert!(ours.rebase(base, 19, &theirs, onto, 17).is_none());
+
+  // appending right after it, without any untouched body between
+  let ours = test_edit(vec![
+    OpCode::Reference(base),
+    OpCode::Cp(15),
+    test_add(" old"),
+    OpCode::MvCr(Direction::Right, 15),
+    OpCode::Cp(4),
+  ]);
+  assert!(ours.rebase(base, 19, &theirs, onto, 17).is_none());
+
+  // moving the words around
+  let ours = test_edit(vec![
+    OpCode::Reference(base),
+    OpCode::MvCr(Direction::Right, 16),
+    OpCode::Cp(3),
+    OpCode::Begin,
+    OpCode::Cp(16),
+  ]);
+  assert!(ours.rebase(base, 19, &theirs, onto, 17).is_none());
+
+  // written on top of another version
+  let ours = test_edit(vec![OpCode::Reference(ArticleVersion(1)), OpCode::Cp(1)]);
+  assert!(ours.rebase(base, 19, &theirs, onto, 17).is_none());
+
+  // an edit far enough away rebases
+  let ours =
+    test_edit(vec![OpCode::Reference(base), test_add("A"), OpCode::Del(1), OpCode::Cp(18)]);
+  assert!(ours.rebase(base, 19, &theirs, onto, 17).is_some());
+}
@@ -35,6 +35,26 @@ pub enum OpCode {
   Cp(u32),
 }
 // NOTE: Default cursor position is the beginning of the body.
+//
+// How the opcodes make the new version depends on the `CursorUnit` of the script. Scripts counting
+// bytes, written before cursors were character-aware, edit the bodies they reference in place, as
+// described above:
+// - every `Reference` appends the referenced body to the new version, and the cursor is placed at
+//   its beginning. The opcodes until the next `Reference` edit that body, and the cursor can't be
+//   moved before it.
+// - `Add` inserts the data at the cursor, `Del` removes the data right of the cursor and `Cp`
+//   inserts a copy of the data right of the cursor at the cursor, leaving the copy to its right.
+//
+// Scripts counting characters build the new version from scratch, left to right, instead:
+// - the cursor only points into the body of the current reference, and it's placed at the
+//   beginning of that body by every `Reference`. It can't be moved outside of it.
+// - `Add` appends the data to the new version, `Del` moves the cursor past the data right of it,
+//   leaving it out of the new version, and `Cp` appends the data right of the cursor to the new
+//   version. The cursor doesn't move when adding or copying.
+//
+// This is a breaking change to the meaning of `Add`, `Del` and `Cp` for scripts counting
+// characters, which couldn't be added before. The numbers `MvCr`, `Del` and `Cp` take count in the
+// `CursorUnit` of the script.
 
 impl OpCode {
   pub fn requires_reference(&self) -> bool {
@@ -42,11 +62,25 @@ impl OpCode {
   }
 }
 
+/// The unit the cursor of a script counts in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CursorUnit {
+  // Counts bytes. This is only kept for the scripts written before cursors were character-aware,
+  // as it allows splitting a UTF-8 encoded character in two, and these scripts edit their
+  // references in place.
+  Byte,
+  // Counts Unicode scalar values, the `char`s of a UTF-8 encoded body.
+  Char,
+}
+
 pub const MAX_SCRIPT_LEN: u32 = 1_000;
 #[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
 #[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Script(
+pub struct Script {
+  unit: CursorUnit,
   #[cfg_attr(
     feature = "borsh",
     borsh(
@@ -54,25 +88,63 @@ pub struct Script(
       deserialize_with = "borsh_deserialize_bounded_vec"
     )
   )]
-  BoundedVec<OpCode, ConstU32<{ MAX_SCRIPT_LEN }>>,
-);
+  data: BoundedVec<OpCode, ConstU32<{ MAX_SCRIPT_LEN }>>,
+}
 
 impl Script {
+  /// Creates a new script, counting characters.
   pub fn new(data: Vec<OpCode>) -> Result<Script, &'static str> {
-    Ok(Script(data.try_into().map_err(|_| "Script length exceeds {MAX_SCRIPT_LEN}")?))
+    Self::with_unit(CursorUnit::Char, data)
+  }
+
+  pub fn with_unit(unit: CursorUnit, data: Vec<OpCode>) -> Result<Script, &'static str> {
+    Ok(Script {
+      unit,
+      data: data.try_into().map_err(|_| "Script length exceeds {MAX_SCRIPT_LEN}")?,
+    })
+  }
+
+  /// Creates a script adding the entire body, without referencing any other version.
+  ///
+  /// The script counts characters if the body is valid UTF-8, and bytes otherwise.
+  pub fn full_text(body: &[u8]) -> Result<Script, &'static str> {
+    let text = core::str::from_utf8(body).ok();
+    let max = usize::try_from(MAX_BODY_LEN).unwrap();
+
+    let mut opcodes = Vec::new();
+    let mut rest = body;
+    while !rest.is_empty() {
+      let mut len = rest.len().min(max);
+      // don't split a character in two
+      if let Some(text) = text {
+        let start = body.len() - rest.len();
+        while !text.is_char_boundary(start + len) {
+          len -= 1;
+        }
+      }
+      let (data, remaining) = rest.split_at(len);
+      opcodes.push(OpCode::Add(Body::new(data.to_vec())?));
+      rest = remaining;
+    }
+
+    Self::with_unit(if text.is_some() { CursorUnit::Char } else { CursorUnit::Byte }, opcodes)
+  }
+
+  pub fn unit(&self) -> CursorUnit {
+    self.unit
   }
 
   pub fn data(&self) -> &[OpCode] {
-    self.0.as_ref()
+    self.data.as_ref()
   }
 
   pub fn consume(self) -> Vec<OpCode> {
-    self.0.into_inner()
+    self.data.into_inner()
   }
 }
 
 impl AsRef<[OpCode]> for Script {
   fn as_ref(&self) -> &[OpCode] {
-    self.0.as_ref()
+    self.data.as_ref()
   }
 }
@@ -0,0 +1,89 @@
+use super::*;
+
+use sp_std::vec::Vec;
+
+use unicode_normalization::UnicodeNormalization;
+
+fn encode(chars: impl Iterator<Item = char>) -> Vec<u8> {
+  let mut data = Vec::new();
+  for c in chars {
+    data.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+  }
+  data
+}
+
+impl Title {
+  /// Returns the canonical form of the title, which articles are stored under.
+  ///
+  /// The canonical form is NFC normalized, has its leading and trailing whitespace trimmed, every
+  /// other run of whitespace replaced with a single space, and its first character lowercased.
+  /// This makes "Paris", "paris" and " Paris " the same title.
+  pub fn canonical(&self) -> Result<Title, &'static str> {
+    let text = core::str::from_utf8(self.data()).map_err(|_| "title isn't valid UTF-8")?;
+    let normalized = text.nfc().collect::<Vec<_>>();
+
+    let mut chars = Vec::with_capacity(normalized.len());
+    for word in normalized.split(|c| c.is_whitespace()).filter(|word| !word.is_empty()) {
+      if chars.is_empty() {
+        chars.extend(word[0].to_lowercase());
+        chars.extend_from_slice(&word[1 ..]);
+      } else {
+        chars.push(' ');
+        chars.extend_from_slice(word);
+      }
+    }
+    if chars.is_empty() {
+      Err("title can't be empty")?;
+    }
+
+    // lowercasing may have decomposed the first character
+    let data = encode(chars.into_iter().nfc());
+    Ok(Title(data.try_into().map_err(|_| "title length exceeds {MAX_TITLE_LEN}")?))
+  }
+
+  /// Returns the confusable skeleton of the title, as defined by Unicode Technical Standard #39.
+  ///
+  /// Titles which look alike, such as "Paris" and "Pаris" (with a Cyrillic "а"), share the same
+  /// skeleton. Returns None if the title isn't valid UTF-8.
+  pub fn skeleton(&self) -> Option<Vec<u8>> {
+    let text = core::str::from_utf8(self.data()).ok()?;
+    Some(encode(unicode_security::skeleton(text)))
+  }
+}
+
+#[cfg(test)]
+fn test_title(title: &str) -> Title {
+  Title::new(title.as_bytes().to_vec()).unwrap()
+}
+
+#[test]
+fn test_canonical_title() {
+  let canonical = test_title("paris");
+  for title in ["Paris", "paris", " Paris ", "Paris\n", "\tparis"] {
+    assert_eq!(test_title(title).canonical().unwrap(), canonical);
+  }
+  assert_eq!(test_title("New   York City").canonical().unwrap(), test_title("new York City"));
+
+  // only the first character is case folded
+  assert_ne!(test_title("PARIS").canonical().unwrap(), canonical);
+
+  // "é" as a single character and as an "e" followed by a combining accent
+  assert_eq!(
+    test_title("Caf\u{e9}").canonical().unwrap(),
+    test_title("cafe\u{301}").canonical().unwrap()
+  );
+  assert_eq!(test_title("Éclair").canonical().unwrap(), test_title("\u{e9}clair"));
+
+  assert!(test_title("   ").canonical().is_err());
+  assert!(Title::new(vec![0xff]).unwrap().canonical().is_err());
+}
+
+#[test]
+fn test_title_skeleton() {
+  let skeleton = |title: &str| test_title(title).canonical().unwrap().skeleton().unwrap();
+
+  // a Cyrillic "а" looks the same as a Latin "a"
+  assert_eq!(skeleton("P\u{430}ris"), skeleton("Paris"));
+  assert_ne!(skeleton("Paris"), skeleton("Rome"));
+  assert_eq!(Title::new(vec![0xff]).unwrap().skeleton(), None);
+}
@@ -0,0 +1,113 @@
+#[cfg(feature = "std")]
+use zeroize::Zeroize;
+
+#[cfg(feature = "borsh")]
+use borsh::{BorshSerialize, BorshDeserialize};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use scale::{Encode, Decode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+use sp_core::{ConstU32, bounded::BoundedVec};
+use sp_std::vec::Vec;
+
+#[cfg(feature = "borsh")]
+use crate::{borsh_serialize_bounded_vec, borsh_deserialize_bounded_vec};
+use crate::{ArticleVersion, BlockNumber, Script, WikiblocksAddress, MINUTES};
+
+// A line or two explaining an edit
+pub const MAX_SUMMARY_LEN: u32 = 500;
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EditSummary(
+  #[cfg_attr(
+    feature = "borsh",
+    borsh(
+      serialize_with = "borsh_serialize_bounded_vec",
+      deserialize_with = "borsh_deserialize_bounded_vec"
+    )
+  )]
+  BoundedVec<u8, ConstU32<{ MAX_SUMMARY_LEN }>>,
+);
+
+#[cfg(feature = "std")]
+impl Zeroize for EditSummary {
+  fn zeroize(&mut self) {
+    self.0.as_mut().zeroize()
+  }
+}
+
+impl EditSummary {
+  pub fn new(data: Vec<u8>) -> Result<EditSummary, &'static str> {
+    Ok(EditSummary(data.try_into().map_err(|_| "summary length exceeds {MAX_SUMMARY_LEN}")?))
+  }
+
+  pub fn data(&self) -> &[u8] {
+    self.0.as_ref()
+  }
+
+  #[cfg(feature = "std")]
+  pub fn consume(self) -> Vec<u8> {
+    self.0.into_inner()
+  }
+}
+
+impl AsRef<[u8]> for EditSummary {
+  fn as_ref(&self) -> &[u8] {
+    self.0.as_ref()
+  }
+}
+
+/// The record of how a version of an article came to be.
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VersionInfo {
+  pub author: WikiblocksAddress,
+  /// The block the version was added in.
+  pub block: BlockNumber,
+  /// The time of the block the version was added in, in milliseconds since the Unix epoch.
+  pub time: u64,
+  /// The first version the script of the version references, which the edit was made on top of.
+  pub parent: Option<ArticleVersion>,
+  /// The length of the rendered body of the version, in characters.
+  pub size: u32,
+  pub summary: Option<EditSummary>,
+}
+
+/// Limits on how often articles may be edited, keeping any single account or title from flooding
+/// the chain with versions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RateLimits {
+  /// The most versions a title may get within a window of `title_window` blocks.
+  pub versions_per_title: u32,
+  /// The amount of blocks the versions of a title are counted over, which can't be zero.
+  pub title_window: BlockNumber,
+  /// The most versions an account may add within a session.
+  pub edits_per_session: u32,
+}
+
+impl Default for RateLimits {
+  fn default() -> Self {
+    RateLimits { versions_per_title: 32, title_window: 10 * MINUTES, edits_per_session: 64 }
+  }
+}
+
+/// A version added to a protected article, waiting for a reviewer to accept it.
+#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PendingVersion {
+  pub author: WikiblocksAddress,
+  /// The version the script was written on top of, which it's rebased from if it's accepted
+  /// after other versions were.
+  pub base: ArticleVersion,
+  pub script: Script,
+  pub summary: Option<EditSummary>,
+  /// The block the version was submitted in.
+  pub block: BlockNumber,
+}
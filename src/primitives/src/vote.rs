@@ -0,0 +1,66 @@
+#[cfg(feature = "borsh")]
+use borsh::{BorshSerialize, BorshDeserialize};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use scale::{Encode, Decode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+use crate::{BlockNumber, SubstrateAmount, VOTE_LOCK_PERIOD, VOTE_WEIGHT_UNIT};
+
+/// The vote of an account on an article version.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Vote {
+  Up,
+  Down,
+}
+
+/// How long the coins backing a vote are held for. Every step doubles the period, and adds the
+/// amount to the weight of the vote once more.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Conviction {
+  Locked1x,
+  Locked2x,
+  Locked3x,
+  Locked4x,
+  Locked5x,
+  Locked6x,
+}
+
+impl Conviction {
+  pub fn multiplier(self) -> u64 {
+    match self {
+      Conviction::Locked1x => 1,
+      Conviction::Locked2x => 2,
+      Conviction::Locked3x => 3,
+      Conviction::Locked4x => 4,
+      Conviction::Locked5x => 5,
+      Conviction::Locked6x => 6,
+    }
+  }
+
+  /// The blocks the coins backing a vote are held for.
+  pub fn lock_period(self) -> BlockNumber {
+    VOTE_LOCK_PERIOD << (self.multiplier() - 1)
+  }
+
+  /// The weight of a vote backed by `amount`, counted in whole coins. Votes weigh at least as
+  /// much as votes without coins.
+  pub fn weight(self, amount: SubstrateAmount) -> u64 {
+    (amount / VOTE_WEIGHT_UNIT).saturating_mul(self.multiplier()).max(1)
+  }
+}
+
+#[test]
+fn conviction() {
+  assert_eq!(Conviction::Locked1x.lock_period(), VOTE_LOCK_PERIOD);
+  assert_eq!(Conviction::Locked6x.lock_period(), 32 * VOTE_LOCK_PERIOD);
+
+  assert_eq!(Conviction::Locked1x.weight(0), 1);
+  assert_eq!(Conviction::Locked3x.weight(VOTE_WEIGHT_UNIT - 1), 1);
+  assert_eq!(Conviction::Locked3x.weight(10 * VOTE_WEIGHT_UNIT), 30);
+}
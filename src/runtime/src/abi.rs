@@ -5,7 +5,7 @@ use scale::{Encode, Decode};
 use wikiblocks_abi::Call;
 
 use crate::{
-  timestamp, coins, articles, votes,
+  timestamp, coins, emissions, articles, votes, discussions,
   validator_sets::{self, MembershipProof},
   babe, grandpa, RuntimeCall,
 };
@@ -35,18 +35,68 @@ impl From<Call> for RuntimeCall {
           RuntimeCall::ValidatorSets(validator_sets::Call::claim_deallocation { session })
         }
       },
+      Call::Emissions(emissions) => match emissions {
+        wikiblocks_abi::emissions::Call::claim_rewards => {
+          RuntimeCall::Emissions(emissions::Call::claim_rewards {})
+        }
+      },
       Call::Articles(articles) => match articles {
-        wikiblocks_abi::articles::Call::add_article { title, script } => {
-          RuntimeCall::Articles(articles::Call::add_article { title, script })
+        wikiblocks_abi::articles::Call::add_article { title, script, summary } => {
+          RuntimeCall::Articles(articles::Call::add_article { title, script, summary })
+        }
+        wikiblocks_abi::articles::Call::add_version {
+          title,
+          script,
+          summary,
+          expected_last_version,
+          rebase,
+        } => RuntimeCall::Articles(articles::Call::add_version {
+          title,
+          script,
+          summary,
+          expected_last_version,
+          rebase,
+        }),
+        wikiblocks_abi::articles::Call::add_redirect { from, to } => {
+          RuntimeCall::Articles(articles::Call::add_redirect { from, to })
+        }
+        wikiblocks_abi::articles::Call::move_article { from, to, versions } => {
+          RuntimeCall::Articles(articles::Call::move_article { from, to, versions })
+        }
+        wikiblocks_abi::articles::Call::tag { title, category } => {
+          RuntimeCall::Articles(articles::Call::tag { title, category })
+        }
+        wikiblocks_abi::articles::Call::untag { title, category } => {
+          RuntimeCall::Articles(articles::Call::untag { title, category })
+        }
+        wikiblocks_abi::articles::Call::revert { title, to_version } => {
+          RuntimeCall::Articles(articles::Call::revert { title, to_version })
         }
-        wikiblocks_abi::articles::Call::add_version { title, script } => {
-          RuntimeCall::Articles(articles::Call::add_version { title, script })
+        wikiblocks_abi::articles::Call::accept_version { title, id } => {
+          RuntimeCall::Articles(articles::Call::accept_version { title, id })
+        }
+        wikiblocks_abi::articles::Call::reject_version { title, id } => {
+          RuntimeCall::Articles(articles::Call::reject_version { title, id })
         }
       },
       Call::Votes(votes) => match votes {
         wikiblocks_abi::votes::Call::upvote { article } => {
           RuntimeCall::Votes(votes::Call::upvote { article })
         }
+        wikiblocks_abi::votes::Call::retract_vote { article } => {
+          RuntimeCall::Votes(votes::Call::retract_vote { article })
+        }
+        wikiblocks_abi::votes::Call::downvote { article } => {
+          RuntimeCall::Votes(votes::Call::downvote { article })
+        }
+        wikiblocks_abi::votes::Call::vote_with_stake { article, vote, amount, conviction } => {
+          RuntimeCall::Votes(votes::Call::vote_with_stake { article, vote, amount, conviction })
+        }
+      },
+      Call::Discussions(discussions) => match discussions {
+        wikiblocks_abi::discussions::Call::post_comment { title, version, parent, body } => {
+          RuntimeCall::Discussions(discussions::Call::post_comment { title, version, parent, body })
+        }
       },
       Call::Babe(babe) => match babe {
         wikiblocks_abi::babe::Call::report_equivocation(report) => {
@@ -115,17 +165,63 @@ impl TryInto<Call> for RuntimeCall {
         }
         _ => Err(())?,
       }),
+      RuntimeCall::Emissions(call) => Call::Emissions(match call {
+        emissions::Call::claim_rewards {} => wikiblocks_abi::emissions::Call::claim_rewards,
+        _ => Err(())?,
+      }),
       RuntimeCall::Articles(call) => Call::Articles(match call {
-        articles::Call::add_article { title, script } => {
-          wikiblocks_abi::articles::Call::add_article { title, script }
+        articles::Call::add_article { title, script, summary } => {
+          wikiblocks_abi::articles::Call::add_article { title, script, summary }
+        }
+        articles::Call::add_version { title, script, summary, expected_last_version, rebase } => {
+          wikiblocks_abi::articles::Call::add_version {
+            title,
+            script,
+            summary,
+            expected_last_version,
+            rebase,
+          }
+        }
+        articles::Call::add_redirect { from, to } => {
+          wikiblocks_abi::articles::Call::add_redirect { from, to }
+        }
+        articles::Call::move_article { from, to, versions } => {
+          wikiblocks_abi::articles::Call::move_article { from, to, versions }
+        }
+        articles::Call::tag { title, category } => {
+          wikiblocks_abi::articles::Call::tag { title, category }
+        }
+        articles::Call::untag { title, category } => {
+          wikiblocks_abi::articles::Call::untag { title, category }
+        }
+        articles::Call::revert { title, to_version } => {
+          wikiblocks_abi::articles::Call::revert { title, to_version }
         }
-        articles::Call::add_version { title, script } => {
-          wikiblocks_abi::articles::Call::add_version { title, script }
+        articles::Call::accept_version { title, id } => {
+          wikiblocks_abi::articles::Call::accept_version { title, id }
+        }
+        articles::Call::reject_version { title, id } => {
+          wikiblocks_abi::articles::Call::reject_version { title, id }
         }
         _ => Err(())?,
       }),
       RuntimeCall::Votes(call) => Call::Votes(match call {
         votes_pallet::Call::upvote { article } => wikiblocks_abi::votes::Call::upvote { article },
+        votes_pallet::Call::retract_vote { article } => {
+          wikiblocks_abi::votes::Call::retract_vote { article }
+        }
+        votes_pallet::Call::downvote { article } => {
+          wikiblocks_abi::votes::Call::downvote { article }
+        }
+        votes_pallet::Call::vote_with_stake { article, vote, amount, conviction } => {
+          wikiblocks_abi::votes::Call::vote_with_stake { article, vote, amount, conviction }
+        }
+        _ => Err(())?,
+      }),
+      RuntimeCall::Discussions(call) => Call::Discussions(match call {
+        discussions::Call::post_comment { title, version, parent, body } => {
+          wikiblocks_abi::discussions::Call::post_comment { title, version, parent, body }
+        }
         _ => Err(())?,
       }),
       RuntimeCall::Babe(call) => Call::Babe(match call {
@@ -26,6 +26,7 @@ pub use emissions_pallet as emissions;
 
 pub use articles_pallet as articles;
 pub use votes_pallet as votes;
+pub use discussions_pallet as discussions;
 
 pub use pallet_babe as babe;
 pub use pallet_grandpa as grandpa;
@@ -47,8 +48,8 @@ use sp_runtime::{
 
 #[allow(unused_imports)]
 use primitives::{
-  AccountLookup, PublicKey, SubstrateAmount, BLOCK_SIZE, DAYS, FAST_EPOCH_DURATION,
-  TARGET_BLOCK_TIME,
+  AccountLookup, Article, ArticleVersion, Comment, CommentId, PublicKey, SubstrateAmount, Title,
+  BLOCK_SIZE, DAYS, FAST_EPOCH_DURATION, TARGET_BLOCK_TIME,
 };
 
 use support::{
@@ -83,6 +84,7 @@ pub type SignedExtra = (
   system::CheckNonce<Runtime>,
   system::CheckWeight<Runtime>,
   transaction_payment::ChargeTransactionPayment<Runtime>,
+  articles::CheckRateLimits<Runtime>,
 );
 
 pub type Transaction = wikiblocks_abi::tx::Transaction<RuntimeCall, SignedExtra>;
@@ -104,10 +106,10 @@ pub mod opaque {
 pub const VERSION: RuntimeVersion = RuntimeVersion {
   spec_name: create_runtime_str!("wikiblocks"),
   impl_name: create_runtime_str!("core"),
-  spec_version: 1,
+  spec_version: 2,
   impl_version: 1,
   apis: RUNTIME_API_VERSIONS,
-  transaction_version: 1,
+  transaction_version: 2,
   state_version: 1,
 };
 
@@ -144,6 +146,16 @@ parameter_types! {
 pub struct CallFilter;
 impl Contains<RuntimeCall> for CallFilter {
   fn contains(call: &RuntimeCall) -> bool {
+    // Articles are only partially migrated while a migration is in progress, so nothing reading
+    // them is dispatched until it's done
+    if matches!(
+      call,
+      RuntimeCall::Articles(_) | RuntimeCall::Votes(_) | RuntimeCall::Discussions(_)
+    ) && Articles::migration().is_some()
+    {
+      return false;
+    }
+
     // If the call is defined in our ABI, it's allowed
     let call: Result<wikiblocks_abi::Call, ()> = call.clone().try_into();
     call.is_ok()
@@ -201,15 +213,41 @@ impl coins::CallToFee<Runtime> for FeeCollector {
     // TODO: convert following mills to how much token we need through an oracle.
     match call {
       RuntimeCall::Articles(c) => match c {
-        articles_pallet::Call::add_article { title, script } |
-        articles_pallet::Call::add_version { title, script } => {
-          u64::try_from(title.encode().len() + script.encode().len()).unwrap()
+        articles_pallet::Call::add_article { title, script, summary } |
+        articles_pallet::Call::add_version { title, script, summary, .. } => {
+          u64::try_from(title.encode().len() + script.encode().len() + summary.encode().len())
+            .unwrap()
+        }
+        articles_pallet::Call::add_redirect { from, to } |
+        articles_pallet::Call::move_article { from, to, .. } => {
+          u64::try_from(from.encode().len() + to.encode().len()).unwrap()
+        }
+        articles_pallet::Call::tag { title, category } |
+        articles_pallet::Call::untag { title, category } => {
+          u64::try_from(title.encode().len() + category.encode().len()).unwrap()
         }
-        _ => unreachable!(),
+        // undoing vandalism should cost next to nothing
+        articles_pallet::Call::revert { .. } => 10,
+        // as should reviewing the edits of protected articles
+        articles_pallet::Call::accept_version { .. } |
+        articles_pallet::Call::reject_version { .. } => 10,
+        // only root may make these
+        articles_pallet::Call::set_rate_limits { .. } |
+        articles_pallet::Call::set_protected { .. } => 0,
+        articles_pallet::Call::__Ignore(_, never) => match *never {},
       },
       RuntimeCall::Votes(c) => match c {
         votes_pallet::Call::upvote { .. } => 10, // 10 / token_price = fee_in_token
-        _ => unreachable!(),
+        votes_pallet::Call::retract_vote { .. } |
+        votes_pallet::Call::downvote { .. } |
+        votes_pallet::Call::vote_with_stake { .. } => 10,
+        votes_pallet::Call::__Ignore(_, never) => match *never {},
+      },
+      RuntimeCall::Discussions(c) => match c {
+        discussions_pallet::Call::post_comment { title, body, .. } => {
+          u64::try_from(title.encode().len() + body.encode().len()).unwrap()
+        }
+        discussions_pallet::Call::__Ignore(_, never) => match *never {},
       },
       _ => 0,
     }
@@ -219,23 +257,89 @@ impl coins::CallToFee<Runtime> for FeeCollector {
 impl coins::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type CallToFee = FeeCollector;
+  type WeightInfo = coins::weights::SubstrateWeight<Runtime>;
 }
 
 impl validator_sets::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type ShouldEndSession = Babe;
+  type WeightInfo = validator_sets::weights::SubstrateWeight<Runtime>;
+}
+
+// Edits are limited per validator set session
+pub struct CurrentSession;
+impl support::traits::Get<u32> for CurrentSession {
+  fn get() -> u32 {
+    ValidatorSets::session().map_or(0, |session| session.0)
+  }
+}
+
+// Validators review the edits of protected articles, for their stake
+pub struct Reviewers;
+impl support::traits::Contains<PublicKey> for Reviewers {
+  fn contains(account: &PublicKey) -> bool {
+    ValidatorSets::allocation(account).is_some_and(|allocation| allocation != 0)
+  }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl articles::BenchmarkHelper for Reviewers {
+  fn reviewer() -> PublicKey {
+    let reviewer = PublicKey::from_raw([0xff; 32]);
+    validator_sets::Allocations::<Runtime>::set(reviewer, Some(1));
+    reviewer
+  }
 }
 
 impl articles::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = articles::weights::SubstrateWeight<Runtime>;
+  type OnArticleMoved = Votes;
+  type OnVersionAdded = Votes;
+  type Time = Timestamp;
+  type CurrentSession = CurrentSession;
+  type Reviewers = Reviewers;
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = Reviewers;
 }
 
 impl votes::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = votes::weights::SubstrateWeight<Runtime>;
+}
+
+impl discussions::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = discussions::weights::SubstrateWeight<Runtime>;
+}
+
+// Authors are rewarded for the score their versions gain within a session
+pub struct VotedVersions;
+impl emissions::VotedVersions for VotedVersions {
+  fn take_total_gain(session: u32) -> u64 {
+    Votes::take_session_gains(session)
+  }
+  fn take(session: u32, limit: u32) -> Vec<(Article, PublicKey, u64)> {
+    Votes::take_voted_versions(session, limit)
+  }
+  // Reads: Votes::SessionGains, or Votes::VotedVersions, Articles::TitlesByIndex and
+  // Articles::Authors per version
+  // Writes: Votes::SessionGains, or Votes::VotedVersions per version
+  fn weight(versions: u32) -> Weight {
+    let versions = u64::from(versions);
+    RocksDbWeight::get().reads_writes(1 + (3 * versions), 1 + versions)
+  }
+}
+
+parameter_types! {
+  pub const AuthorRewardShare: Perbill = Perbill::from_percent(20);
 }
 
 impl emissions::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = emissions::weights::SubstrateWeight<Runtime>;
+  type AuthorRewardShare = AuthorRewardShare;
+  type VotedVersions = VotedVersions;
 }
 
 pub struct IdentityValidatorIdOf;
@@ -320,6 +424,8 @@ construct_runtime!(
 
     Babe: babe,
     Grandpa: grandpa,
+
+    Discussions: discussions,
   }
 );
 
@@ -336,7 +442,13 @@ mod benches {
 
     [pallet_timestamp, Timestamp]
 
-    [balances, Balances]
+    [coins, Coins]
+    [validator_sets, ValidatorSets]
+    [emissions, Emissions]
+
+    [articles, Articles]
+    [votes, Votes]
+    [discussions, Discussions]
 
     [babe, Babe]
     [grandpa, Grandpa]
@@ -344,9 +456,25 @@ mod benches {
 }
 
 sp_api::decl_runtime_apis! {
-  #[api_version(1)]
+  #[api_version(2)]
   pub trait WikiblocksRuntimeApi {
     fn validators() -> Vec<PublicKey>;
+    #[api_version(2)]
+    fn titles(start: u32, limit: u32) -> Vec<Title>;
+    #[api_version(2)]
+    fn category_members(category: Title, after: Option<Title>, limit: u32) -> Vec<Title>;
+    #[api_version(2)]
+    fn categories(title: Title) -> Vec<Title>;
+    #[api_version(2)]
+    fn contributions(account: PublicKey, start: u32, limit: u32) -> Vec<Article>;
+    #[api_version(2)]
+    fn pending_versions(title: Title) -> Vec<u32>;
+    #[api_version(2)]
+    fn votes(account: PublicKey, after: Option<Article>, limit: u32) -> Vec<Article>;
+    #[api_version(2)]
+    fn canonical_version(title: Title) -> Option<ArticleVersion>;
+    #[api_version(2)]
+    fn comments(title: Title, start: CommentId, limit: u32) -> Vec<(CommentId, Comment)>;
   }
 }
 
@@ -552,5 +680,76 @@ sp_api::impl_runtime_apis! {
       .map(|(id, _)| id.into_inner())
       .collect()
     }
+
+    fn titles(start: u32, limit: u32) -> Vec<Title> {
+      Articles::titles(start, limit)
+    }
+
+    fn category_members(category: Title, after: Option<Title>, limit: u32) -> Vec<Title> {
+      Articles::category_members(&category, after, limit)
+    }
+
+    fn categories(title: Title) -> Vec<Title> {
+      Articles::categories(&title)
+    }
+
+    fn contributions(account: PublicKey, start: u32, limit: u32) -> Vec<Article> {
+      Articles::contributions(account, start, limit)
+    }
+
+    fn pending_versions(title: Title) -> Vec<u32> {
+      Articles::pending_versions(&title)
+    }
+
+    fn votes(account: PublicKey, after: Option<Article>, limit: u32) -> Vec<Article> {
+      Votes::votes(account, after, limit)
+    }
+
+    fn canonical_version(title: Title) -> Option<ArticleVersion> {
+      Votes::canonical_version(&title)
+    }
+
+    fn comments(title: Title, start: CommentId, limit: u32) -> Vec<(CommentId, Comment)> {
+      Discussions::comments(&title, start, limit)
+    }
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  impl frame_benchmarking::Benchmark<Block> for Runtime {
+    fn benchmark_metadata(extra: bool) -> (
+      Vec<frame_benchmarking::BenchmarkList>,
+      Vec<support::traits::StorageInfo>,
+    ) {
+      use frame_benchmarking::{baseline::Pallet as BaselineBench, Benchmarking, BenchmarkList};
+      use frame_system_benchmarking::Pallet as SystemBench;
+      use support::traits::StorageInfoTrait;
+
+      let mut list = Vec::<BenchmarkList>::new();
+      list_benchmarks!(list, extra);
+
+      (list, AllPalletsWithSystem::storage_info())
+    }
+
+    fn dispatch_benchmark(
+      config: frame_benchmarking::BenchmarkConfig
+    ) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+      use frame_benchmarking::{
+        baseline::{self, Pallet as BaselineBench},
+        Benchmarking, BenchmarkBatch, TrackedStorageKey,
+      };
+      use frame_system_benchmarking::Pallet as SystemBench;
+      use support::traits::WhitelistedStorageKeys;
+
+      impl frame_system_benchmarking::Config for Runtime {}
+      impl baseline::Config for Runtime {}
+
+      let whitelist: Vec<TrackedStorageKey> = AllPalletsWithSystem::whitelisted_storage_keys();
+
+      let mut batches = Vec::<BenchmarkBatch>::new();
+      let params = (&config, &whitelist);
+      add_benchmarks!(params, batches);
+
+      Ok(batches)
+    }
   }
 }
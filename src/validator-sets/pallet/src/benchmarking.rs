@@ -0,0 +1,84 @@
+use super::*;
+
+use frame_benchmarking::v2::*;
+
+// Allocates a key share to `n` new validators.
+//
+// Allocating and deallocating checks the fault tolerance of the set by iterating over the sorted
+// allocations until `MAX_KEY_SHARES_PER_SET` key shares are found, so their worst case is with
+// that many validators allocated a single key share.
+fn allocate_validators<T: Config>(n: u32) {
+  let amount = AllocationPerKeyShare::<T>::get();
+  for i in 0 .. n {
+    let validator: Public = account("validator", i, 0);
+    Coins::<T>::mint(validator, amount).unwrap();
+    Pallet::<T>::allocate(RawOrigin::Signed(validator).into(), amount).unwrap();
+  }
+}
+
+#[benchmarks]
+mod benchmarks {
+  use super::*;
+
+  #[benchmark]
+  fn allocate() {
+    allocate_validators::<T>(MAX_KEY_SHARES_PER_SET);
+
+    let caller: Public = whitelisted_caller();
+    let amount = AllocationPerKeyShare::<T>::get();
+    Coins::<T>::mint(caller, amount).unwrap();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), amount);
+
+    assert_eq!(Pallet::<T>::allocation(caller), Some(amount));
+  }
+
+  #[benchmark]
+  fn deallocate() {
+    allocate_validators::<T>(MAX_KEY_SHARES_PER_SET);
+
+    let caller: Public = whitelisted_caller();
+    let amount = AllocationPerKeyShare::<T>::get();
+    Coins::<T>::mint(caller, 2 * amount).unwrap();
+    Pallet::<T>::allocate(RawOrigin::Signed(caller).into(), 2 * amount).unwrap();
+
+    // remove a key share, without removing the validator
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), amount);
+
+    assert_eq!(Pallet::<T>::allocation(caller), Some(amount));
+  }
+
+  #[benchmark]
+  fn claim_deallocation() {
+    let caller: Public = whitelisted_caller();
+    let amount = AllocationPerKeyShare::<T>::get();
+    let session = Pallet::<T>::session().unwrap();
+    Coins::<T>::mint(Pallet::<T>::account(), amount).unwrap();
+    PendingDeallocations::<T>::set(caller, session, Some(amount));
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), session);
+
+    assert_eq!(Coins::<T>::balances(caller), Some(amount));
+  }
+
+  #[benchmark]
+  fn rotate_session(v: Linear<1, { MAX_KEY_SHARES_PER_SET }>) {
+    // have the validators in both the current set and the set being rotated to
+    allocate_validators::<T>(v);
+    Pallet::<T>::new_session();
+    // BABE is initialized at the start of every block, before the session can rotate
+    <pallet_babe::Pallet<T> as frame_support::traits::Hooks<_>>::on_initialize(
+      frame_system::Pallet::<T>::block_number(),
+    );
+
+    #[block]
+    {
+      Pallet::<T>::rotate_session();
+    }
+  }
+
+  impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
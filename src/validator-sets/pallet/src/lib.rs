@@ -1,5 +1,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
 use core::marker::PhantomData;
 
 use scale::{Encode, Decode};
@@ -78,6 +87,8 @@ pub mod pallet {
     type RuntimeEvent: IsType<<Self as frame_system::Config>::RuntimeEvent> + From<Event<Self>>;
 
     type ShouldEndSession: ShouldEndSession<BlockNumberFor<Self>>;
+
+    type WeightInfo: WeightInfo;
   }
 
   #[pallet::genesis_config]
@@ -261,7 +272,7 @@ pub mod pallet {
 
   /// Pending deallocations, keyed by the Session they become unlocked on.
   #[pallet::storage]
-  type PendingDeallocations<T: Config> =
+  pub(crate) type PendingDeallocations<T: Config> =
     StorageDoubleMap<_, Blake2_128Concat, Public, Identity, Session, SubstrateAmount, OptionQuery>;
 
   /// Disabled validators.
@@ -379,9 +390,8 @@ pub mod pallet {
   impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
     fn on_initialize(n: BlockNumberFor<T>) -> Weight {
       if T::ShouldEndSession::should_end_session(n) {
-        Self::rotate_session();
-        // TODO: set the proper weights
-        T::BlockWeights::get().max_block
+        let validators = Self::rotate_session();
+        <T as Config>::WeightInfo::rotate_session(validators)
       } else {
         Weight::zero()
       }
@@ -402,7 +412,7 @@ pub mod pallet {
   }
 
   impl<T: Config> Pallet<T> {
-    fn account() -> T::AccountId {
+    pub(crate) fn account() -> T::AccountId {
       system_address(b"ValidatorSets").into()
     }
 
@@ -593,7 +603,8 @@ pub mod pallet {
       PendingDeallocations::<T>::take(key, session)
     }
 
-    fn rotate_session() {
+    /// Rotates to the next session, returning the size of the larger of the two sets.
+    pub(crate) fn rotate_session() -> u32 {
       // next wikiblocks validators that is in the queue.
       let now_validators =
         Participants::<T>::get().expect("no Wikiblocks participants upon rotate_session");
@@ -609,6 +620,7 @@ pub mod pallet {
       // Update Babe and Grandpa
       let session = prior_wikiblocks_session.0 + 1;
       let next_validators = Participants::<T>::get().unwrap();
+      let now_len = now_validators.len();
       Babe::<T>::enact_epoch_change(
         WeakBoundedVec::force_from(
           now_validators.iter().copied().map(|(id, w)| (BabeAuthorityId::from(id), w)).collect(),
@@ -635,6 +647,8 @@ pub mod pallet {
       for disabled in disabled {
         Self::disable_validator(disabled);
       }
+
+      u32::try_from(now_len.max(next_validators.len())).unwrap()
     }
 
     pub fn distribute_block_rewards(
@@ -710,7 +724,7 @@ pub mod pallet {
   #[pallet::call]
   impl<T: Config> Pallet<T> {
     #[pallet::call_index(0)]
-    #[pallet::weight(0)] // TODO
+    #[pallet::weight(<T as Config>::WeightInfo::allocate())]
     pub fn allocate(origin: OriginFor<T>, amount: SubstrateAmount) -> DispatchResult {
       let validator = ensure_signed(origin)?;
       Coins::<T>::transfer_internal(validator, Self::account(), amount)?;
@@ -718,7 +732,7 @@ pub mod pallet {
     }
 
     #[pallet::call_index(1)]
-    #[pallet::weight(0)] // TODO
+    #[pallet::weight(<T as Config>::WeightInfo::deallocate())]
     pub fn deallocate(origin: OriginFor<T>, amount: SubstrateAmount) -> DispatchResult {
       let account = ensure_signed(origin)?;
 
@@ -731,7 +745,7 @@ pub mod pallet {
     }
 
     #[pallet::call_index(2)]
-    #[pallet::weight((0, DispatchClass::Operational))] // TODO
+    #[pallet::weight((<T as Config>::WeightInfo::claim_deallocation(), DispatchClass::Operational))]
     pub fn claim_deallocation(origin: OriginFor<T>, session: Session) -> DispatchResult {
       let account = ensure_signed(origin)?;
       let Some(amount) = Self::take_deallocatable_amount(session, account) else {
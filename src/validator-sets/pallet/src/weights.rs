@@ -0,0 +1,105 @@
+//! Weights for the Validator Sets pallet.
+//!
+//! Generated by running the benchmarks in `benchmarking.rs` natively in the mock runtime of the
+//! pallet, over 10 steps of every component repeated 5 times, with a state backend counting the
+//! storage accesses as the benchmarking state of the node does. The session rotation was run
+//! without Grandpa's `new_session`, so the accesses it makes are counted by hand. The execution
+//! times and proof sizes are of native code over the in-memory trie of the mock, not of the wasm
+//! runtime on the reference hardware, so they are to be replaced by running `wikiblocks-node
+//! benchmark pallet --chain dev --pallet validator_sets --extrinsic '*' --steps 50 --repeat 20
+//! --output src/validator-sets/pallet/src/weights.rs` on it.
+
+use core::marker::PhantomData;
+
+use frame_support::{
+  traits::Get,
+  weights::{constants::RocksDbWeight, Weight},
+};
+
+pub trait WeightInfo {
+  fn allocate() -> Weight;
+  fn deallocate() -> Weight;
+  fn claim_deallocation() -> Weight;
+  fn rotate_session(v: u32) -> Weight;
+}
+
+/// Weights for the Validator Sets pallet, using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+  // Reads: Coins::Balances (twice), AllocationPerKeyShare, Allocations, InSet, SortedAllocations
+  // (602 keys)
+  // Writes: Coins::Balances (twice), Allocations, SortedAllocations
+  fn allocate() -> Weight {
+    Weight::from_parts(4_208_233_000, 61_472)
+      .saturating_add(T::DbWeight::get().reads(607))
+      .saturating_add(T::DbWeight::get().writes(4))
+  }
+
+  // Reads: Babe::Authorities, Coins::Balances (twice), AllocationPerKeyShare, Allocations, InSet,
+  // SortedAllocations (602 keys)
+  // Writes: Coins::Balances (twice), Allocations, SortedAllocations (twice)
+  fn deallocate() -> Weight {
+    Weight::from_parts(3_425_495_000, 61_467)
+      .saturating_add(T::DbWeight::get().reads(608))
+      .saturating_add(T::DbWeight::get().writes(5))
+  }
+
+  // Reads: Coins::Balances (twice), PendingDeallocations
+  // Writes: Coins::Balances (twice), PendingDeallocations
+  fn claim_deallocation() -> Weight {
+    Weight::from_parts(30_966_000, 761)
+      .saturating_add(T::DbWeight::get().reads(3))
+      .saturating_add(T::DbWeight::get().writes(3))
+  }
+
+  // Reads: Babe::CurrentSlot, Babe::EpochIndex, Babe::EpochStart, Babe::GenesisSlot,
+  // Babe::NextEpochConfig, Babe::NextRandomness, Babe::PendingEpochConfigChange,
+  // Babe::SegmentIndex, Babe::SkippedEpochs, System::Digest, AllocationPerKeyShare, Allocations
+  // (600 keys), CurrentSession, DisabledIndices, InSet (600 keys), Participants, SortedAllocations
+  // (600 keys)
+  // Writes: Babe::Authorities, Babe::EpochIndex, Babe::EpochStart, Babe::NextAuthorities,
+  // Babe::NextRandomness, Babe::Randomness, Babe::SegmentIndex, Babe::SkippedEpochs,
+  // System::Digest, CurrentSession, InSet (600 keys), Participants, SessionBeginBlock,
+  // TotalAllocatedStake
+  // The range of component `v` is `[1, 600]`.
+  // Counted by hand, Reads: Grandpa::Stalled, Grandpa::PendingChange, Grandpa::CurrentSetId
+  // Writes: Grandpa::PendingChange, Grandpa::CurrentSetId, Grandpa::SetIdSession
+  fn rotate_session(v: u32) -> Weight {
+    Weight::from_parts(146_608_746, 2_099)
+      .saturating_add(Weight::from_parts(10_894_400, 352).saturating_mul(v.into()))
+      .saturating_add(T::DbWeight::get().reads(39))
+      .saturating_add(T::DbWeight::get().reads(3).saturating_mul(v.into()))
+      .saturating_add(T::DbWeight::get().writes(23))
+      .saturating_add(T::DbWeight::get().writes(1).saturating_mul(v.into()))
+  }
+}
+
+// For tests, and backwards compatibility
+impl WeightInfo for () {
+  fn allocate() -> Weight {
+    Weight::from_parts(4_208_233_000, 61_472)
+      .saturating_add(RocksDbWeight::get().reads(607))
+      .saturating_add(RocksDbWeight::get().writes(4))
+  }
+
+  fn deallocate() -> Weight {
+    Weight::from_parts(3_425_495_000, 61_467)
+      .saturating_add(RocksDbWeight::get().reads(608))
+      .saturating_add(RocksDbWeight::get().writes(5))
+  }
+
+  fn claim_deallocation() -> Weight {
+    Weight::from_parts(30_966_000, 761)
+      .saturating_add(RocksDbWeight::get().reads(3))
+      .saturating_add(RocksDbWeight::get().writes(3))
+  }
+
+  fn rotate_session(v: u32) -> Weight {
+    Weight::from_parts(146_608_746, 2_099)
+      .saturating_add(Weight::from_parts(10_894_400, 352).saturating_mul(v.into()))
+      .saturating_add(RocksDbWeight::get().reads(39))
+      .saturating_add(RocksDbWeight::get().reads(3).saturating_mul(v.into()))
+      .saturating_add(RocksDbWeight::get().writes(23))
+      .saturating_add(RocksDbWeight::get().writes(1).saturating_mul(v.into()))
+  }
+}
@@ -0,0 +1,141 @@
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+use sp_core::sr25519::Public;
+use sp_runtime::SaturatedConversion;
+use sp_std::vec;
+
+use articles_pallet::Pallet as Articles;
+use coins_pallet::Pallet as Coins;
+use wikiblocks_primitives::{
+  Article, ArticleVersion, Body, Conviction, OpCode, Script, Title, Vote, CANONICAL_WINDOW,
+  MAX_EXPIRATIONS, MAX_TITLE_LEN,
+};
+
+// An article with the longest title possible, as the title is canonicalized to be looked up,
+// which takes time linear to its length. It's authored by another account than the voter's, and
+// has `CANONICAL_WINDOW` versions for its canonical version to be selected among.
+fn article<T: Config>() -> Article {
+  let title = Title::new(vec![b'a'; usize::try_from(MAX_TITLE_LEN).unwrap()]).unwrap();
+  let script = || Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap();
+  let author: Public = account("author", 0, 0);
+  Articles::<T>::add_article(RawOrigin::Signed(author).into(), title.clone(), None, script())
+    .unwrap();
+  for version in 0 .. CANONICAL_WINDOW - 1 {
+    Articles::<T>::add_version(
+      RawOrigin::Signed(author).into(),
+      title.clone(),
+      None,
+      ArticleVersion(version),
+      false,
+      script(),
+    )
+    .unwrap();
+  }
+  Article::new(title, ArticleVersion(0))
+}
+
+#[benchmarks]
+mod benchmarks {
+  use super::*;
+
+  // Replacing a vote is costlier than voting, so the benchmarks of votes replace one.
+  #[benchmark]
+  fn upvote() {
+    let caller: Public = whitelisted_caller();
+    let article = article::<T>();
+    Pallet::<T>::downvote(RawOrigin::Signed(caller).into(), article.clone()).unwrap();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), article.clone());
+
+    assert_eq!(Pallet::<T>::upvotes(&article), 1);
+    assert_eq!(Pallet::<T>::score(article), 1);
+  }
+
+  #[benchmark]
+  fn retract_vote() {
+    let caller: Public = whitelisted_caller();
+    let article = article::<T>();
+    Pallet::<T>::upvote(RawOrigin::Signed(caller).into(), article.clone()).unwrap();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), article.clone());
+
+    assert_eq!(Pallet::<T>::upvotes(article), 0);
+  }
+
+  #[benchmark]
+  fn downvote() {
+    let caller: Public = whitelisted_caller();
+    let article = article::<T>();
+    Pallet::<T>::upvote(RawOrigin::Signed(caller).into(), article.clone()).unwrap();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), article.clone());
+
+    assert_eq!(Pallet::<T>::downvotes(&article), 1);
+    assert_eq!(Pallet::<T>::score(article), -1);
+  }
+
+  #[benchmark]
+  fn vote_with_stake() {
+    let caller: Public = whitelisted_caller();
+    let article = article::<T>();
+    Coins::<T>::mint(caller, 1_000).unwrap();
+    Pallet::<T>::downvote(RawOrigin::Signed(caller).into(), article.clone()).unwrap();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller), article.clone(), Vote::Up, 1_000, Conviction::Locked6x);
+
+    assert_eq!(Pallet::<T>::upvotes(&article), 1);
+    assert_eq!(Coins::<T>::held(caller, VOTES_HOLD), Some(1_000));
+  }
+
+  // Releasing the coins of `u` accounts, and counting `e` votes whose coins are released as votes
+  // without coins, as `on_initialize` does for up to `MAX_EXPIRATIONS` of them in a block. Every
+  // vote is on another article with `CANONICAL_WINDOW` versions, whose canonical version is
+  // selected anew.
+  #[benchmark]
+  fn expire(u: Linear<0, MAX_EXPIRATIONS>, e: Linear<0, MAX_EXPIRATIONS>) {
+    let until = frame_system::Pallet::<T>::block_number().saturated_into::<u64>() +
+      Conviction::Locked1x.lock_period();
+    for i in 0 .. u {
+      let voter: Public = account("unlocked", i, 0);
+      Coins::<T>::mint(voter, 1_000).unwrap();
+      Coins::<T>::hold(voter, VOTES_HOLD, 1_000).unwrap();
+      Unlocks::<T>::set(until, voter, 1_000);
+    }
+    let mut articles = vec![];
+    for i in 0 .. e {
+      let letter = |n: u32| b'a' + u8::try_from(n % 26).unwrap();
+      let title = Title::new(vec![letter(i), letter(i / 26), letter(i / (26 * 26))]).unwrap();
+      articles_pallet::Titles::<T>::set(&title, Some(i));
+      articles_pallet::TitlesByIndex::<T>::set(i, Some(title.clone()));
+      articles_pallet::LastVersion::<T>::set(&title, Some(ArticleVersion(CANONICAL_WINDOW - 1)));
+
+      let voter: Public = account("voter", i, 0);
+      let key = (i, ArticleVersion(0));
+      Voters::<T>::set(voter, key, Some(Vote::Up));
+      VoteWeights::<T>::set(voter, key, Some((2, until)));
+      StakedVotes::<T>::set(until, (voter, key), Some(()));
+      articles.push(Article::new(title, ArticleVersion(0)));
+    }
+    articles_pallet::TitleCount::<T>::set(e);
+
+    #[block]
+    {
+      Pallet::<T>::unlock(until, u);
+      Pallet::<T>::expire_stakes(until, e);
+    }
+
+    assert_eq!(Unlocks::<T>::iter_prefix(until).count(), 0);
+    for article in articles {
+      assert_eq!(Pallet::<T>::upvotes(article), 1);
+    }
+  }
+
+  impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -1,5 +1,19 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+mod migrations;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[allow(clippy::cast_possible_truncation)]
 #[frame_support::pallet]
 pub mod pallet {
@@ -7,14 +21,28 @@ pub mod pallet {
   use frame_support::pallet_prelude::*;
 
   use sp_core::sr25519::Public;
-  use sp_std::vec;
+  use sp_runtime::SaturatedConversion;
+  use sp_std::{vec, vec::Vec};
 
   use articles_pallet::{Config as ArticlesConfig, Pallet as Articles};
-  use wikiblocks_primitives::Article;
+  use articles_pallet::{OnArticleMoved, OnVersionAdded};
+  use coins_pallet::{Config as CoinsConfig, HoldReason, Pallet as Coins};
+  use wikiblocks_primitives::{
+    Article, ArticleVersion, Conviction, SubstrateAmount, Title, Vote, CANONICAL_QUORUM,
+    CANONICAL_WINDOW, MAX_EXPIRATIONS, MAX_PAGE_LEN,
+  };
+
+  use super::WeightInfo;
+
+  /// What the coins backing votes are held for.
+  pub const VOTES_HOLD: HoldReason = *b"votes\0\0\0";
 
   #[pallet::config]
-  pub trait Config: frame_system::Config<AccountId = Public> + ArticlesConfig {
+  pub trait Config:
+    frame_system::Config<AccountId = Public> + ArticlesConfig + CoinsConfig
+  {
     type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+    type WeightInfo: WeightInfo;
   }
 
   #[pallet::error]
@@ -22,43 +50,516 @@ pub mod pallet {
     InvalidTitle,
     InvalidVersion,
     TooManyUpvotes,
+    TooManyDownvotes,
+    AlreadyVoted,
+    NotVoted,
+    InvalidStake,
+    SelfVote,
   }
 
   #[pallet::event]
-  pub enum Event<T: Config> {}
+  #[pallet::generate_deposit(fn deposit_event)]
+  pub enum Event<T: Config> {
+    Upvoted { article: Article, voter: Public },
+    Downvoted { article: Article, voter: Public },
+    VoteRetracted { article: Article, voter: Public },
+    StakeLocked { voter: Public, amount: SubstrateAmount, weight: u64, until: u64 },
+    StakeUnlocked { voter: Public, amount: SubstrateAmount },
+    CanonicalVersionChanged { title: Title, version: Option<ArticleVersion> },
+  }
+
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
 
   #[pallet::pallet]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(_);
 
+  /// The upvotes of every version, including those counted before v1 without who cast them.
   #[pallet::storage]
   #[pallet::getter(fn upvotes)]
   pub type Upvotes<T: Config> = StorageMap<_, Blake2_128Concat, Article, u64, ValueQuery>;
 
-  impl<T: Config> Pallet<T> {}
+  #[pallet::storage]
+  #[pallet::getter(fn downvotes)]
+  pub type Downvotes<T: Config> = StorageMap<_, Blake2_128Concat, Article, u64, ValueQuery>;
+
+  /// The upvotes of every version, minus its downvotes.
+  #[pallet::storage]
+  #[pallet::getter(fn score)]
+  pub type Scores<T: Config> = StorageMap<_, Blake2_128Concat, Article, i64, ValueQuery>;
+
+  /// The votes of every account, keyed by the account, the position of the article's title in
+  /// `Articles::Titles` and the version.
+  ///
+  /// The position of a title moves with its article, so votes don't have to.
+  #[pallet::storage]
+  pub type Voters<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    Public,
+    Blake2_128Concat,
+    (u32, ArticleVersion),
+    Vote,
+    OptionQuery,
+  >;
+
+  /// The weight of every vote backed by coins, and the block its coins are released in, keyed
+  /// like `Voters`. Votes without one weigh 1.
+  #[pallet::storage]
+  pub type VoteWeights<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    Public,
+    Blake2_128Concat,
+    (u32, ArticleVersion),
+    (u64, u64),
+    OptionQuery,
+  >;
+
+  /// The votes backed by coins, keyed by the block their coins are released in, when they fall
+  /// back to weighing 1.
+  #[pallet::storage]
+  pub type StakedVotes<T: Config> = StorageDoubleMap<
+    _,
+    Identity,
+    u64,
+    Blake2_128Concat,
+    (Public, (u32, ArticleVersion)),
+    (),
+    OptionQuery,
+  >;
+
+  /// The coins backing votes every account gets back, keyed by the block they're released in.
+  #[pallet::storage]
+  #[pallet::getter(fn unlocks)]
+  pub type Unlocks<T: Config> =
+    StorageDoubleMap<_, Identity, u64, Blake2_128Concat, Public, SubstrateAmount, ValueQuery>;
+
+  /// The first block whose coins and staked votes may not all have been released yet.
+  ///
+  /// At most `MAX_EXPIRATIONS` are released per block, so the rest are carried over, and released
+  /// late, in the blocks after.
+  #[pallet::storage]
+  pub type NextExpiry<T: Config> = StorageValue<_, u64, OptionQuery>;
+
+  /// The version of every article the votes endorse, keyed by its canonical title.
+  ///
+  /// It's the version with the highest score among the latest `CANONICAL_WINDOW` versions as of
+  /// the last vote on any of them, the latest one on ties, if its score is at least
+  /// `CANONICAL_QUORUM`. Articles without one have no version endorsed. It's selected anew once
+  /// newer versions push it out of the window.
+  ///
+  /// Scores count the weight of the votes, not how many accounts cast them, so the quorum is met
+  /// by a single vote backed by enough coins, as by three votes without any, until the coins are
+  /// released.
+  #[pallet::storage]
+  pub type CanonicalVersion<T: Config> =
+    StorageMap<_, Blake2_128Concat, Title, ArticleVersion, OptionQuery>;
+
+  /// The versions voted on during every session, keyed by the session and like `Voters`, with
+  /// how many more accounts upvote them than downvote them since the first vote on them within it.
+  ///
+  /// Authors are rewarded for the accounts, not the weight of their votes, so coins can't buy
+  /// rewards for versions of other accounts the voter holds, and every account rewarded pays the
+  /// fee of its vote.
+  ///
+  /// Every version voted on is kept, as every vote pays a fee, until the emissions of the session
+  /// pay their authors.
+  #[pallet::storage]
+  pub type VotedVersions<T: Config> =
+    StorageDoubleMap<_, Identity, u32, Blake2_128Concat, (u32, ArticleVersion), i64, OptionQuery>;
+
+  /// The accounts the versions voted on during every session gained within it, summed.
+  #[pallet::storage]
+  pub type SessionGains<T: Config> = StorageMap<_, Identity, u32, u64, ValueQuery>;
 
-  #[pallet::call]
   impl<T: Config> Pallet<T> {
-    #[pallet::call_index(0)]
-    #[pallet::weight((0, DispatchClass::Normal))] // TODO
-    pub fn upvote(origin: OriginFor<T>, article: Article) -> DispatchResult {
-      let _ = ensure_signed(origin)?;
+    // The key of an existing article version in `Voters`.
+    fn voters_key(article: &Article) -> Result<(u32, ArticleVersion), Error<T>> {
+      let index = Articles::<T>::title_index(article.title()).ok_or(Error::<T>::InvalidTitle)?;
+      let last_version =
+        Articles::<T>::last_version(article.title()).ok_or(Error::<T>::InvalidVersion)?;
+      if article.version().0 > last_version.0 {
+        Err(Error::<T>::InvalidVersion)?;
+      }
+      Ok((index, article.version()))
+    }
+
+    /// Returns the vote of an account on an article version.
+    pub fn vote(account: Public, article: &Article) -> Option<Vote> {
+      Voters::<T>::get(account, Self::voters_key(article).ok()?)
+    }
+
+    /// Returns up to `limit` article versions an account voted on, in storage order, starting
+    /// after the version `after`.
+    ///
+    /// Versions are returned under the current title of their article. At most `MAX_PAGE_LEN`
+    /// versions are returned.
+    pub fn votes(account: Public, after: Option<Article>, limit: u32) -> Vec<Article> {
+      let limit = usize::try_from(limit.min(MAX_PAGE_LEN)).unwrap();
+      let keys = match after {
+        Some(after) => {
+          let Ok(after) = Self::voters_key(&after) else { return vec![] };
+          let after = Voters::<T>::hashed_key_for(account, after);
+          Voters::<T>::iter_key_prefix_from(account, after)
+        }
+        None => Voters::<T>::iter_key_prefix(account),
+      };
+      keys
+        .take(limit)
+        .filter_map(|(index, version)| {
+          Some(Article::new(articles_pallet::TitlesByIndex::<T>::get(index)?, version))
+        })
+        .collect()
+    }
+
+    /// Returns the weight of the vote of an account on an article version, if it voted on it.
+    pub fn vote_weight(account: Public, article: &Article) -> Option<u64> {
+      let key = Self::voters_key(article).ok()?;
+      Voters::<T>::contains_key(account, key)
+        .then(|| VoteWeights::<T>::get(account, key).map_or(1, |(weight, _)| weight))
+    }
+
+    // Takes the weight of the vote of an account, which no longer counts its coins.
+    fn take_weight(voter: Public, key: (u32, ArticleVersion)) -> u64 {
+      let Some((weight, until)) = VoteWeights::<T>::take(voter, key) else { return 1 };
+      StakedVotes::<T>::remove(until, (voter, key));
+      weight
+    }
+
+    // Releases up to `limit` of the coins backing votes released in a block, returning how many
+    // were.
+    pub(crate) fn unlock(block: u64, limit: u32) -> u32 {
+      let mut unlocks = 0;
+      for (voter, amount) in Unlocks::<T>::drain_prefix(block).take(usize::try_from(limit).unwrap())
+      {
+        unlocks += 1;
+        if Coins::<T>::release(voter, VOTES_HOLD, amount).is_ok() {
+          Self::deposit_event(Event::StakeUnlocked { voter, amount });
+        }
+      }
+      unlocks
+    }
+
+    // Counts up to `limit` of the votes whose coins are released in a block as votes without
+    // coins, returning how many there were.
+    pub(crate) fn expire_stakes(block: u64, limit: u32) -> u32 {
+      let mut expired = 0;
+      let votes = StakedVotes::<T>::drain_prefix(block).take(usize::try_from(limit).unwrap());
+      for ((voter, key), ()) in votes {
+        expired += 1;
+        let Some((weight, _)) = VoteWeights::<T>::take(voter, key) else { continue };
+        let Some(vote) = Voters::<T>::get(voter, key) else { continue };
+        let Some(title) = articles_pallet::TitlesByIndex::<T>::get(key.0) else { continue };
+        let article = Article::new(title, key.1);
+        // removing the weight can't fail, and adding 1 back can't overflow what it was removed from
+        let _ = Self::tally(&article, vote, weight, false);
+        let _ = Self::tally(&article, vote, 1, true);
+        Self::select_canonical(article.title());
+      }
+      expired
+    }
+
+    // Adds the weight of a vote to the tallies of an article version, or removes it.
+    fn tally(article: &Article, vote: Vote, weight: u64, added: bool) -> Result<(), Error<T>> {
+      let (tallies, too_many) = match vote {
+        Vote::Up => (Upvotes::<T>::get(article), Error::<T>::TooManyUpvotes),
+        Vote::Down => (Downvotes::<T>::get(article), Error::<T>::TooManyDownvotes),
+      };
+      let tallies = if added {
+        tallies.checked_add(weight).ok_or(too_many)?
+      } else {
+        tallies.saturating_sub(weight)
+      };
+      match vote {
+        Vote::Up => Upvotes::<T>::set(article, tallies),
+        Vote::Down => Downvotes::<T>::set(article, tallies),
+      }
+
+      // an added upvote or a removed downvote raises the score
+      let score = Scores::<T>::get(article);
+      let weight = i64::try_from(weight).unwrap_or(i64::MAX);
+      let score = if added == (vote == Vote::Up) {
+        score.saturating_add(weight)
+      } else {
+        score.saturating_sub(weight)
+      };
+      Scores::<T>::set(article, score);
+      Ok(())
+    }
+
+    /// Returns the version of an article the votes endorse, following redirects, or its last
+    /// version if none is endorsed.
+    pub fn canonical_version(title: &Title) -> Option<ArticleVersion> {
+      let title = Articles::<T>::resolve(title)?;
+      CanonicalVersion::<T>::get(&title).or_else(|| Articles::<T>::last_version(&title))
+    }
+
+    // Selects the canonical version of an article anew, after the scores of its versions changed.
+    pub(crate) fn select_canonical(title: &Title) {
+      let Some(last_version) = Articles::<T>::last_version(title) else { return };
+      let first = last_version.0.saturating_sub(CANONICAL_WINDOW - 1);
+      let mut selected = None;
+      let mut highest = CANONICAL_QUORUM;
+      for version in first ..= last_version.0 {
+        let score = Scores::<T>::get(Article::new(title.clone(), ArticleVersion(version)));
+        if score >= highest {
+          selected = Some(ArticleVersion(version));
+          highest = score;
+        }
+      }
+
+      if CanonicalVersion::<T>::get(title) != selected {
+        CanonicalVersion::<T>::set(title, selected);
+        Self::deposit_event(Event::CanonicalVersionChanged {
+          title: title.clone(),
+          version: selected,
+        });
+      }
+    }
+
+    // Records the vote of an account on a version changed during the current session, from
+    // `previous`, whatever the weight of either.
+    fn record_vote(key: (u32, ArticleVersion), previous: Option<Vote>, vote: Option<Vote>) {
+      let count = |vote: Option<Vote>| match vote {
+        Some(Vote::Up) => 1,
+        Some(Vote::Down) => -1,
+        None => 0,
+      };
+      let session = <T as ArticlesConfig>::CurrentSession::get();
+      let before = VotedVersions::<T>::get(session, key).unwrap_or(0);
+      let after = before + count(vote) - count(previous);
+      let gain = |accounts: i64| u64::try_from(accounts).unwrap_or(0);
+      SessionGains::<T>::mutate(session, |gains| {
+        *gains = gains.saturating_sub(gain(before)).saturating_add(gain(after));
+      });
+      VotedVersions::<T>::set(session, key, Some(after));
+    }
+
+    /// Takes how many accounts the versions voted on during a session gained within it, summed.
+    pub fn take_session_gains(session: u32) -> u64 {
+      SessionGains::<T>::take(session)
+    }
+
+    /// Takes up to `limit` versions voted on during a session, with their authors and how many
+    /// accounts they gained within it, so the authors can be rewarded.
+    ///
+    /// Versions which didn't gain any are taken all the same.
+    pub fn take_voted_versions(session: u32, limit: u32) -> Vec<(Article, Public, u64)> {
+      VotedVersions::<T>::drain_prefix(session)
+        .take(usize::try_from(limit).unwrap())
+        .filter_map(|((index, version), accounts)| {
+          let article = Article::new(articles_pallet::TitlesByIndex::<T>::get(index)?, version);
+          let author = Articles::<T>::authors(&article)?;
+          Some((article, author, u64::try_from(accounts).unwrap_or(0)))
+        })
+        .collect()
+    }
 
+    // Sets the vote of an account on an article version, replacing any other vote it had on it.
+    //
+    // Votes without a stake, its weight and the block its coins are released in, weigh 1, and
+    // can't replace a vote the same way.
+    fn cast(
+      voter: Public,
+      article: Article,
+      vote: Vote,
+      stake: Option<(u64, u64)>,
+    ) -> DispatchResult {
       // make sure title exist
       if !Articles::<T>::title_exist(article.title()) {
         Err(Error::<T>::InvalidTitle)?;
       }
 
-      // make sure version exist
-      let Some(last_version) = Articles::<T>::last_version(article.title()) else {
-        return Err(Error::<T>::InvalidVersion)?;
-      };
-      if article.version().0 > last_version.0 {
-        Err(Error::<T>::InvalidVersion)?;
+      // make sure version exist, and the account didn't vote the same yet
+      let key = Self::voters_key(&article)?;
+      // authors are rewarded for the votes on their versions, so they can't cast them
+      if Articles::<T>::authors(&article) == Some(voter) {
+        Err(Error::<T>::SelfVote)?;
+      }
+      let previous = Voters::<T>::get(voter, key);
+      if let Some(previous) = previous {
+        if (previous == vote) && stake.is_none() {
+          Err(Error::<T>::AlreadyVoted)?;
+        }
+        let previous_weight = Self::take_weight(voter, key);
+        Self::tally(&article, previous, previous_weight, false)?;
+      }
+      Voters::<T>::set(voter, key, Some(vote));
+      VoteWeights::<T>::set(voter, key, stake);
+      if let Some((_, until)) = stake {
+        StakedVotes::<T>::set(until, (voter, key), Some(()));
+      }
+
+      // update the tallies
+      Self::tally(&article, vote, stake.map_or(1, |(weight, _)| weight), true)?;
+      Self::select_canonical(article.title());
+
+      // the author is rewarded for the accounts gained once the session ends
+      Self::record_vote(key, previous, Some(vote));
+
+      Self::deposit_event(match vote {
+        Vote::Up => Event::Upvoted { article, voter },
+        Vote::Down => Event::Downvoted { article, voter },
+      });
+      Ok(())
+    }
+  }
+
+  #[pallet::hooks]
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    // Releases the coins whose lock period ended, and stops counting them toward their votes, up
+    // to `MAX_EXPIRATIONS` of them, starting with those carried over from the blocks before.
+    fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+      let block = n.saturated_into::<u64>();
+      let mut next = NextExpiry::<T>::get().unwrap_or(block);
+      let (mut blocks, mut unlocks, mut expired) = (0u32, 0, 0);
+      // every block gone through counts toward the limit, so a long way to catch up is spread
+      let mut left = MAX_EXPIRATIONS;
+      while (next <= block) && (left != 0) {
+        blocks += 1;
+        left -= 1;
+        let unlocked = Self::unlock(next, left);
+        left -= unlocked;
+        let stakes = Self::expire_stakes(next, left);
+        left -= stakes;
+        unlocks += unlocked;
+        expired += stakes;
+        // the block may have more left if the limit was reached
+        if left == 0 {
+          break;
+        }
+        next += 1;
+      }
+      NextExpiry::<T>::set(Some(next));
+
+      T::DbWeight::get()
+        .reads_writes(1 + (2 * u64::from(blocks)), 1)
+        .saturating_add(<T as Config>::WeightInfo::expire(unlocks, expired))
+    }
+
+    fn on_runtime_upgrade() -> Weight {
+      let version = StorageVersion::get::<Pallet<T>>();
+      let mut weight = Weight::zero();
+      if version < 1 {
+        weight += crate::migrations::v1::<T>();
+      }
+      if version < 2 {
+        weight += crate::migrations::v2::<T>();
+      }
+      if version < 3 {
+        weight += crate::migrations::v3::<T>();
+      }
+      if version < 4 {
+        weight += crate::migrations::v4::<T>();
       }
+      weight
+    }
+  }
+
+  // The tallies and the canonical version follow the versions to their new title.
+  impl<T: Config> OnArticleMoved for Pallet<T> {
+    fn on_article_moved(from: &Title, to: &Title, last_version: ArticleVersion) {
+      CanonicalVersion::<T>::swap(from, to);
+      for version in 0 ..= last_version.0 {
+        let from = Article::new(from.clone(), ArticleVersion(version));
+        let to = Article::new(to.clone(), ArticleVersion(version));
+        Upvotes::<T>::swap(&from, &to);
+        Downvotes::<T>::swap(&from, &to);
+        Scores::<T>::swap(&from, &to);
+      }
+    }
+
+    fn weight(versions: u32) -> Weight {
+      T::DbWeight::get()
+        .reads_writes(6, 6)
+        .saturating_mul(versions.into())
+        .saturating_add(T::DbWeight::get().reads_writes(2, 2))
+    }
+  }
+
+  // The canonical version is selected anew once a new version pushes it out of the window.
+  impl<T: Config> OnVersionAdded for Pallet<T> {
+    fn on_version_added(article: &Article) {
+      let Some(canonical) = CanonicalVersion::<T>::get(article.title()) else { return };
+      if article.version().0.saturating_sub(canonical.0) >= CANONICAL_WINDOW {
+        Self::select_canonical(article.title());
+      }
+    }
+
+    fn weight() -> Weight {
+      T::DbWeight::get().reads_writes(2 + u64::from(CANONICAL_WINDOW), 1)
+    }
+  }
+
+  #[pallet::call]
+  impl<T: Config> Pallet<T> {
+    /// Upvotes an article version, replacing the downvote of the account if it had one.
+    ///
+    /// Every account has a single vote on every version.
+    #[pallet::call_index(0)]
+    #[pallet::weight(<T as Config>::WeightInfo::upvote())]
+    pub fn upvote(origin: OriginFor<T>, article: Article) -> DispatchResult {
+      let voter = ensure_signed(origin)?;
+      Self::cast(voter, article, Vote::Up, None)
+    }
+
+    /// Retracts the vote of the account for an article version.
+    #[pallet::call_index(1)]
+    #[pallet::weight(<T as Config>::WeightInfo::retract_vote())]
+    pub fn retract_vote(origin: OriginFor<T>, article: Article) -> DispatchResult {
+      let voter = ensure_signed(origin)?;
+
+      let key = Self::voters_key(&article)?;
+      let vote = Voters::<T>::take(voter, key).ok_or(Error::<T>::NotVoted)?;
+      let weight = Self::take_weight(voter, key);
+      Self::tally(&article, vote, weight, false)?;
+      Self::select_canonical(article.title());
+      Self::record_vote(key, Some(vote), None);
+
+      Self::deposit_event(Event::VoteRetracted { article, voter });
+      Ok(())
+    }
+
+    /// Downvotes an article version, replacing the upvote of the account if it had one.
+    #[pallet::call_index(2)]
+    #[pallet::weight(<T as Config>::WeightInfo::downvote())]
+    pub fn downvote(origin: OriginFor<T>, article: Article) -> DispatchResult {
+      let voter = ensure_signed(origin)?;
+      Self::cast(voter, article, Vote::Down, None)
+    }
+
+    /// Votes on an article version with the weight of `amount` coins, which are held for the
+    /// lock period of the conviction. Longer periods weigh more.
+    ///
+    /// The vote replaces any other vote the account had on the version, whose coins are still held
+    /// until their own period ends, as are the coins of votes which are retracted.
+    ///
+    /// Once the coins are released, the vote weighs 1, as a vote without coins.
+    #[pallet::call_index(3)]
+    #[pallet::weight(<T as Config>::WeightInfo::vote_with_stake())]
+    pub fn vote_with_stake(
+      origin: OriginFor<T>,
+      article: Article,
+      vote: Vote,
+      amount: SubstrateAmount,
+      conviction: Conviction,
+    ) -> DispatchResult {
+      let voter = ensure_signed(origin)?;
+      if amount == 0 {
+        Err(Error::<T>::InvalidStake)?;
+      }
+
+      // hold the coins until the lock period ends
+      Coins::<T>::hold(voter, VOTES_HOLD, amount)?;
+      let until = frame_system::Pallet::<T>::block_number()
+        .saturated_into::<u64>()
+        .saturating_add(conviction.lock_period());
+      Unlocks::<T>::mutate(until, voter, |held| *held = held.saturating_add(amount));
 
-      // update the upvotes
-      let current = Upvotes::<T>::get(&article);
-      Upvotes::<T>::set(article, current.checked_add(1).ok_or(Error::<T>::TooManyUpvotes)?);
+      let weight = conviction.weight(amount);
+      Self::cast(voter, article, vote, Some((weight, until)))?;
+      Self::deposit_event(Event::StakeLocked { voter, amount, weight, until });
       Ok(())
     }
   }
@@ -0,0 +1,61 @@
+use frame_support::{
+  pallet_prelude::*,
+  storage::{storage_prefix, unhashed},
+  traits::{PalletInfoAccess, StorageVersion},
+};
+
+use wikiblocks_primitives::{Vote, CANONICAL_WINDOW};
+
+use crate::pallet::*;
+
+/// Upvotes were counted without who voted before v1, so an account could upvote a version any
+/// amount of times. The counts are kept, as the votes they're made of can't be told apart, so they
+/// stay on their versions without any account being able to retract them.
+pub(crate) fn v1<T: Config>() -> Weight {
+  StorageVersion::new(1).put::<Pallet<T>>();
+  T::DbWeight::get().writes(1)
+}
+
+/// Votes were only upvotes before v2, stored without a direction. They're translated to upvotes,
+/// and every version gets a score of its upvotes, as none had downvotes.
+pub(crate) fn v2<T: Config>() -> Weight {
+  let mut votes = 0;
+  Voters::<T>::translate::<(), _>(|_, _, ()| {
+    votes += 1;
+    Some(Vote::Up)
+  });
+  let mut scores = 0;
+  for (article, upvotes) in Upvotes::<T>::iter() {
+    scores += 1;
+    Scores::<T>::set(article, i64::try_from(upvotes).unwrap_or(i64::MAX));
+  }
+
+  StorageVersion::new(2).put::<Pallet<T>>();
+  T::DbWeight::get().reads_writes(votes + scores, votes + scores + 1)
+}
+
+/// The canonical versions weren't stored before v3, so they're selected from the scores of every
+/// article's latest versions.
+pub(crate) fn v3<T: Config>() -> Weight {
+  let mut titles = 0;
+  for title in articles_pallet::TitlesByIndex::<T>::iter_values() {
+    titles += 1;
+    Pallet::<T>::select_canonical(&title);
+  }
+
+  StorageVersion::new(3).put::<Pallet<T>>();
+  let selection = 2 + u64::from(CANONICAL_WINDOW);
+  T::DbWeight::get().reads_writes(titles * (1 + selection), titles + 1)
+}
+
+/// The versions voted on were kept without their session before v4, capped in a counted map, and
+/// rewarded for their whole score. They're removed, so the versions voted on during the session
+/// the upgrade happens in are only rewarded for the score they gain after it.
+pub(crate) fn v4<T: Config>() -> Weight {
+  let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+  let versions = unhashed::clear_prefix(&storage_prefix(pallet, b"VotedVersions"), None, None);
+  unhashed::kill(&storage_prefix(pallet, b"CounterForVotedVersions"));
+
+  StorageVersion::new(4).put::<Pallet<T>>();
+  T::DbWeight::get().reads_writes(u64::from(versions.loops), u64::from(versions.unique) + 2)
+}
@@ -0,0 +1,118 @@
+//! Test environment for Votes pallet.
+
+use super::*;
+
+use frame_support::{
+  construct_runtime, parameter_types,
+  traits::{ConstU32, ConstU64, UnixTime},
+};
+
+use sp_core::{H256, Pair, sr25519::Public};
+use sp_runtime::{
+  traits::{BlakeTwo256, IdentityLookup},
+  BuildStorage,
+};
+
+use wikiblocks_primitives::{insecure_pair_from_name, SubstrateAmount};
+
+use crate as votes;
+pub use articles_pallet as articles;
+pub use coins_pallet as coins;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+  pub enum Test
+  {
+    System: frame_system,
+    Coins: coins,
+    Articles: articles,
+    Votes: votes,
+  }
+);
+
+// Blocks are six seconds apart, starting from the Unix epoch.
+pub struct MockTime;
+impl UnixTime for MockTime {
+  fn now() -> core::time::Duration {
+    core::time::Duration::from_secs(System::block_number() * 6)
+  }
+}
+
+impl frame_system::Config for Test {
+  type BaseCallFilter = frame_support::traits::Everything;
+  type BlockWeights = ();
+  type BlockLength = ();
+  type RuntimeOrigin = RuntimeOrigin;
+  type RuntimeCall = RuntimeCall;
+  type Nonce = u64;
+  type Hash = H256;
+  type Hashing = BlakeTwo256;
+  type AccountId = Public;
+  type Lookup = IdentityLookup<Self::AccountId>;
+  type Block = Block;
+  type RuntimeEvent = RuntimeEvent;
+  type BlockHashCount = ConstU64<250>;
+  type DbWeight = ();
+  type Version = ();
+  type PalletInfo = PalletInfo;
+  type AccountData = ();
+  type OnNewAccount = ();
+  type OnKilledAccount = ();
+  type SystemWeightInfo = ();
+  type SS58Prefix = ();
+  type OnSetCode = ();
+  type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+  pub static Session: u32 = 0;
+}
+
+pub struct FeeCollector;
+impl coins::CallToFee<Test> for FeeCollector {
+  fn call_to_fee(_: &RuntimeCall) -> SubstrateAmount {
+    0
+  }
+}
+
+impl coins::Config for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type CallToFee = FeeCollector;
+  type WeightInfo = ();
+}
+
+impl articles::Config for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = ();
+  type OnArticleMoved = Votes;
+  type OnVersionAdded = Votes;
+  type Time = MockTime;
+  type CurrentSession = Session;
+  type Reviewers = frame_support::traits::Everything;
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = frame_support::traits::Everything;
+}
+
+impl Config for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+  let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+
+  coins::GenesisConfig::<Test> {
+    accounts: ["alice", "bob", "carol"]
+      .into_iter()
+      .map(|name| (insecure_pair_from_name(name).public(), 1 << 40))
+      .collect(),
+    _ignore: Default::default(),
+  }
+  .assimilate_storage(&mut t)
+  .unwrap();
+
+  let mut ext = sp_io::TestExternalities::new(t);
+  ext.execute_with(|| System::set_block_number(1));
+  ext
+}
@@ -0,0 +1,555 @@
+use crate::{mock::*, pallet, VOTES_HOLD};
+
+use frame_support::{
+  assert_noop, assert_ok,
+  traits::{OnInitialize, OnRuntimeUpgrade, StorageVersion},
+};
+use frame_system::RawOrigin;
+
+use sp_core::{sr25519::Public, Pair};
+use wikiblocks_primitives::{
+  insecure_pair_from_name, Article, ArticleVersion, Body, Conviction, OpCode, Script, Title, Vote,
+  CANONICAL_WINDOW, MAX_EXPIRATIONS, VOTE_WEIGHT_UNIT,
+};
+
+// Adds an article with the given amount of versions, authored by "author", returning its title.
+fn article(versions: u32) -> Title {
+  let author = insecure_pair_from_name("author").public();
+  let title = Title::new(b"paris".to_vec()).unwrap();
+  let script = |body: u32| {
+    Script::new(vec![OpCode::Add(Body::new(body.to_string().into_bytes()).unwrap())]).unwrap()
+  };
+  assert_ok!(Articles::add_article(
+    RawOrigin::Signed(author).into(),
+    title.clone(),
+    None,
+    script(0)
+  ));
+  for version in 1 .. versions {
+    assert_ok!(Articles::add_version(
+      RawOrigin::Signed(author).into(),
+      title.clone(),
+      None,
+      ArticleVersion(version - 1),
+      false,
+      script(version)
+    ));
+  }
+  title
+}
+
+#[test]
+fn upvote() {
+  new_test_ext().execute_with(|| {
+    let alice = insecure_pair_from_name("alice").public();
+    let bob = insecure_pair_from_name("bob").public();
+    let paris = article(1);
+    let version = Article::new(paris.clone(), ArticleVersion(0));
+
+    // only versions which exist are voted on
+    assert_noop!(
+      Votes::upvote(
+        RawOrigin::Signed(bob).into(),
+        Article::new(Title::new(b"rome".to_vec()).unwrap(), ArticleVersion(0))
+      ),
+      pallet::Error::<Test>::InvalidTitle
+    );
+    assert_noop!(
+      Votes::upvote(RawOrigin::Signed(bob).into(), Article::new(paris.clone(), ArticleVersion(1))),
+      pallet::Error::<Test>::InvalidVersion
+    );
+
+    // authors are rewarded for the votes on their versions, so they can't cast them
+    let author = insecure_pair_from_name("author").public();
+    assert_noop!(
+      Votes::upvote(RawOrigin::Signed(author).into(), version.clone()),
+      pallet::Error::<Test>::SelfVote
+    );
+
+    // every account votes once on every version
+    assert_ok!(Votes::upvote(RawOrigin::Signed(bob).into(), version.clone()));
+    System::assert_last_event(RuntimeEvent::Votes(pallet::Event::Upvoted {
+      article: version.clone(),
+      voter: bob,
+    }));
+    assert_noop!(
+      Votes::upvote(RawOrigin::Signed(bob).into(), version.clone()),
+      pallet::Error::<Test>::AlreadyVoted
+    );
+    assert_ok!(Votes::upvote(RawOrigin::Signed(alice).into(), version.clone()));
+    assert_eq!(Votes::upvotes(&version), 2);
+    assert_eq!(Votes::score(&version), 2);
+
+    assert_eq!(Votes::vote(bob, &version), Some(Vote::Up));
+    assert_eq!(Votes::vote_weight(bob, &version), Some(1));
+    assert_eq!(Votes::votes(bob, None, 10), vec![version.clone()]);
+    assert_eq!(Votes::votes(bob, Some(version), 10), vec![]);
+  })
+}
+
+#[test]
+fn retract_vote() {
+  new_test_ext().execute_with(|| {
+    let bob = insecure_pair_from_name("bob").public();
+    let paris = article(1);
+    let version = Article::new(paris, ArticleVersion(0));
+
+    assert_noop!(
+      Votes::retract_vote(RawOrigin::Signed(bob).into(), version.clone()),
+      pallet::Error::<Test>::NotVoted
+    );
+    assert_ok!(Votes::upvote(RawOrigin::Signed(bob).into(), version.clone()));
+    assert_ok!(Votes::retract_vote(RawOrigin::Signed(bob).into(), version.clone()));
+    System::assert_last_event(RuntimeEvent::Votes(pallet::Event::VoteRetracted {
+      article: version.clone(),
+      voter: bob,
+    }));
+    assert_eq!(Votes::upvotes(&version), 0);
+    assert_eq!(Votes::score(&version), 0);
+    assert_eq!(Votes::vote(bob, &version), None);
+
+    // a retracted vote may be cast again
+    assert_ok!(Votes::upvote(RawOrigin::Signed(bob).into(), version.clone()));
+    assert_eq!(Votes::upvotes(&version), 1);
+  })
+}
+
+#[test]
+fn downvote() {
+  new_test_ext().execute_with(|| {
+    let bob = insecure_pair_from_name("bob").public();
+    let paris = article(1);
+    let version = Article::new(paris, ArticleVersion(0));
+
+    // a downvote replaces the upvote of the account
+    assert_ok!(Votes::upvote(RawOrigin::Signed(bob).into(), version.clone()));
+    assert_ok!(Votes::downvote(RawOrigin::Signed(bob).into(), version.clone()));
+    System::assert_last_event(RuntimeEvent::Votes(pallet::Event::Downvoted {
+      article: version.clone(),
+      voter: bob,
+    }));
+    assert_noop!(
+      Votes::downvote(RawOrigin::Signed(bob).into(), version.clone()),
+      pallet::Error::<Test>::AlreadyVoted
+    );
+    assert_eq!(Votes::upvotes(&version), 0);
+    assert_eq!(Votes::downvotes(&version), 1);
+    assert_eq!(Votes::score(&version), -1);
+    assert_eq!(Votes::vote(bob, &version), Some(Vote::Down));
+
+    // and retracting it raises the score back
+    assert_ok!(Votes::retract_vote(RawOrigin::Signed(bob).into(), version.clone()));
+    assert_eq!(Votes::downvotes(&version), 0);
+    assert_eq!(Votes::score(&version), 0);
+  })
+}
+
+#[test]
+fn vote_with_stake() {
+  new_test_ext().execute_with(|| {
+    let bob = insecure_pair_from_name("bob").public();
+    let paris = article(1);
+    let version = Article::new(paris, ArticleVersion(0));
+    let balance = Coins::balances(bob).unwrap();
+
+    assert_noop!(
+      Votes::vote_with_stake(
+        RawOrigin::Signed(bob).into(),
+        version.clone(),
+        Vote::Up,
+        0,
+        Conviction::Locked1x
+      ),
+      pallet::Error::<Test>::InvalidStake
+    );
+
+    // the coins weigh the vote for every whole coin, times the conviction
+    let amount = 10 * VOTE_WEIGHT_UNIT;
+    assert_ok!(Votes::vote_with_stake(
+      RawOrigin::Signed(bob).into(),
+      version.clone(),
+      Vote::Up,
+      amount,
+      Conviction::Locked3x
+    ));
+    let until = 1 + Conviction::Locked3x.lock_period();
+    System::assert_last_event(RuntimeEvent::Votes(pallet::Event::StakeLocked {
+      voter: bob,
+      amount,
+      weight: 30,
+      until,
+    }));
+    assert_eq!(Votes::upvotes(&version), 30);
+    assert_eq!(Votes::score(&version), 30);
+    assert_eq!(Votes::vote_weight(bob, &version), Some(30));
+    assert_eq!(Coins::held(bob, VOTES_HOLD), Some(amount));
+    assert_eq!(Coins::balances(bob), Some(balance - amount));
+    assert_eq!(Votes::unlocks(until, bob), amount);
+
+    // a staked vote may replace a vote in the same direction, replacing its weight
+    assert_ok!(Votes::vote_with_stake(
+      RawOrigin::Signed(bob).into(),
+      version.clone(),
+      Vote::Up,
+      amount,
+      Conviction::Locked1x
+    ));
+    assert_eq!(Votes::upvotes(&version), 10);
+    assert_eq!(Coins::held(bob, VOTES_HOLD), Some(2 * amount));
+
+    // while a vote without coins weighs 1
+    assert_ok!(Votes::downvote(RawOrigin::Signed(bob).into(), version.clone()));
+    assert_eq!(Votes::upvotes(&version), 0);
+    assert_eq!(Votes::downvotes(&version), 1);
+    assert_eq!(Votes::vote_weight(bob, &version), Some(1));
+
+    // the account can't stake more than it has
+    assert!(Votes::vote_with_stake(
+      RawOrigin::Signed(bob).into(),
+      version,
+      Vote::Up,
+      balance,
+      Conviction::Locked1x
+    )
+    .is_err());
+  })
+}
+
+#[test]
+fn unlocks() {
+  new_test_ext().execute_with(|| {
+    let bob = insecure_pair_from_name("bob").public();
+    let carol = insecure_pair_from_name("carol").public();
+    let paris = article(2);
+    let version = |version: u32| Article::new(paris.clone(), ArticleVersion(version));
+    let balance = Coins::balances(bob).unwrap();
+    let stake = |voter: Public, version: Article, conviction: Conviction| {
+      Votes::vote_with_stake(
+        RawOrigin::Signed(voter).into(),
+        version,
+        Vote::Up,
+        10 * VOTE_WEIGHT_UNIT,
+        conviction,
+      )
+    };
+
+    let amount = 10 * VOTE_WEIGHT_UNIT;
+    assert_ok!(stake(bob, version(0), Conviction::Locked1x));
+    assert_ok!(stake(bob, version(1), Conviction::Locked2x));
+    // a vote replaced with a longer stake keeps counting once the coins of the first are released
+    assert_ok!(stake(carol, version(0), Conviction::Locked1x));
+    assert_ok!(stake(carol, version(0), Conviction::Locked2x));
+    let first = 1 + Conviction::Locked1x.lock_period();
+    let second = 1 + Conviction::Locked2x.lock_period();
+    assert_eq!(Coins::held(bob, VOTES_HOLD), Some(2 * amount));
+    assert_eq!(Votes::score(version(0)), 30);
+    assert_eq!(Votes::score(version(1)), 20);
+    assert_eq!(Votes::canonical_version(&paris), Some(ArticleVersion(0)));
+
+    // the coins are held, and counted, until their lock period ends
+    Votes::on_initialize(first - 1);
+    assert_eq!(Coins::held(bob, VOTES_HOLD), Some(2 * amount));
+    assert_eq!(Votes::score(version(0)), 30);
+
+    // after which the votes weigh as votes without coins
+    Votes::on_initialize(first);
+    System::assert_has_event(RuntimeEvent::Votes(pallet::Event::StakeUnlocked {
+      voter: bob,
+      amount,
+    }));
+    assert_eq!(Votes::unlocks(first, bob), 0);
+    assert_eq!(Coins::held(bob, VOTES_HOLD), Some(amount));
+    assert_eq!(Coins::held(carol, VOTES_HOLD), Some(amount));
+    assert_eq!(Coins::balances(bob), Some(balance - amount));
+    assert_eq!(Votes::vote_weight(bob, &version(0)), Some(1));
+    assert_eq!(Votes::vote_weight(carol, &version(0)), Some(20));
+    assert_eq!(Votes::upvotes(version(0)), 21);
+    assert_eq!(Votes::score(version(0)), 21);
+
+    // which may take the version under the quorum
+    for block in (first + 1) ..= second {
+      Votes::on_initialize(block);
+    }
+    assert_eq!(Coins::held(bob, VOTES_HOLD), None);
+    assert_eq!(Coins::balances(bob), Some(balance));
+    assert_eq!(Votes::score(version(0)), 2);
+    assert_eq!(Votes::score(version(1)), 1);
+    System::assert_last_event(RuntimeEvent::Votes(pallet::Event::CanonicalVersionChanged {
+      title: paris.clone(),
+      version: None,
+    }));
+    assert!(pallet::StakedVotes::<Test>::iter().next().is_none());
+  })
+}
+
+#[test]
+fn carried_over_expirations() {
+  new_test_ext().execute_with(|| {
+    let paris = article(1);
+    let version = Article::new(paris, ArticleVersion(0));
+    let voters = (0 .. MAX_EXPIRATIONS)
+      .map(|i| {
+        let mut voter = [0xff; 32];
+        voter[.. 4].copy_from_slice(&i.to_le_bytes());
+        Public::from_raw(voter)
+      })
+      .collect::<Vec<_>>();
+    for voter in &voters {
+      assert_ok!(Coins::mint(*voter, VOTE_WEIGHT_UNIT));
+      assert_ok!(Votes::vote_with_stake(
+        RawOrigin::Signed(*voter).into(),
+        version.clone(),
+        Vote::Up,
+        VOTE_WEIGHT_UNIT,
+        Conviction::Locked2x
+      ));
+    }
+    let until = 1 + Conviction::Locked2x.lock_period();
+    assert_eq!(Votes::score(&version), 2 * i64::from(MAX_EXPIRATIONS));
+
+    // every vote releases coins and expires, so only so many of them are handled in the block
+    Votes::on_initialize(until - 1);
+    Votes::on_initialize(until);
+    let unlocked = voters.iter().filter(|voter| Votes::unlocks(until, **voter) == 0).count();
+    assert_eq!(unlocked, usize::try_from(MAX_EXPIRATIONS - 1).unwrap());
+    assert_eq!(pallet::NextExpiry::<Test>::get(), Some(until));
+
+    // the rest are handled in the blocks after
+    Votes::on_initialize(until + 1);
+    Votes::on_initialize(until + 2);
+    for voter in &voters {
+      assert_eq!(Coins::held(*voter, VOTES_HOLD), None);
+      assert_eq!(Votes::vote_weight(*voter, &version), Some(1));
+    }
+    assert_eq!(Votes::score(&version), i64::from(MAX_EXPIRATIONS));
+    assert!(pallet::StakedVotes::<Test>::iter().next().is_none());
+    assert_eq!(pallet::NextExpiry::<Test>::get(), Some(until + 3));
+  })
+}
+
+#[test]
+fn canonical_version() {
+  new_test_ext().execute_with(|| {
+    let alice = insecure_pair_from_name("alice").public();
+    let bob = insecure_pair_from_name("bob").public();
+    let carol = insecure_pair_from_name("carol").public();
+    let paris = article(3);
+    let upvote = |voter: Public, version: u32| {
+      Votes::upvote(
+        RawOrigin::Signed(voter).into(),
+        Article::new(paris.clone(), ArticleVersion(version)),
+      )
+    };
+
+    // the last version stands in until a version reaches the quorum
+    assert_ok!(upvote(alice, 1));
+    assert_ok!(upvote(bob, 1));
+    assert_eq!(pallet::CanonicalVersion::<Test>::get(&paris), None);
+    assert_eq!(Votes::canonical_version(&paris), Some(ArticleVersion(2)));
+
+    assert_ok!(upvote(carol, 1));
+    System::assert_last_event(RuntimeEvent::Votes(pallet::Event::Upvoted {
+      article: Article::new(paris.clone(), ArticleVersion(1)),
+      voter: carol,
+    }));
+    assert_eq!(Votes::canonical_version(&paris), Some(ArticleVersion(1)));
+    assert!(System::events().iter().any(|record| record.event ==
+      RuntimeEvent::Votes(pallet::Event::CanonicalVersionChanged {
+        title: paris.clone(),
+        version: Some(ArticleVersion(1)),
+      })));
+
+    // the latest version wins ties
+    assert_ok!(upvote(alice, 0));
+    assert_ok!(upvote(bob, 0));
+    assert_ok!(upvote(carol, 0));
+    assert_eq!(Votes::canonical_version(&paris), Some(ArticleVersion(1)));
+    assert_ok!(upvote(alice, 2));
+    assert_ok!(upvote(bob, 2));
+    assert_ok!(upvote(carol, 2));
+    assert_eq!(Votes::canonical_version(&paris), Some(ArticleVersion(2)));
+
+    // and versions fall under the quorum once their votes are retracted
+    for version in 0 ..= 2 {
+      assert_ok!(Votes::downvote(
+        RawOrigin::Signed(carol).into(),
+        Article::new(paris.clone(), ArticleVersion(version))
+      ));
+    }
+    assert_eq!(pallet::CanonicalVersion::<Test>::get(&paris), None);
+    assert_eq!(Votes::canonical_version(&paris), Some(ArticleVersion(2)));
+  })
+}
+
+#[test]
+fn canonical_window() {
+  new_test_ext().execute_with(|| {
+    let author = insecure_pair_from_name("author").public();
+    let paris = article(1);
+    let first = Article::new(paris.clone(), ArticleVersion(0));
+    for voter in ["alice", "bob", "carol"] {
+      let voter = insecure_pair_from_name(voter).public();
+      assert_ok!(Votes::upvote(RawOrigin::Signed(voter).into(), first.clone()));
+    }
+    assert_eq!(Votes::canonical_version(&paris), Some(ArticleVersion(0)));
+
+    // the canonical version stands while it's among the latest versions
+    let add_version = |version: u32| {
+      Articles::add_version(
+        RawOrigin::Signed(author).into(),
+        paris.clone(),
+        None,
+        ArticleVersion(version - 1),
+        false,
+        Script::new(vec![OpCode::Add(Body::new(b"body".to_vec()).unwrap())]).unwrap(),
+      )
+    };
+    for version in 1 .. CANONICAL_WINDOW {
+      assert_ok!(add_version(version));
+    }
+    assert_eq!(pallet::CanonicalVersion::<Test>::get(&paris), Some(ArticleVersion(0)));
+
+    // and is selected anew once a new version pushes it out of them
+    assert_ok!(add_version(CANONICAL_WINDOW));
+    System::assert_has_event(RuntimeEvent::Votes(pallet::Event::CanonicalVersionChanged {
+      title: paris.clone(),
+      version: None,
+    }));
+    assert_eq!(Votes::canonical_version(&paris), Some(ArticleVersion(CANONICAL_WINDOW)));
+  })
+}
+
+#[test]
+fn voted_versions() {
+  new_test_ext().execute_with(|| {
+    let author = insecure_pair_from_name("author").public();
+    let alice = insecure_pair_from_name("alice").public();
+    let bob = insecure_pair_from_name("bob").public();
+    let carol = insecure_pair_from_name("carol").public();
+    let paris = article(2);
+    let version = |version: u32| Article::new(paris.clone(), ArticleVersion(version));
+    let vote = |voter: Public, version: Article, vote: Vote| match vote {
+      Vote::Up => Votes::upvote(RawOrigin::Signed(voter).into(), version),
+      Vote::Down => Votes::downvote(RawOrigin::Signed(voter).into(), version),
+    };
+
+    // the score gained before the session isn't rewarded again
+    assert_ok!(vote(alice, version(0), Vote::Up));
+    assert_ok!(vote(bob, version(0), Vote::Up));
+    Session::set(1);
+    assert_ok!(vote(carol, version(0), Vote::Up));
+    assert_ok!(vote(alice, version(1), Vote::Up));
+    assert_ok!(vote(bob, version(1), Vote::Up));
+    // and neither is score gained then lost within it
+    assert_ok!(vote(carol, version(1), Vote::Up));
+    assert_ok!(Votes::retract_vote(RawOrigin::Signed(carol).into(), version(1)));
+    assert_eq!(Votes::take_session_gains(0), 2);
+    assert_eq!(Votes::take_session_gains(1), 3);
+    assert_eq!(Votes::take_session_gains(1), 0);
+
+    // every version voted on is taken, a bounded amount at a time
+    let mut taken = Votes::take_voted_versions(1, 1);
+    assert_eq!(taken.len(), 1);
+    taken.extend(Votes::take_voted_versions(1, 2));
+    taken.sort_by_key(|(article, _, _)| article.version().0);
+    assert_eq!(taken, vec![(version(0), author, 1), (version(1), author, 2)]);
+    assert!(Votes::take_voted_versions(1, 2).is_empty());
+    assert_eq!(Votes::take_voted_versions(0, 2), vec![(version(0), author, 2)]);
+  })
+}
+
+#[test]
+fn alt_farming() {
+  new_test_ext().execute_with(|| {
+    let author = insecure_pair_from_name("author").public();
+    let alt = insecure_pair_from_name("alt").public();
+    let bob = insecure_pair_from_name("bob").public();
+    let carol = insecure_pair_from_name("carol").public();
+    let paris = article(1);
+    assert_ok!(Articles::add_version(
+      RawOrigin::Signed(alt).into(),
+      paris.clone(),
+      None,
+      ArticleVersion(0),
+      false,
+      Script::new(vec![OpCode::Add(Body::new(b"alt".to_vec()).unwrap())]).unwrap()
+    ));
+    let version = |version: u32| Article::new(paris.clone(), ArticleVersion(version));
+
+    // the coins of an account weigh its vote on the version of an alt account
+    assert_ok!(Votes::vote_with_stake(
+      RawOrigin::Signed(bob).into(),
+      version(1),
+      Vote::Up,
+      100 * VOTE_WEIGHT_UNIT,
+      Conviction::Locked3x
+    ));
+    assert_ok!(Votes::vote_with_stake(
+      RawOrigin::Signed(bob).into(),
+      version(1),
+      Vote::Up,
+      100 * VOTE_WEIGHT_UNIT,
+      Conviction::Locked3x
+    ));
+    assert_ok!(Votes::upvote(RawOrigin::Signed(carol).into(), version(0)));
+    assert_eq!(Votes::score(version(1)), 300);
+
+    // but the alt is rewarded for the account, as the author of a version upvoted without coins
+    let until = 1 + Conviction::Locked3x.lock_period();
+    Votes::on_initialize(until);
+    assert_eq!(Votes::take_session_gains(0), 2);
+    let mut taken = Votes::take_voted_versions(0, 2);
+    taken.sort_by_key(|(article, _, _)| article.version().0);
+    assert_eq!(taken, vec![(version(0), author, 1), (version(1), alt, 1)]);
+  })
+}
+
+#[test]
+fn migrate_voted_versions() {
+  new_test_ext().execute_with(|| {
+    // the versions voted on were kept in a counted map before v4
+    let key = frame_support::storage::storage_prefix(b"Votes", b"VotedVersions");
+    frame_support::storage::unhashed::put(&[key.as_slice(), &[0; 8]].concat(), &());
+    let counter = frame_support::storage::storage_prefix(b"Votes", b"CounterForVotedVersions");
+    frame_support::storage::unhashed::put(&counter, &1u32);
+    StorageVersion::new(3).put::<Votes>();
+
+    Votes::on_runtime_upgrade();
+    assert_eq!(StorageVersion::get::<Votes>(), StorageVersion::new(4));
+    assert!(pallet::VotedVersions::<Test>::iter().next().is_none());
+    assert!(!frame_support::storage::unhashed::exists(&counter));
+  })
+}
+
+#[test]
+fn migrate_canonical_versions() {
+  new_test_ext().execute_with(|| {
+    let paris = article(2);
+
+    // the scores were kept without selecting a canonical version before v3
+    pallet::Scores::<Test>::set(Article::new(paris.clone(), ArticleVersion(0)), 5);
+    pallet::Scores::<Test>::set(Article::new(paris.clone(), ArticleVersion(1)), 1);
+    StorageVersion::new(2).put::<Votes>();
+
+    Votes::on_runtime_upgrade();
+    assert_eq!(StorageVersion::get::<Votes>(), StorageVersion::new(4));
+    assert_eq!(pallet::CanonicalVersion::<Test>::get(&paris), Some(ArticleVersion(0)));
+  })
+}
+
+#[test]
+fn migrate_upvotes() {
+  new_test_ext().execute_with(|| {
+    let paris = article(1);
+    let version = Article::new(paris, ArticleVersion(0));
+
+    // upvotes were only counted before v1
+    pallet::Upvotes::<Test>::set(&version, 5);
+    StorageVersion::new(0).put::<Votes>();
+
+    Votes::on_runtime_upgrade();
+    assert_eq!(StorageVersion::get::<Votes>(), StorageVersion::new(4));
+    assert_eq!(Votes::upvotes(&version), 5);
+    assert_eq!(Votes::score(&version), 5);
+  })
+}
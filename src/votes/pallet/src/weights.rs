@@ -0,0 +1,126 @@
+//! Weights for the Votes pallet.
+//!
+//! Generated by running the benchmarks in `benchmarking.rs` natively in the mock runtime of the
+//! pallet, over 10 steps of every component repeated 5 times, with a state backend counting the
+//! storage accesses as the benchmarking state of the node does. The execution times and proof sizes
+//! are of native code over the in-memory trie of the mock, not of the wasm runtime on the reference
+//! hardware, so they are to be replaced by running `wikiblocks-node benchmark pallet --chain dev
+//! --pallet votes --extrinsic '*' --steps 50 --repeat 20 --output src/votes/pallet/src/weights.rs`
+//! on it.
+
+use core::marker::PhantomData;
+
+use frame_support::{
+  traits::Get,
+  weights::{constants::RocksDbWeight, Weight},
+};
+
+pub trait WeightInfo {
+  fn upvote() -> Weight;
+  fn retract_vote() -> Weight;
+  fn downvote() -> Weight;
+  fn vote_with_stake() -> Weight;
+  fn expire(u: u32, e: u32) -> Weight;
+}
+
+/// Weights for the Votes pallet, using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+  // Reads: Articles::Authors, Articles::LastVersion, Articles::Titles, CanonicalVersion, Downvotes,
+  // Scores (16 keys), SessionGains, Upvotes, VoteWeights, VotedVersions, Voters
+  // Writes: Downvotes, Scores, SessionGains, Upvotes, VoteWeights, VotedVersions, Voters
+  fn upvote() -> Weight {
+    Weight::from_parts(242_028_000, 6_632)
+      .saturating_add(T::DbWeight::get().reads(26))
+      .saturating_add(T::DbWeight::get().writes(7))
+  }
+
+  // Reads: Articles::LastVersion, Articles::Titles, CanonicalVersion, Scores (16 keys),
+  // SessionGains, Upvotes, VoteWeights, VotedVersions, Voters
+  // Writes: Scores, SessionGains, Upvotes, VotedVersions, Voters
+  fn retract_vote() -> Weight {
+    Weight::from_parts(152_443_000, 5_079)
+      .saturating_add(T::DbWeight::get().reads(24))
+      .saturating_add(T::DbWeight::get().writes(5))
+  }
+
+  // Reads: Articles::Authors, Articles::LastVersion, Articles::Titles, CanonicalVersion, Downvotes,
+  // Scores (16 keys), SessionGains, Upvotes, VoteWeights, VotedVersions, Voters
+  // Writes: Downvotes, Scores, SessionGains, Upvotes, VoteWeights, VotedVersions, Voters
+  fn downvote() -> Weight {
+    Weight::from_parts(247_402_000, 6_632)
+      .saturating_add(T::DbWeight::get().reads(26))
+      .saturating_add(T::DbWeight::get().writes(7))
+  }
+
+  // Reads: Articles::Authors, Articles::LastVersion, Articles::Titles, Coins::Balances,
+  // Coins::Holds, CanonicalVersion, Downvotes, Scores (16 keys), SessionGains, Unlocks, Upvotes,
+  // VoteWeights, VotedVersions, Voters
+  // Writes: Coins::Balances, Coins::Holds, Downvotes, Scores, SessionGains, StakedVotes, Unlocks,
+  // Upvotes, VoteWeights, VotedVersions, Voters
+  fn vote_with_stake() -> Weight {
+    Weight::from_parts(271_030_000, 6_925)
+      .saturating_add(T::DbWeight::get().reads(29))
+      .saturating_add(T::DbWeight::get().writes(11))
+  }
+
+  // Reads: Articles::LastVersion (256 keys), Articles::TitlesByIndex (256 keys), Coins::Balances
+  // (256 keys), Coins::Holds (256 keys), CanonicalVersion (256 keys), Scores (4096 keys),
+  // SessionGains, StakedVotes (257 keys), Unlocks (257 keys), Upvotes (256 keys), VoteWeights (256
+  // keys), VotedVersions (256 keys), Voters (256 keys)
+  // Writes: Coins::Balances (256 keys), Coins::Holds (256 keys), Scores (256 keys), SessionGains,
+  // StakedVotes (256 keys), Unlocks (256 keys), Upvotes (256 keys), VoteWeights (256 keys),
+  // VotedVersions (256 keys)
+  // The range of component `u` is `[0, 256]`.
+  // The range of component `e` is `[0, 256]`.
+  fn expire(u: u32, e: u32) -> Weight {
+    Weight::from_parts(6_688_097_000, 611)
+      .saturating_add(Weight::from_parts(11_544_560, 219).saturating_mul(u.into()))
+      .saturating_add(Weight::from_parts(67_971_157, 416).saturating_mul(e.into()))
+      .saturating_add(T::DbWeight::get().reads(2))
+      .saturating_add(T::DbWeight::get().reads(3).saturating_mul(u.into()))
+      .saturating_add(T::DbWeight::get().reads(24).saturating_mul(e.into()))
+      .saturating_add(T::DbWeight::get().writes(1))
+      .saturating_add(T::DbWeight::get().writes(3).saturating_mul(u.into()))
+      .saturating_add(T::DbWeight::get().writes(5).saturating_mul(e.into()))
+  }
+}
+
+// For tests, and backwards compatibility
+impl WeightInfo for () {
+  fn upvote() -> Weight {
+    Weight::from_parts(242_028_000, 6_632)
+      .saturating_add(RocksDbWeight::get().reads(26))
+      .saturating_add(RocksDbWeight::get().writes(7))
+  }
+
+  fn retract_vote() -> Weight {
+    Weight::from_parts(152_443_000, 5_079)
+      .saturating_add(RocksDbWeight::get().reads(24))
+      .saturating_add(RocksDbWeight::get().writes(5))
+  }
+
+  fn downvote() -> Weight {
+    Weight::from_parts(247_402_000, 6_632)
+      .saturating_add(RocksDbWeight::get().reads(26))
+      .saturating_add(RocksDbWeight::get().writes(7))
+  }
+
+  fn vote_with_stake() -> Weight {
+    Weight::from_parts(271_030_000, 6_925)
+      .saturating_add(RocksDbWeight::get().reads(29))
+      .saturating_add(RocksDbWeight::get().writes(11))
+  }
+
+  fn expire(u: u32, e: u32) -> Weight {
+    Weight::from_parts(6_688_097_000, 611)
+      .saturating_add(Weight::from_parts(11_544_560, 219).saturating_mul(u.into()))
+      .saturating_add(Weight::from_parts(67_971_157, 416).saturating_mul(e.into()))
+      .saturating_add(RocksDbWeight::get().reads(2))
+      .saturating_add(RocksDbWeight::get().reads(3).saturating_mul(u.into()))
+      .saturating_add(RocksDbWeight::get().reads(24).saturating_mul(e.into()))
+      .saturating_add(RocksDbWeight::get().writes(1))
+      .saturating_add(RocksDbWeight::get().writes(3).saturating_mul(u.into()))
+      .saturating_add(RocksDbWeight::get().writes(5).saturating_mul(e.into()))
+  }
+}
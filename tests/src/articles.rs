@@ -1,5 +1,7 @@
 use crate::{wikiblocks_test, publish_tx};
 
+use scale::Encode;
+
 use wikiblocks_abi::primitives::{
   insecure_pair_from_name, Article, ArticleVersion, Body, OpCode, Script, Title,
 };
@@ -25,21 +27,40 @@ async fn test_add_article(wikiblocks: Wikiblocks) {
 
   // send the tx
   let script = Script::new(script).unwrap();
-  let tx =
-    wikiblocks.sign(&signer, WikiblocksArticles::add_article(title.clone(), script.clone()), 0, 0);
+  let tx = wikiblocks.sign(
+    &signer,
+    WikiblocksArticles::add_article(title.clone(), script.clone(), None),
+    0,
+    0,
+  );
   publish_tx(&wikiblocks, &tx).await;
 
+  // the node indexes the article for search once it's finalized
+  let canonical = WikiblocksArticles::canonical(&title).unwrap();
+  let mut found = vec![];
+  for _ in 0 .. 10 {
+    found = wikiblocks.search_titles("my first", 10).await.unwrap();
+    if !found.is_empty() {
+      break;
+    }
+    tokio::time::sleep(core::time::Duration::from_secs(1)).await;
+  }
+  assert_eq!(found, vec![canonical.clone()]);
+  assert_eq!(wikiblocks.search_titles("My First Art", 10).await.unwrap(), vec![canonical.clone()]);
+  assert_eq!(wikiblocks.search("FIRST body", 10).await.unwrap(), vec![canonical]);
+  assert!(wikiblocks.search("second", 10).await.unwrap().is_empty());
+
   // read back
-  let result = wikiblocks
-    .as_of_latest_finalized_block()
-    .await
-    .unwrap()
-    .articles()
-    .article(Article::new(title, ArticleVersion(0)))
-    .await
-    .unwrap()
-    .unwrap();
+  let wikiblocks = wikiblocks.as_of_latest_finalized_block().await.unwrap();
+  let article = Article::new(title, ArticleVersion(0));
+  let result = wikiblocks.articles().article(article.clone()).await.unwrap().unwrap();
 
   // check they are equal
   assert_eq!(result, script);
+
+  // the script is also indexed along with the extrinsic which added it, under its hash
+  let (hash, len) = wikiblocks.articles().script_hash(article).await.unwrap().unwrap();
+  assert_eq!(hash, sp_core::hashing::blake2_256(&tx.encode()));
+  assert_eq!(usize::try_from(len).unwrap(), tx.encode().len());
+  assert_eq!(wikiblocks.articles().indexed_script(hash).await.unwrap(), Some(script));
 }